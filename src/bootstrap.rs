@@ -0,0 +1,184 @@
+use std::time::{Duration, SystemTime};
+
+use namada_sdk::{
+    address::Address,
+    args::{InputAmount, TxBuilder, TxTransparentTransferData},
+    io::NullIo,
+    key::{common::SecretKey, RefTo, SchemeType},
+    masp::fs::FsShieldedUtils,
+    signing::default_sign,
+    token::{self, DenominatedAmount},
+    wallet::fs::FsWalletUtils,
+    Namada,
+};
+use rand::rngs::OsRng;
+use serde::Serialize;
+use tempfile::tempdir;
+use tendermint_rpc::Client;
+use thiserror::Error;
+use tokio::time::sleep;
+
+use crate::{
+    config::AppConfig,
+    report::unix_millis,
+    sdk::{namada::Sdk, rpc::RpcPool},
+};
+
+#[derive(Error, Debug)]
+pub enum BootstrapError {
+    #[error("can't build the funding tx: {0}")]
+    Build(String),
+    #[error("can't submit the funding tx: {0}")]
+    Submit(String),
+    #[error("funding tx {0} was rejected")]
+    Rejected(String),
+}
+
+/// Outcome of the bootstrap phase, shared by the reports of every worker.
+#[derive(Clone, Debug, Serialize)]
+pub struct BootstrapReport {
+    pub faucet: String,
+    /// native tokens transferred to each sub-faucet
+    pub budget: u64,
+    pub tx_hash: String,
+    /// address of the sub-faucet of each worker, by worker id
+    pub sub_faucets: Vec<String>,
+    /// unix timestamp in milliseconds
+    pub started_at: Option<u128>,
+    pub duration_ms: u128,
+}
+
+/// Sub-faucets funded by the faucet, one per worker, so that the workers
+/// don't compete for the balance and the fees of a single account.
+#[derive(Clone, Debug)]
+pub struct Bootstrap {
+    pub report: BootstrapReport,
+    faucet_sks: Vec<SecretKey>,
+}
+
+impl Bootstrap {
+    /// The secret key the worker signs with instead of the faucet.
+    pub fn faucet_sk(&self, worker_id: u64) -> String {
+        self.faucet_sks[worker_id as usize].to_string()
+    }
+}
+
+/// Generates a key per worker and funds each implicit account with `budget`
+/// native tokens from the faucet, in a single tx.
+pub async fn bootstrap(config: &AppConfig, budget: u64) -> Result<Bootstrap, BootstrapError> {
+    // removed once the sub-faucets are funded
+    let temp_dir = tempdir().expect("Temporary directory should be created.");
+    let base_dir = temp_dir.path().to_path_buf();
+    let rpc_pool = RpcPool::new(&config.rpc, config.rpc_selection);
+
+    // Wait for the first 3 blocks
+    loop {
+        let latest_blocked = rpc_pool.latest_block().await;
+        if let Ok(block) = latest_blocked {
+            if block.block.header.height.value() > 2 {
+                break;
+            }
+        } else {
+            sleep(Duration::from_secs(10)).await;
+        }
+    }
+
+    let wallet = FsWalletUtils::new(base_dir.join("wallet"));
+    let shielded_ctx = FsShieldedUtils::new(base_dir.clone());
+    let sdk = Sdk::new(config, &base_dir, rpc_pool, wallet, shielded_ctx, NullIo).await;
+
+    let started_at = SystemTime::now();
+    sdk.reveal_faucet_pk().await;
+
+    let faucet_pk = sdk.find_public_key("faucet").await;
+    let faucet = Address::from(&faucet_pk);
+    let nam = sdk
+        .namada
+        .wallet
+        .read()
+        .await
+        .find_address("nam")
+        .expect("Native token should be present.")
+        .into_owned();
+
+    let mut faucet_sks = vec![];
+    let mut transfers = vec![];
+    for worker_id in 0..config.workers {
+        let (_, sk) = sdk
+            .namada
+            .wallet
+            .write()
+            .await
+            .gen_store_secret_key(
+                SchemeType::Ed25519,
+                Some(format!("faucet-{}", worker_id)),
+                true,
+                None,
+                &mut OsRng,
+            )
+            .expect("Sub-faucet key should be generated.");
+
+        transfers.push(TxTransparentTransferData {
+            source: faucet.clone(),
+            target: Address::from(&sk.ref_to()),
+            token: nam.clone(),
+            amount: InputAmount::Unvalidated(DenominatedAmount::native(
+                token::Amount::native_whole(budget),
+            )),
+        });
+        faucet_sks.push(sk);
+    }
+    let sub_faucets = transfers
+        .iter()
+        .map(|transfer| transfer.target.to_string())
+        .collect();
+
+    let transfer_tx_builder = sdk
+        .namada
+        .new_transparent_transfer(transfers)
+        .signing_keys(vec![faucet_pk.clone()])
+        .wrapper_fee_payer(faucet_pk);
+    let (mut transfer_tx, signing_data) = transfer_tx_builder
+        .build(&sdk.namada)
+        .await
+        .map_err(|e| BootstrapError::Build(e.to_string()))?;
+
+    sdk.namada
+        .sign(
+            &mut transfer_tx,
+            &transfer_tx_builder.tx,
+            signing_data,
+            default_sign,
+            (),
+        )
+        .await
+        .map_err(|e| BootstrapError::Build(e.to_string()))?;
+
+    let tx_hash = transfer_tx.header_hash().to_string();
+    let tx_response = sdk
+        .submit(transfer_tx.clone(), &transfer_tx_builder.tx)
+        .await
+        .map_err(|e| BootstrapError::Submit(e.to_string()))?;
+
+    let wrapper_hash = transfer_tx.wrapper_hash();
+    for commitment in &transfer_tx.header.batch {
+        if tx_response
+            .is_applied_and_valid(wrapper_hash.as_ref(), commitment)
+            .is_none()
+        {
+            return Err(BootstrapError::Rejected(tx_hash));
+        }
+    }
+
+    Ok(Bootstrap {
+        report: BootstrapReport {
+            faucet: faucet.to_string(),
+            budget,
+            tx_hash,
+            sub_faucets,
+            started_at: unix_millis(started_at),
+            duration_ms: started_at.elapsed().unwrap_or_default().as_millis(),
+        },
+        faucet_sks,
+    })
+}
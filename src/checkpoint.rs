@@ -0,0 +1,67 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{scenario::Scenario, state::state::Storage};
+
+const RUN_FILE: &str = "checkpoint-run.json";
+const STORAGE_FILE: &str = "checkpoint-storage.json";
+
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    #[error("can't access checkpoint `{0}`: {1}")]
+    Io(PathBuf, String),
+    #[error("invalid checkpoint `{0}`: {1}")]
+    Json(PathBuf, String),
+}
+
+/// What a run needs to be resumed from its work directory, along with the
+/// storage saved after every step, the wallet and the shielded context.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Checkpoint {
+    pub chain_id: String,
+    pub scenario_name: String,
+    pub scenario: Scenario,
+    pub try_index: u64,
+}
+
+impl Checkpoint {
+    pub fn load(dir: &Path) -> Result<Self, CheckpointError> {
+        read(&dir.join(RUN_FILE))
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), CheckpointError> {
+        write(&dir.join(RUN_FILE), self)
+    }
+
+    pub fn load_storage(dir: &Path) -> Result<Storage, CheckpointError> {
+        read(&dir.join(STORAGE_FILE))
+    }
+
+    pub fn save_storage(dir: &Path, storage: &Storage) -> Result<(), CheckpointError> {
+        write(&dir.join(STORAGE_FILE), storage)
+    }
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Result<T, CheckpointError> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| CheckpointError::Io(path.to_owned(), e.to_string()))?;
+
+    serde_json::from_str(&json).map_err(|e| CheckpointError::Json(path.to_owned(), e.to_string()))
+}
+
+// written to a temporary file first so a crash never leaves a partial checkpoint
+fn write<T: Serialize>(path: &Path, value: &T) -> Result<(), CheckpointError> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| CheckpointError::Json(path.to_owned(), e.to_string()))?;
+
+    let tmp_path = path.with_extension("tmp");
+    fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+        .and_then(|_| fs::write(&tmp_path, json))
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|e| CheckpointError::Io(path.to_owned(), e.to_string()))
+}
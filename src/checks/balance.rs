@@ -5,9 +5,15 @@ use async_trait::async_trait;
 use namada_sdk::rpc;
 use namada_sdk::token::Amount;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
-use crate::entity::address::{AccountIndentifier, ADDRESS_PREFIX};
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage, utils::value::Value};
+use crate::entity::address::AccountIndentifier;
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::state::Storage,
+    utils::value::{ParameterError, Resolver, Value},
+};
 
 use super::{Check, CheckParam};
 
@@ -40,7 +46,7 @@ impl Check for BalanceCheck {
         let current_balance = match balance {
             Ok(res) => res,
             Err(e) => {
-                println!("{}", e);
+                warn!(error = %e, "Can't query the balance");
                 return StepResult::fail_check(0.to_string(), previous_balance.to_string());
             }
         };
@@ -71,7 +77,7 @@ impl FromStr for Operation {
         match s.to_lowercase().as_str() {
             "ge" => Ok(Self::Ge),
             "le" => Ok(Self::Le),
-            _ => unimplemented!(),
+            _ => Err(format!("unknown operation `{}`", s)),
         }
     }
 }
@@ -95,75 +101,32 @@ pub struct BalanceCheckParameters {
 impl CheckParam for BalanceCheckParameters {
     type D = BalanceCheckParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                state.get_step_item(&value, &field).parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(token) = resolver.account("token", dto.token)? else {
+            return Ok(None);
         };
-        let address = match dto.address {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(amount) = resolver.token_amount("amount", dto.amount, &token)? else {
+            return Ok(None);
         };
-        let token = match dto.token {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(address) = resolver.account("address", dto.address)? else {
+            return Ok(None);
         };
         let op = match dto.op {
-            Value::Ref { .. } => unimplemented!(),
-            Value::Value { value } => Operation::from_str(&value).unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+            Value::Ref { .. } => return Err(ParameterError::ref_unsupported("op")),
+            Value::Value { value } => {
+                Operation::from_str(&value).map_err(|e| ParameterError::new("op", e))?
+            }
+            Value::Fuzz { .. } => return Err(ParameterError::fuzz_unsupported("op")),
+            Value::Expr { .. } => return Err(ParameterError::expr_unsupported("op")),
         };
 
-        Some(Self {
+        Ok(Some(Self {
             amount,
             address,
             token,
             op,
-        })
+        }))
     }
 }
@@ -3,8 +3,13 @@ use async_trait::async_trait;
 use namada_sdk::rpc;
 use serde::{Deserialize, Serialize};
 
-use crate::entity::address::{AccountIndentifier, ADDRESS_PREFIX};
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage, utils::value::Value};
+use crate::entity::address::AccountIndentifier;
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::state::Storage,
+    utils::value::{ParameterError, Resolver, Value},
+};
 
 use super::{Check, CheckParam};
 
@@ -69,69 +74,23 @@ pub struct BondsCheckParameters {
 impl CheckParam for BondsCheckParameters {
     type D = BondsCheckParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                state.get_step_item(&value, &field).parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(amount) = resolver.amount("amount", dto.amount)? else {
+            return Ok(None);
         };
-        let delegate = match dto.delegate {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(delegate) = resolver.account("delegate", dto.delegate)? else {
+            return Ok(None);
         };
-        let delegator = match dto.delegator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(delegator) = resolver.account("delegator", dto.delegator)? else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             amount,
             delegate,
             delegator,
-        })
+        }))
     }
 }
@@ -1,6 +1,11 @@
 use async_trait::async_trait;
 
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::{schema::OutputSchema, state::Storage},
+    utils::value::ParameterError,
+};
 
 pub mod balance;
 pub mod bonds;
@@ -12,6 +17,10 @@ pub mod storage;
 pub trait Check {
     type P: CheckParam;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::default()
+    }
+
     async fn execute(&self, sdk: &Sdk, paramaters: Self::P, state: &Storage) -> StepResult;
 
     async fn run(
@@ -20,24 +29,24 @@ pub trait Check {
         dto: <<Self as Check>::P as CheckParam>::D,
         state: &Storage,
         avoid_check: bool,
-    ) -> StepResult {
-        let parameters = if let Some(parameters) = Self::P::from_dto(dto, state) {
+    ) -> Result<StepResult, ParameterError> {
+        let parameters = if let Some(parameters) = Self::P::from_dto(dto, state)? {
             parameters
         } else if avoid_check {
-            return StepResult::skip_check(false);
+            return Ok(StepResult::skip_check(false));
         } else {
-            return StepResult::fail_check(
+            return Ok(StepResult::fail_check(
                 "couldn't parse parameters".to_string(),
                 "couldn parse parameters".to_string(),
-            );
+            ));
         };
 
         let outcome = self.execute(sdk, parameters, state).await;
 
         if avoid_check {
-            return StepResult::skip_check(outcome.is_succesful());
+            Ok(StepResult::skip_check(outcome.is_succesful()))
         } else {
-            return outcome;
+            Ok(outcome)
         }
     }
 }
@@ -45,5 +54,5 @@ pub trait Check {
 pub trait CheckParam: Sized {
     type D;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Option<Self>;
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError>;
 }
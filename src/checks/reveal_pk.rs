@@ -2,9 +2,15 @@ use async_trait::async_trait;
 
 use namada_sdk::rpc;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
-use crate::entity::address::{AccountIndentifier, ADDRESS_PREFIX};
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage, utils::value::Value};
+use crate::entity::address::AccountIndentifier;
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::state::Storage,
+    utils::value::{ParameterError, Resolver, Value},
+};
 
 use super::{Check, CheckParam};
 
@@ -27,7 +33,7 @@ impl Check for RevealPkCheck {
         let is_pk_revealed = rpc::is_public_key_revealed(&sdk.namada.client, &source_address).await;
 
         if let Err(e) = is_pk_revealed {
-            println!("{}", e);
+            warn!(error = %e, "Can't query whether the public key is revealed");
             StepResult::fail_check(false.to_string(), true.to_string())
         } else {
             StepResult::success_empty()
@@ -48,31 +54,13 @@ pub struct RevealPkCheckParameters {
 impl CheckParam for RevealPkCheckParameters {
     type D = RevealPkCheckParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
 
-        Some(Self { source })
+        Ok(Some(Self { source }))
     }
 }
@@ -1,7 +1,12 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage, utils::value::Value};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::state::Storage,
+    utils::value::{ParameterError, Value},
+};
 
 use super::{Check, CheckParam};
 
@@ -18,14 +23,14 @@ impl StepCheck {
 impl Check for StepCheck {
     type P = StepCheckParameters;
 
-    async fn execute(&self, _sdk: &Sdk, paramaters: Self::P, state: &Storage) -> StepResult {
-        let step_outcome =
-            state.is_step_successful(&paramaters.id) || state.is_step_noop(&paramaters.id);
-
-        if step_outcome.eq(&paramaters.outcome) {
+    async fn execute(&self, _sdk: &Sdk, paramaters: Self::P, _state: &Storage) -> StepResult {
+        if paramaters.step_outcome.eq(&paramaters.outcome) {
             StepResult::success_empty()
         } else {
-            StepResult::fail_check(step_outcome.to_string(), paramaters.outcome.to_string())
+            StepResult::fail_check(
+                paramaters.step_outcome.to_string(),
+                paramaters.outcome.to_string(),
+            )
         }
     }
 }
@@ -39,24 +44,38 @@ pub struct StepCheckParametersDto {
 #[derive(Clone, Debug)]
 pub struct StepCheckParameters {
     outcome: bool,
-    id: u64,
+    step_outcome: bool,
 }
 
 impl CheckParam for StepCheckParameters {
     type D = StepCheckParametersDto;
 
-    fn from_dto(dto: Self::D, _state: &Storage) -> Option<Self> {
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
         let outcome = match dto.outcome {
-            Value::Ref { .. } => unimplemented!(),
+            Value::Ref { .. } => return Err(ParameterError::ref_unsupported("outcome")),
             Value::Value { value } => value.eq("success"),
-            Value::Fuzz { .. } => unimplemented!(),
+            Value::Fuzz { .. } => return Err(ParameterError::fuzz_unsupported("outcome")),
+            Value::Expr { .. } => return Err(ParameterError::expr_unsupported("outcome")),
         };
         let id = match dto.id {
-            Value::Ref { .. } => unimplemented!(),
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+            Value::Ref { .. } => return Err(ParameterError::ref_unsupported("id")),
+            Value::Value { value } => value
+                .parse::<u64>()
+                .map_err(|e| ParameterError::new("id", e))?,
+            Value::Fuzz { .. } => return Err(ParameterError::fuzz_unsupported("id")),
+            Value::Expr { .. } => return Err(ParameterError::expr_unsupported("id")),
         };
 
-        Some(Self { outcome, id })
+        let step_outcome = state
+            .is_step_successful(&id)
+            .map_err(|e| ParameterError::new("id", e))?
+            || state
+                .is_step_noop(&id)
+                .map_err(|e| ParameterError::new("id", e))?;
+
+        Ok(Some(Self {
+            outcome,
+            step_outcome,
+        }))
     }
 }
@@ -1,7 +1,12 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage, utils::value::Value};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::state::Storage,
+    utils::value::{ParameterError, Resolver, Value},
+};
 
 use super::{Check, CheckParam};
 
@@ -18,13 +23,11 @@ impl StorageCheck {
 impl Check for StorageCheck {
     type P = StorageCheckParameters;
 
-    async fn execute(&self, _sdk: &Sdk, parameters: Self::P, state: &Storage) -> StepResult {
-        let data = state.get_step_item(&parameters.step, &parameters.field);
-
-        if data.eq(&parameters.value) {
+    async fn execute(&self, _sdk: &Sdk, parameters: Self::P, _state: &Storage) -> StepResult {
+        if parameters.data.eq(&parameters.value) {
             StepResult::success_empty()
         } else {
-            StepResult::fail_check(data, parameters.value)
+            StepResult::fail_check(parameters.data, parameters.value)
         }
     }
 }
@@ -38,29 +41,23 @@ pub struct StorageCheckParametersDto {
 
 #[derive(Clone, Debug)]
 pub struct StorageCheckParameters {
-    step: u64,
-    field: String,
+    data: String,
     value: String,
 }
 
 impl CheckParam for StorageCheckParameters {
     type D = StorageCheckParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let step = dto.step;
-        let field = dto.field;
-        let value = match dto.value {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                state.get_step_item(&value, &field)
-            }
-            Value::Value { value } => value,
-            Value::Fuzz { .. } => unimplemented!(),
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(data) = resolver.string("step", Value::r(dto.step, dto.field))? else {
+            return Ok(None);
+        };
+        let Some(value) = resolver.string("value", dto.value)? else {
+            return Ok(None);
         };
 
-        Some(Self { step, field, value })
+        Ok(Some(Self { data, value }))
     }
 }
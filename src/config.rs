@@ -1,11 +1,57 @@
-#[derive(clap::Parser, Clone)]
+use std::{net::SocketAddr, path::PathBuf};
+
+use crate::gen::TaskWeights;
+
+// the commit status body expected by the namada CI
+const DEFAULT_WEBHOOK_BODY: &str = r#"{"commit_sha":"{sha}","repo_owner":"anoma","repo":"namada","state":"{state}","description":"{artifacts_url}/{bucket}/{prefix}{report}.md","context":"Scenario {scenario}"}"#;
+
+#[derive(clap::Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub config: Option<AppConfig>,
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Check scenario files without connecting to a chain
+    Validate {
+        /// Scenario files to check, defaults to every file in the `scenarios` folder
+        scenarios: Vec<String>,
+    },
+    /// Generate scenarios and run them until stopped
+    Soak {
+        #[command(flatten)]
+        config: Box<AppConfig>,
+
+        #[command(flatten)]
+        soak: Box<SoakConfig>,
+    },
+}
+
+#[derive(clap::Args, Clone)]
 pub struct AppConfig {
     #[clap(long, env)]
     pub scenario: Option<String>,
 
+    #[clap(long, env, default_value = "scenarios")]
+    pub scenarios: String,
+
+    #[clap(long, env, value_enum, default_value_t = SelectionStrategy::Random)]
+    pub selection: SelectionStrategy,
+
     #[clap(long, env)]
+    pub selection_manifest: Option<PathBuf>,
+
+    #[clap(long, env, value_delimiter = ',')]
     #[arg(required = true)]
-    pub rpc: String,
+    pub rpc: Vec<String>,
+
+    #[clap(long, env, value_enum, default_value_t = RpcSelection::Sticky)]
+    pub rpc_selection: RpcSelection,
 
     #[clap(long, env)]
     #[arg(required = true)]
@@ -18,12 +64,62 @@ pub struct AppConfig {
     #[arg(required = true)]
     pub faucet_sk: String,
 
+    #[clap(long, env, conflicts_with_all = ["dry_run", "resume"])]
+    pub faucet_budget: Option<u64>,
+
     #[clap(long, env)]
     pub avoid_check: bool,
 
     #[clap(long, env)]
+    pub dry_run: bool,
+
+    #[clap(long, env, requires = "dry_run")]
+    pub dump_tx_dir: Option<PathBuf>,
+
+    #[clap(long, env, value_enum, default_value_t = InvalidScenarioPolicy::Abort)]
+    pub on_invalid_scenario: InvalidScenarioPolicy,
+
+    #[clap(long, env, value_enum, default_value_t = Schedule::Sequential)]
+    pub schedule: Schedule,
+
+    #[clap(long, env)]
+    pub seed: Option<u64>,
+
+    /// Directory the runs checkpoint to, a temporary one if not set
+    #[clap(long, env)]
+    pub work_dir: Option<PathBuf>,
+
+    /// Continue the interrupted run checkpointed to `--work-dir`
+    #[clap(long, env, requires = "work_dir")]
+    pub resume: bool,
+
+    #[clap(long, env, value_enum, default_value_t = LogFormat::Pretty)]
+    pub log_format: LogFormat,
+
+    #[clap(long, env, default_value = "info")]
+    pub log_level: String,
+
+    #[clap(long, env)]
+    pub metrics_addr: Option<SocketAddr>,
+
+    /// Defaults to `local`, plus `s3` and `webhook` if they are configured,
+    /// see `AppConfig::report_sinks`
+    #[clap(long, env, value_enum, value_delimiter = ',')]
+    pub report_sinks: Option<Vec<ReportSinkKind>>,
+
+    #[clap(long, env, default_value = "reports")]
+    pub report_dir: PathBuf,
+
+    #[clap(long, env)]
+    pub webhook_url: Option<String>,
+
+    /// Former name of `--webhook-url`, still read by older deployments
+    #[clap(long, env, hide = true)]
     pub report_url: Option<String>,
 
+    #[clap(long, env, default_value = DEFAULT_WEBHOOK_BODY)]
+    pub webhook_body: String,
+
     #[clap(long, env)]
     pub sha: Option<String>,
 
@@ -36,6 +132,133 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub minio_secret_key: Option<String>,
 
+    #[clap(long, env, default_value = "scenario-testing-results")]
+    pub minio_bucket: String,
+
+    #[clap(long, env)]
+    pub minio_prefix: Option<String>,
+
     #[clap(long, env)]
     pub artifacts_url: Option<String>,
 }
+
+impl AppConfig {
+    /// The sinks of `--report-sinks`. If it isn't set, the reports are copied
+    /// locally, uploaded if the MinIO credentials are set and the commit status
+    /// is posted if `--webhook-url` (or `--report-url`) is set, like before
+    /// the sinks existed.
+    pub fn report_sinks(&self) -> Vec<ReportSinkKind> {
+        if let Some(report_sinks) = &self.report_sinks {
+            return report_sinks.clone();
+        }
+        let mut report_sinks = vec![ReportSinkKind::Local];
+        if self.minio_url.is_some()
+            && self.minio_access_key.is_some()
+            && self.minio_secret_key.is_some()
+        {
+            report_sinks.push(ReportSinkKind::S3);
+        }
+        if self.webhook_url().is_some() {
+            report_sinks.push(ReportSinkKind::Webhook);
+        }
+        report_sinks
+    }
+
+    pub fn webhook_url(&self) -> Option<&String> {
+        self.webhook_url.as_ref().or(self.report_url.as_ref())
+    }
+
+    /// The work directory of a worker, each worker has its own folder in
+    /// `--work-dir` if there are several.
+    pub fn worker_dir(&self, worker_id: u64) -> Option<PathBuf> {
+        let work_dir = self.work_dir.as_ref()?;
+        if self.workers > 1 {
+            Some(work_dir.join(format!("worker-{}", worker_id)))
+        } else {
+            Some(work_dir.clone())
+        }
+    }
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct SoakConfig {
+    #[clap(long, env = "SOAK_DURATION")]
+    pub duration: Option<u64>,
+
+    #[clap(long, env = "SOAK_ITERATIONS")]
+    pub iterations: Option<u64>,
+
+    #[clap(long, env = "SOAK_STEPS", default_value_t = 200)]
+    pub steps: u64,
+
+    #[clap(long, env = "SOAK_ARTIFACTS_DIR", default_value = "soak-artifacts")]
+    pub artifacts_dir: PathBuf,
+
+    #[command(flatten)]
+    pub weights: TaskWeights,
+}
+
+/// What the runner does when a step parameter can't be resolved
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidScenarioPolicy {
+    /// Record the step as invalid and keep running the next steps
+    Continue,
+    /// Record the step as invalid and stop the current run
+    Abort,
+}
+
+/// Which scenarios each worker runs when `--scenario` isn't given, see
+/// `selection`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionStrategy {
+    /// Each worker runs a scenario picked with its seed
+    Random,
+    /// Worker `i` runs the `i`-th scenario, wrapping around
+    RoundRobin,
+    /// Each worker runs a scenario picked according to the manifest weights
+    Weighted,
+    /// Every scenario runs once, spread across the workers
+    Once,
+    /// Each worker runs the scenarios the manifest lists for it
+    List,
+}
+
+/// How the runner orders the steps of a scenario
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    /// Run the steps one after the other
+    Sequential,
+    /// Run the steps that don't depend on each other concurrently
+    Dag,
+}
+
+/// Which of the `--rpc` endpoints serves a request, see `RpcPool`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpcSelection {
+    /// Keep using the same endpoint until it fails
+    Sticky,
+    /// Use the endpoints one after the other
+    RoundRobin,
+}
+
+/// How the logs are written to stdout, see `logging`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// A human readable line per event, prefixed by its spans
+    Pretty,
+    /// A JSON object per event, with the fields of its spans
+    Json,
+}
+
+/// Where the reports of a run are published, see `sinks`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportSinkKind {
+    /// Copy the reports to `--report-dir`
+    Local,
+    /// Upload the reports to an S3 compatible storage (e.g. MinIO)
+    S3,
+    /// Post `--webhook-body` to `--webhook-url`
+    Webhook,
+    /// Print the markdown report
+    Stdout,
+}
@@ -21,6 +21,11 @@ pub enum AccountIndentifier {
 }
 
 impl AccountIndentifier {
+    /// Whether this refers to the native token by its `nam` alias.
+    pub fn is_native_token(&self) -> bool {
+        matches!(self, AccountIndentifier::Alias(alias) if alias == "nam")
+    }
+
     pub async fn to_namada_address(&self, sdk: &Sdk) -> Address {
         match self {
             AccountIndentifier::Alias(alias) => {
@@ -0,0 +1,196 @@
+use std::fmt::Display;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    policy::FailureKind, scenario::StepResult, state::state::StepOutcome,
+    utils::value::ParameterError,
+};
+
+/// How a tx is expected to end up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExpectedResult {
+    /// The tx is applied and accepted by every VP
+    #[default]
+    Success,
+    /// The tx is applied but rejected by a validity predicate
+    VpRejection,
+    /// The wrapper of the tx is rejected, e.g. by the fee checks, so none of
+    /// its inner txs run
+    WrapperRejection,
+}
+
+impl Display for ExpectedResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpectedResult::Success => write!(f, "success"),
+            ExpectedResult::VpRejection => write!(f, "vp-rejection"),
+            ExpectedResult::WrapperRejection => write!(f, "wrapper-rejection"),
+        }
+    }
+}
+
+/// What a tx step predicts the chain does with its tx, set next to its
+/// `config`. The step succeeds when the chain does what was predicted, and
+/// fails with a check failure otherwise.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StepExpectation {
+    #[serde(default)]
+    pub result: ExpectedResult,
+    /// Regex the error of a rejected tx must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Regex the address of one of the VPs that rejected the tx must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vp: Option<String>,
+}
+
+impl StepExpectation {
+    /// The `error` and `vp` patterns, compiled.
+    pub fn patterns(&self) -> Result<(Option<Regex>, Option<Regex>), ParameterError> {
+        let compile = |field: &str, pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .map_err(|e| ParameterError::new(field, e))
+        };
+        Ok((
+            compile("expect.error", &self.error)?,
+            compile("expect.vp", &self.vp)?,
+        ))
+    }
+
+    /// Turns the result of a tx step into the result of the expectation. A
+    /// no-op fails it since the tx was never sent, results that don't tell
+    /// what the chain did with the tx (timeouts, RPC errors...) are left as
+    /// they are, so the policy of the step can retry them.
+    pub fn check(&self, result: StepResult) -> Result<StepResult, ParameterError> {
+        let (error_pattern, vp_pattern) = self.patterns()?;
+
+        let actual = match (&result.outcome, result.failure) {
+            (StepOutcome::NoOp, _) => return Ok(self.fail(result, "no-op".to_string())),
+            (StepOutcome::Success, _) => ExpectedResult::Success,
+            (StepOutcome::Fail(_), Some(FailureKind::VpRejection)) => ExpectedResult::VpRejection,
+            (StepOutcome::Fail(_), Some(FailureKind::WrapperRejection)) => {
+                ExpectedResult::WrapperRejection
+            }
+            _ => return Ok(result),
+        };
+        if actual != self.result {
+            return Ok(self.fail(result, actual.to_string()));
+        }
+
+        if actual != ExpectedResult::Success {
+            let error = result.fail_error();
+            if error_pattern.is_some_and(|pattern| !pattern.is_match(&error)) {
+                return Ok(self.fail(result, format!("{} ({})", actual, error)));
+            }
+            let rejected_vps = result.data.get_field("rejected-vps").unwrap_or_default();
+            if vp_pattern.is_some_and(|pattern| {
+                !rejected_vps
+                    .split(',')
+                    .filter(|vp| !vp.is_empty())
+                    .any(|vp| pattern.is_match(vp))
+            }) {
+                return Ok(self.fail(
+                    result,
+                    format!("{} (rejected by [{}])", actual, rejected_vps),
+                ));
+            }
+        }
+
+        Ok(StepResult::success(result.data))
+    }
+
+    // the data of the step is kept for the reports
+    fn fail(&self, result: StepResult, actual: String) -> StepResult {
+        StepResult {
+            data: result.data,
+            ..StepResult::fail_check(actual, self.to_string())
+        }
+    }
+}
+
+impl Display for StepExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.result)?;
+        if let Some(error) = &self.error {
+            write!(f, ", error matching `{}`", error)?;
+        }
+        if let Some(vp) = &self.vp {
+            write!(f, ", rejected by a VP matching `{}`", vp)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::state::StepStorage;
+
+    use super::*;
+
+    fn expectation(json: serde_json::Value) -> StepExpectation {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn rejection(failure: FailureKind, error: &str, rejected_vps: &str) -> StepResult {
+        let mut data = StepStorage::default();
+        data.add("rejected-vps".to_string(), rejected_vps.to_string());
+        StepResult::fail_with_kind(failure, error.to_string(), data)
+    }
+
+    #[test]
+    fn test_check() {
+        let expect = expectation(serde_json::json!({ "result": "vp-rejection", "vp": "^tnam1q" }));
+        let result = expect
+            .check(rejection(
+                FailureKind::VpRejection,
+                "insufficient balance",
+                "tnam1qabc",
+            ))
+            .unwrap();
+        assert!(result.is_strict_succesful());
+
+        let result = expect
+            .check(rejection(
+                FailureKind::VpRejection,
+                "insufficient balance",
+                "tnam1pabc",
+            ))
+            .unwrap();
+        assert!(matches!(result.outcome, StepOutcome::CheckFail(_, _)));
+        assert!(result.data.get_field("rejected-vps").is_some());
+
+        let result = expect
+            .check(StepResult::success(StepStorage::default()))
+            .unwrap();
+        assert!(matches!(result.outcome, StepOutcome::CheckFail(_, _)));
+
+        // a timeout doesn't tell what the chain did with the tx
+        let result = expect
+            .check(rejection(FailureKind::Timeout, "timeout", ""))
+            .unwrap();
+        assert_eq!(result.failure, Some(FailureKind::Timeout));
+
+        let result = expect.check(StepResult::no_op()).unwrap();
+        assert!(matches!(result.outcome, StepOutcome::CheckFail(_, _)));
+
+        let expect =
+            expectation(serde_json::json!({ "result": "wrapper-rejection", "error": "fee" }));
+        let result = expect
+            .check(rejection(
+                FailureKind::WrapperRejection,
+                "insufficient fee",
+                "",
+            ))
+            .unwrap();
+        assert!(result.is_strict_succesful());
+
+        let expect = expectation(serde_json::json!({ "error": "(" }));
+        assert_eq!(expect.patterns().unwrap_err().parameter, "expect.error");
+    }
+}
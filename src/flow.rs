@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    scenario::{Step, StepResult},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
+};
+
+/// Upper bound on the iterations of a `repeat` step using `until` without `max`.
+pub const DEFAULT_MAX_ITERATIONS: u64 = 10;
+
+/// A condition over the results of the steps that already ran.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Condition {
+    /// Holds if `step` was successful
+    #[serde(rename = "succeeded")]
+    Succeeded { step: u64 },
+    /// Holds if `field` of `step` is equal to `value`, doesn't hold if either
+    /// of them references a failed or no-op step
+    #[serde(rename = "equals")]
+    Equals {
+        step: u64,
+        field: String,
+        value: Value,
+    },
+}
+
+impl Condition {
+    pub fn holds(&self, state: &Storage) -> Result<bool, ParameterError> {
+        match self {
+            Condition::Succeeded { step } => state
+                .is_step_successful(step)
+                .map_err(|e| ParameterError::new("condition", e)),
+            Condition::Equals { step, field, value } => {
+                let resolver = Resolver::new(state);
+
+                let Some(actual) = resolver.string("condition", Value::r(*step, field.clone()))?
+                else {
+                    return Ok(false);
+                };
+                let Some(expected) = resolver.string("condition.value", value.clone())? else {
+                    return Ok(false);
+                };
+
+                Ok(actual == expected)
+            }
+        }
+    }
+}
+
+pub enum IfStorageKeys {
+    Taken,
+}
+
+impl ToString for IfStorageKeys {
+    fn to_string(&self) -> String {
+        match self {
+            IfStorageKeys::Taken => "taken".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IfParametersDto {
+    pub condition: Condition,
+    pub steps: Vec<Step>,
+}
+
+pub struct IfStep;
+
+impl IfStep {
+    pub fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![OutputField::new(
+            IfStorageKeys::Taken,
+            FieldType::Bool,
+        )])
+    }
+
+    pub fn result(taken: bool) -> StepResult {
+        let mut storage = StepStorage::default();
+        storage.add(IfStorageKeys::Taken.to_string(), taken.to_string());
+
+        StepResult::success(storage)
+    }
+}
+
+pub enum RepeatStorageKeys {
+    Iterations,
+}
+
+impl ToString for RepeatStorageKeys {
+    fn to_string(&self) -> String {
+        match self {
+            RepeatStorageKeys::Iterations => "iterations".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RepeatParametersDto {
+    pub times: Option<Value>,
+    pub until: Option<Condition>,
+    pub max: Option<Value>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RepeatParameters {
+    pub until: Option<Condition>,
+    pub iterations: u64,
+}
+
+impl RepeatParameters {
+    /// `times` runs the block exactly that many times, `until` runs it until
+    /// the condition holds after an iteration, at most `max` times.
+    pub fn from_dto(
+        dto: RepeatParametersDto,
+        state: &Storage,
+    ) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(times) = resolver.optional("times", dto.times, Resolver::number)? else {
+            return Ok(None);
+        };
+        let Some(max) = resolver.optional("max", dto.max, Resolver::number)? else {
+            return Ok(None);
+        };
+        let iterations = match (times, &dto.until) {
+            (Some(_), Some(_)) => {
+                return Err(ParameterError::new(
+                    "times",
+                    "can't be used together with `until`",
+                ))
+            }
+            (Some(times), None) => times,
+            (None, Some(_)) => max.unwrap_or(DEFAULT_MAX_ITERATIONS),
+            (None, None) => {
+                return Err(ParameterError::new(
+                    "times",
+                    "either `times` or `until` is required",
+                ))
+            }
+        };
+
+        Ok(Some(Self {
+            until: dto.until,
+            iterations,
+        }))
+    }
+}
+
+pub struct RepeatStep;
+
+impl RepeatStep {
+    pub fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![OutputField::new(
+            RepeatStorageKeys::Iterations,
+            FieldType::Number,
+        )])
+    }
+
+    pub fn result(iterations: u64, condition_met: bool) -> StepResult {
+        if !condition_met {
+            return StepResult::fail(format!("condition not met after {} iterations", iterations));
+        }
+
+        let mut storage = StepStorage::default();
+        storage.add(
+            RepeatStorageKeys::Iterations.to_string(),
+            iterations.to_string(),
+        );
+
+        StepResult::success(storage)
+    }
+}
+
+pub enum ParallelStorageKeys {
+    Size,
+}
+
+impl ToString for ParallelStorageKeys {
+    fn to_string(&self) -> String {
+        match self {
+            ParallelStorageKeys::Size => "size".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ParallelParametersDto {
+    pub steps: Vec<Step>,
+}
+
+pub struct ParallelStep;
+
+impl ParallelStep {
+    pub fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![OutputField::new(
+            ParallelStorageKeys::Size,
+            FieldType::Number,
+        )])
+    }
+
+    pub fn result(size: usize) -> StepResult {
+        let mut storage = StepStorage::default();
+        storage.add(ParallelStorageKeys::Size.to_string(), size.to_string());
+
+        StepResult::success(storage)
+    }
+}
@@ -1,9 +1,11 @@
 use std::{collections::BTreeSet, fmt::Display};
 
 use derive_builder::Builder;
-use namada_scenario_tester::utils::{settings::TxSettingsDto, value::Value};
 
-use crate::constants::DEFAULT_GAS_LIMIT;
+use crate::{
+    gen::constants::DEFAULT_GAS_LIMIT,
+    utils::{settings::TxSettingsDto, value::Value},
+};
 
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Alias {
@@ -186,8 +188,10 @@ impl Default for TxSettings {
 impl From<TxSettings> for TxSettingsDto {
     fn from(value: TxSettings) -> Self {
         Self {
-            broadcast_only: Some(value.broadcast_only),
+            broadcast_only: Some(Value::v(value.broadcast_only.to_string())),
             gas_token: None,
+            gas_price: None,
+            fee_amount: None,
             gas_payer: Some(Value::v(value.gas_payer.to_string())),
             signers: Some(
                 value
@@ -199,6 +203,7 @@ impl From<TxSettings> for TxSettingsDto {
                     .collect(),
             ),
             expiration: None,
+            expiration_offset: None,
             gas_limit: Some(Value::v(value.gas_limit.to_string())),
         }
     }
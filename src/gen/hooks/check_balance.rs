@@ -1,12 +1,14 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    checks::balance::BalanceCheckParametersDto, queries::balance::BalanceQueryStorageKeys,
-    scenario::StepType, utils::value::Value,
-};
 
-use crate::{entity::Alias, step::Hook};
+use crate::{
+    checks::balance::BalanceCheckParametersDto,
+    gen::{entity::Alias, step::Hook},
+    queries::balance::BalanceQueryStorageKeys,
+    scenario::StepType,
+    utils::value::Value,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct CheckBalance {
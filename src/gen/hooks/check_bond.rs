@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    checks::bonds::BondsCheckParametersDto, scenario::StepType, utils::value::Value,
-};
 
-use crate::{constants::BOND_VALIDATOR_STORAGE_KEY, entity::Alias, step::Hook};
+use crate::{
+    checks::bonds::BondsCheckParametersDto,
+    gen::{constants::BOND_VALIDATOR_STORAGE_KEY, entity::Alias, step::Hook},
+    scenario::StepType,
+    utils::value::Value,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct CheckBond {
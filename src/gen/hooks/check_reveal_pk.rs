@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    checks::reveal_pk::RevealPkCheckParametersDto, scenario::StepType, utils::value::Value,
-};
 
-use crate::{entity::Alias, step::Hook};
+use crate::{
+    checks::reveal_pk::RevealPkCheckParametersDto,
+    gen::{entity::Alias, step::Hook},
+    scenario::StepType,
+    utils::value::Value,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct CheckRevealPk {
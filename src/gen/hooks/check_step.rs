@@ -1,11 +1,10 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    checks::step::StepCheckParametersDto, scenario::StepType, utils::value::Value,
-};
 
-use crate::step::Hook;
+use crate::{
+    checks::step::StepCheckParametersDto, gen::step::Hook, scenario::StepType, utils::value::Value,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct CheckStep {
@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    queries::balance::BalanceQueryParametersDto, scenario::StepType, utils::value::Value,
-};
 
-use crate::{entity::Alias, step::Hook};
+use crate::{
+    gen::{entity::Alias, step::Hook},
+    queries::balance::BalanceQueryParametersDto,
+    scenario::StepType,
+    utils::value::Value,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct QueryBalance {
@@ -1,9 +1,8 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{queries::proposals::ProposalsQueryParametersDto, scenario::StepType};
 
-use crate::step::Hook;
+use crate::{gen::step::Hook, queries::proposals::ProposalsQueryParametersDto, scenario::StepType};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct QueryProposals {}
@@ -1,11 +1,10 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    queries::validators::ValidatorsQueryParametersDto, scenario::StepType,
-};
 
-use crate::step::Hook;
+use crate::{
+    gen::step::Hook, queries::validators::ValidatorsQueryParametersDto, scenario::StepType,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct QueryValidatorSet {}
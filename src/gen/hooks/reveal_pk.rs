@@ -1,14 +1,16 @@
 use std::{collections::BTreeSet, fmt::Display};
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::reveal_pk::RevealPkParametersDto, utils::value::Value,
-};
 
 use crate::{
-    constants::DEFAULT_GAS_LIMIT,
-    entity::{Alias, TxSettings},
-    step::Hook,
+    gen::{
+        constants::DEFAULT_GAS_LIMIT,
+        entity::{Alias, TxSettings},
+        step::Hook,
+    },
+    scenario::StepType,
+    tasks::reveal_pk::RevealPkParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
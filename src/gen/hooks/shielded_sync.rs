@@ -1,8 +1,6 @@
 use std::fmt::Display;
 
-use namada_scenario_tester::scenario::StepType;
-
-use crate::step::Hook;
+use crate::{gen::step::Hook, scenario::StepType};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ShieldedSync {}
@@ -1,9 +1,8 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::scenario::StepType;
 
-use crate::step::Hook;
+use crate::{gen::step::Hook, scenario::StepType};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct CheckStorage {
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::gen::{
+    scenario_builder::{ScenarioBuilder, Weight},
+    step::TaskType,
+};
+
+pub mod constants;
+pub mod entity;
+pub mod hooks;
+pub mod scenario_builder;
+pub mod state;
+pub mod step;
+pub mod steps;
+pub mod utils;
+
+/// Weight of each task when picking the next step of a generated scenario,
+/// new wallet keys and faucet transfers always have a weight of 2.
+#[derive(clap::Args, Clone, Debug)]
+pub struct TaskWeights {
+    #[arg(long, default_value_t = 0)]
+    pub transparent_transfers: u64,
+    #[arg(long, default_value_t = 0)]
+    pub shielding_transfer: u64,
+    #[arg(long, default_value_t = 0)]
+    pub unshielding_transfer: u64,
+    #[arg(long, default_value_t = 0)]
+    pub init_account: u64,
+    #[arg(long, default_value_t = 0)]
+    pub bond: u64,
+    #[arg(long, default_value_t = 0)]
+    pub unbond: u64,
+    #[arg(long, default_value_t = 0)]
+    pub withdraw: u64,
+    #[arg(long, default_value_t = 0)]
+    pub vote_proposal: u64,
+    #[arg(long, default_value_t = 0)]
+    pub redelegate: u64,
+    #[arg(long, default_value_t = 0)]
+    pub init_default_proposal: u64,
+    #[arg(long, default_value_t = 0)]
+    pub init_pgf_steward_proposal: u64,
+    #[arg(long, default_value_t = 0)]
+    pub init_pgf_funding_proposal: u64,
+    #[arg(long, default_value_t = 0)]
+    pub become_validator: u64,
+    #[arg(long, default_value_t = 0)]
+    pub update_account: u64,
+    #[arg(long, default_value_t = 0)]
+    pub deactivate_validator: u64,
+    #[arg(long, default_value_t = 0)]
+    pub change_metadata: u64,
+    #[arg(long, default_value_t = 0)]
+    pub claim_rewards: u64,
+    #[arg(long, default_value_t = 0)]
+    pub transparent_transfer_batch: u64,
+    #[arg(long, default_value_t = 0)]
+    pub bond_batch: u64,
+    #[arg(long, default_value_t = 0)]
+    pub redelegate_batch: u64,
+    #[arg(long, default_value_t = 6)]
+    pub shielding_batch: u64,
+}
+
+impl TaskWeights {
+    pub fn tasks(&self) -> HashMap<TaskType, Weight> {
+        // TODO:
+        // change commission
+        // activate validator
+
+        // TODO:
+        // randomize tx settings
+
+        HashMap::from_iter([
+            (TaskType::NewWalletKey, 2.into()),
+            (TaskType::FaucetTransafer, 2.into()),
+            (
+                TaskType::TransparentTransfer,
+                self.transparent_transfers.into(),
+            ),
+            (TaskType::ShieldingTransfer, self.shielding_transfer.into()),
+            (
+                TaskType::UnshieldingTransfer,
+                self.unshielding_transfer.into(),
+            ),
+            (TaskType::InitAccount, self.init_account.into()),
+            (TaskType::Bond, self.bond.into()),
+            (
+                TaskType::InitDefaultProposal,
+                self.init_default_proposal.into(),
+            ),
+            (TaskType::Unbond, self.unbond.into()),
+            (TaskType::Withdraw, self.withdraw.into()),
+            (TaskType::VoteProposal, self.vote_proposal.into()),
+            (TaskType::Redelegate, self.redelegate.into()),
+            (
+                TaskType::InitPgfStewardProposal,
+                self.init_pgf_steward_proposal.into(),
+            ),
+            (
+                TaskType::InitPgfFundingProposal,
+                self.init_pgf_funding_proposal.into(),
+            ),
+            (TaskType::BecomeValidator, self.become_validator.into()),
+            (TaskType::UpdateAccount, self.update_account.into()),
+            (
+                TaskType::DeactivateValidator,
+                self.deactivate_validator.into(),
+            ),
+            (TaskType::ChangeMetadata, self.change_metadata.into()),
+            (TaskType::ClaimRewards, self.claim_rewards.into()),
+            (
+                TaskType::TransparentTransferBatch,
+                self.transparent_transfer_batch.into(),
+            ),
+            (TaskType::BondBatch, self.bond_batch.into()),
+            (TaskType::RedelegateBatch, self.redelegate_batch.into()),
+            (TaskType::ShieldingBatch, self.shielding_batch.into()),
+        ])
+    }
+
+    pub fn builder(&self) -> ScenarioBuilder {
+        let tasks = self.tasks();
+        ScenarioBuilder::new(
+            tasks.keys().cloned().collect_vec(),
+            tasks.values().cloned().collect_vec(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    pub fn test_basic() {
+        for _ in 0..10000 {
+            let tasks: HashMap<TaskType, Weight> = HashMap::from_iter([
+                (TaskType::NewWalletKey, 1.into()),
+                (TaskType::FaucetTransafer, 2.into()),
+                (TaskType::TransparentTransfer, 3.into()),
+            ]);
+
+            let mut scenario_builder = ScenarioBuilder::new(
+                tasks.keys().cloned().collect_vec(),
+                tasks.values().cloned().collect_vec(),
+            );
+
+            for _ in 0..=200 {
+                let next_task = loop {
+                    let task_type = scenario_builder.choose_next_task();
+                    if scenario_builder.is_valid_task(task_type) {
+                        break task_type;
+                    }
+                };
+                let step = scenario_builder.build_step(next_task);
+
+                scenario_builder.update_state(step.clone());
+                scenario_builder.update_scenario(step.clone());
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_basic_plus_pos() {
+        for _ in 0..10000 {
+            let tasks: HashMap<TaskType, Weight> = HashMap::from_iter([
+                (TaskType::NewWalletKey, 1.into()),
+                (TaskType::FaucetTransafer, 2.into()),
+                (TaskType::TransparentTransfer, 3.into()),
+                (TaskType::Bond, 4.into()),
+                (TaskType::Unbond, 4.into()),
+                (TaskType::Withdraw, 8.into()),
+                (TaskType::Redelegate, 4.into()),
+            ]);
+
+            let mut scenario_builder = ScenarioBuilder::new(
+                tasks.keys().cloned().collect_vec(),
+                tasks.values().cloned().collect_vec(),
+            );
+
+            for _ in 0..=200 {
+                let next_task = loop {
+                    let task_type = scenario_builder.choose_next_task();
+                    if scenario_builder.is_valid_task(task_type) {
+                        break task_type;
+                    }
+                };
+                let step = scenario_builder.build_step(next_task);
+
+                scenario_builder.update_state(step.clone());
+                scenario_builder.update_scenario(step.clone());
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_basic_plus_pos_plus_goverance() {
+        for _ in 0..10000 {
+            let tasks: HashMap<TaskType, Weight> = HashMap::from_iter([
+                (TaskType::NewWalletKey, 1.into()),
+                (TaskType::FaucetTransafer, 2.into()),
+                (TaskType::TransparentTransfer, 3.into()),
+                (TaskType::Bond, 4.into()),
+                (TaskType::Unbond, 4.into()),
+                (TaskType::Withdraw, 8.into()),
+                (TaskType::Redelegate, 4.into()),
+                (TaskType::InitDefaultProposal, 8.into()),
+                (TaskType::VoteProposal, 8.into()),
+                (TaskType::InitPgfStewardProposal, 12.into()),
+                (TaskType::InitPgfFundingProposal, 12.into()),
+            ]);
+
+            let mut scenario_builder = ScenarioBuilder::new(
+                tasks.keys().cloned().collect_vec(),
+                tasks.values().cloned().collect_vec(),
+            );
+
+            for _ in 0..=200 {
+                let next_task = loop {
+                    let task_type = scenario_builder.choose_next_task();
+                    if scenario_builder.is_valid_task(task_type) {
+                        break task_type;
+                    }
+                };
+                let step = scenario_builder.build_step(next_task);
+
+                scenario_builder.update_state(step.clone());
+                scenario_builder.update_scenario(step.clone());
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_basic_plus_pos_plus_goverance_plus_account() {
+        for _ in 0..10000 {
+            let tasks: HashMap<TaskType, Weight> = HashMap::from_iter([
+                (TaskType::NewWalletKey, 1.into()),
+                (TaskType::FaucetTransafer, 2.into()),
+                (TaskType::TransparentTransfer, 3.into()),
+                (TaskType::Bond, 4.into()),
+                (TaskType::Unbond, 4.into()),
+                (TaskType::Withdraw, 8.into()),
+                (TaskType::Redelegate, 4.into()),
+                (TaskType::InitDefaultProposal, 8.into()),
+                (TaskType::VoteProposal, 8.into()),
+                (TaskType::InitPgfStewardProposal, 12.into()),
+                (TaskType::InitPgfFundingProposal, 12.into()),
+                (TaskType::InitAccount, 6.into()),
+                (TaskType::UpdateAccount, 6.into()),
+            ]);
+
+            let mut scenario_builder = ScenarioBuilder::new(
+                tasks.keys().cloned().collect_vec(),
+                tasks.values().cloned().collect_vec(),
+            );
+
+            for _ in 0..=200 {
+                let next_task = loop {
+                    let task_type = scenario_builder.choose_next_task();
+                    if scenario_builder.is_valid_task(task_type) {
+                        break task_type;
+                    }
+                };
+                let step = scenario_builder.build_step(next_task);
+
+                scenario_builder.update_state(step.clone());
+                scenario_builder.update_scenario(step.clone());
+            }
+        }
+    }
+}
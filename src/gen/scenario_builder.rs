@@ -1,14 +1,14 @@
 use std::fs;
 
-use namada_scenario_tester::scenario::{
-    Scenario, ScenarioSettings, Step as ScenarioStep, StepType,
-};
 use weighted_rand::{builder::*, table::WalkerTable};
 
 use crate::{
-    state::State,
-    step::{Step, TaskType},
-    utils,
+    gen::{
+        state::State,
+        step::{Step, TaskType},
+        utils,
+    },
+    scenario::{Scenario, ScenarioSettings, Step as ScenarioStep, StepType},
 };
 
 #[derive(Clone)]
@@ -98,8 +98,29 @@ impl ScenarioBuilder {
         self.scenario.extend(post_hooks_json);
     }
 
-    pub fn to_file(&self) {
-        let scenario = Scenario {
+    /// Adds `steps` valid steps, and their hooks, to the scenario.
+    pub fn generate(&mut self, steps: u64) {
+        for _ in 0..=steps {
+            let next_task = loop {
+                let task_type = self.choose_next_task();
+                if self.is_valid_task(task_type) {
+                    break task_type;
+                }
+            };
+            let step = self.build_step(next_task);
+
+            self.update_state(step.clone());
+            self.update_scenario(step.clone());
+        }
+    }
+
+    /// A random name for the scenario, namespaced by its number of steps.
+    pub fn name(&self) -> String {
+        utils::random_with_namespace(self.scenario.len().to_string().as_str())
+    }
+
+    pub fn build(&self) -> Scenario {
+        Scenario {
             settings: ScenarioSettings { retry_for: None },
             steps: self
                 .scenario
@@ -109,11 +130,16 @@ impl ScenarioBuilder {
                 .map(|(index, step_type)| ScenarioStep {
                     id: index as u64,
                     config: step_type,
+                    policy: None,
+                    expect: None,
                 })
                 .collect(),
-        };
-        let scenario_json = serde_json::to_string_pretty(&scenario).unwrap();
-        let scenario_name = utils::random_with_namespace(scenario.steps.len().to_string().as_str());
+        }
+    }
+
+    pub fn to_file(&self) {
+        let scenario_json = serde_json::to_string_pretty(&self.build()).unwrap();
+        let scenario_name = self.name();
         fs::write(format!("scenarios/{}.json", scenario_name), scenario_json)
             .expect("Unable to write file");
         println!("Scenario {} generate and saved to file.", scenario_name)
@@ -3,7 +3,7 @@ use std::{
     collections::{BTreeSet, HashMap, HashSet},
 };
 
-use crate::{
+use crate::gen::{
     constants::DEFAULT_GAS_PRICE,
     entity::{Account, Alias, Bond, PaymentAddress, TxSettings, Unbond},
 };
@@ -194,7 +194,7 @@ impl State {
                 !account.is_validator
                     && account
                         .address_type
-                        .eq(&crate::entity::AddressType::Enstablished)
+                        .eq(&crate::gen::entity::AddressType::Enstablished)
             })
             .cloned()
             .collect()
@@ -207,7 +207,7 @@ impl State {
                 !account.is_validator
                     && account
                         .address_type
-                        .eq(&crate::entity::AddressType::Enstablished)
+                        .eq(&crate::gen::entity::AddressType::Enstablished)
                     && !self.bonds.contains_key(&account.alias)
                     && !self.unbonds.contains_key(&account.alias)
             })
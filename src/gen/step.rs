@@ -1,15 +1,32 @@
 use dyn_clone::DynClone;
-use namada_scenario_tester::scenario::StepType;
 use namada_sdk::token::NATIVE_SCALE;
 
 use crate::{
-    constants::{DEFAULT_GAS_LIMIT, MAX_PGF_ACTIONS, MIN_FEE, PROPOSAL_FUNDS},
-    entity::{Alias, TxSettings},
-    state::State,
-    steps::{
-        become_validator::BecomeValidatorBuilder, bond_batch::BondBatchBuilder, bonds::BondBuilder, change_consensus_key::ChangeConsensusKeyBuilder, change_metadata::ChangeMetadataBuilder, claim_rewards::ClaimRewardsBuilder, deactivate_validator::DeactivateValidatorBuilder, faucet_transfer::FaucetTransferBuilder, init_account::InitAccountBuilder, init_default_proposal::InitDefaultProposalBuilder, init_funding_proposal::InitPgfFundingProposalBuilder, init_steward_proposal::InitPgfStewardProposalBuilder, new_wallet_key::NewWalletStepBuilder, redelegate::RedelegateBuilder, redelegate_batch::RedelegateBatchBuilder, shielding_batch_transfer::ShieldingBatchBuilder, shielding_transfer::ShieldingTransferBuilder, transparent_transfer::TransparentTransferBuilder, transparent_transfer_batch::TransparentTransferBatchBuilder, unbond::UnbondBuilder, unshielding_transfer::UnshieldingTransferBuilder, update_account::UpdateAccountBuilder, vote::VoteProposalBuilder, withdraw::WithdrawBuilder
+    gen::{
+        constants::{DEFAULT_GAS_LIMIT, MAX_PGF_ACTIONS, MIN_FEE, PROPOSAL_FUNDS},
+        entity::{Alias, TxSettings},
+        state::State,
+        steps::{
+            become_validator::BecomeValidatorBuilder, bond_batch::BondBatchBuilder,
+            bonds::BondBuilder, change_consensus_key::ChangeConsensusKeyBuilder,
+            change_metadata::ChangeMetadataBuilder, claim_rewards::ClaimRewardsBuilder,
+            deactivate_validator::DeactivateValidatorBuilder,
+            faucet_transfer::FaucetTransferBuilder, init_account::InitAccountBuilder,
+            init_default_proposal::InitDefaultProposalBuilder,
+            init_funding_proposal::InitPgfFundingProposalBuilder,
+            init_steward_proposal::InitPgfStewardProposalBuilder,
+            new_wallet_key::NewWalletStepBuilder, redelegate::RedelegateBuilder,
+            redelegate_batch::RedelegateBatchBuilder,
+            shielding_batch_transfer::ShieldingBatchBuilder,
+            shielding_transfer::ShieldingTransferBuilder,
+            transparent_transfer::TransparentTransferBuilder,
+            transparent_transfer_batch::TransparentTransferBatchBuilder, unbond::UnbondBuilder,
+            unshielding_transfer::UnshieldingTransferBuilder, update_account::UpdateAccountBuilder,
+            vote::VoteProposalBuilder, withdraw::WithdrawBuilder,
+        },
+        utils,
     },
-    utils,
+    scenario::StepType,
 };
 
 use std::{
@@ -1,15 +1,17 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::bond::TxBondParametersDto, utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, TxSettings},
-    hooks::{check_step::CheckStep, query_validators::QueryValidatorSet},
-    state::State,
-    step::Step,
+    gen::{
+        entity::{Alias, TxSettings},
+        hooks::{check_step::CheckStep, query_validators::QueryValidatorSet},
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::bond::TxBondParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -31,17 +33,17 @@ impl Step for Bond {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.decrease_account_token_balance(&self.source, &Alias::native_token(), self.amount);
         state.decrease_account_fees(&self.tx_settings);
         state.insert_bond(&self.source, self.amount);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(CheckStep::new(step_index))]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(QueryValidatorSet::new())]
     }
 
@@ -1,15 +1,17 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::change_metadata::TxChangeMetadataParametersDto, utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, TxSettings},
-    hooks::check_step::CheckStep,
-    state::State,
-    step::Step,
+    gen::{
+        entity::{Alias, TxSettings},
+        hooks::check_step::CheckStep,
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::change_metadata::TxChangeMetadataParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -34,15 +36,15 @@ impl Step for ChangeMetadata {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.decrease_account_fees(&self.tx_settings);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(CheckStep::new(step_index))]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![]
     }
 
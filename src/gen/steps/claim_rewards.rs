@@ -1,11 +1,13 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::claim_rewards::TxClaimRewardsteParametersDto, utils::value::Value,
-};
 
-use crate::{entity::TxSettings, hooks::check_step::CheckStep, state::State, step::Step};
+use crate::{
+    gen::{entity::TxSettings, hooks::check_step::CheckStep, state::State, step::Step},
+    scenario::StepType,
+    tasks::claim_rewards::TxClaimRewardsteParametersDto,
+    utils::value::Value,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct ClaimRewards {
@@ -24,15 +26,15 @@ impl Step for ClaimRewards {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.decrease_account_fees(&self.tx_settings);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(CheckStep::new(step_index))]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![]
     }
 
@@ -1,13 +1,14 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::tx_transparent_transfer::TxTransparentTransferParametersDto,
+
+use crate::{
+    gen::{entity::Alias, hooks::check_step::CheckStep, state::State, step::Step},
+    scenario::StepType,
+    tasks::tx_transparent_transfer::TxTransparentTransferParametersDto,
     utils::value::Value,
 };
 
-use crate::{entity::Alias, hooks::check_step::CheckStep, state::State, step::Step};
-
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
 pub struct FaucetTransfer {
     pub target: Alias,
@@ -28,15 +29,15 @@ impl Step for FaucetTransfer {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.increase_account_token_balance(&self.target, self.token.clone(), self.amount);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(CheckStep::new(step_index))]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![]
     }
 
@@ -1,15 +1,17 @@
 use std::{collections::BTreeSet, fmt::Display};
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::init_account::TxInitAccountParametersDto, utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, TxSettings},
-    hooks::check_step::CheckStep,
-    state::State,
-    step::Step,
+    gen::{
+        entity::{Alias, TxSettings},
+        hooks::check_step::CheckStep,
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::init_account::TxInitAccountParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -36,16 +38,16 @@ impl Step for InitAccount {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.add_new_account(self.alias.clone(), self.pks.clone(), self.threshold);
         state.decrease_account_fees(&self.tx_settings);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(CheckStep::new(step_index))]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![]
     }
 
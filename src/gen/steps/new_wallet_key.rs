@@ -1,15 +1,17 @@
 use std::fmt::Display;
 
 use crate::{
-    entity::Alias,
-    hooks::{check_reveal_pk::CheckRevealPk, reveal_pk::RevealPk},
-    state::State,
-    step::Step,
+    gen::{
+        entity::Alias,
+        hooks::{check_reveal_pk::CheckRevealPk, reveal_pk::RevealPk},
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::wallet_new_key::WalletNewKeyParametersDto,
+    utils::value::Value,
 };
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::wallet_new_key::WalletNewKeyParametersDto, utils::value::Value,
-};
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Builder)]
 pub struct NewWalletStep {
@@ -26,18 +28,18 @@ impl Step for NewWalletStep {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.insert_new_key(self.alias.clone());
     }
 
-    fn post_hooks(&self, _step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, _step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![
             Box::new(RevealPk::new(self.alias.clone())),
             Box::new(CheckRevealPk::new(self.alias.clone())),
         ]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![]
     }
 
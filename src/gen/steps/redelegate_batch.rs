@@ -1,16 +1,17 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::redelegate_batch::TxRedelegateBatchParametersDto,
-    utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, TxSettings},
-    hooks::{check_step::CheckStep, query_validators::QueryValidatorSet},
-    state::State,
-    step::Step,
+    gen::{
+        entity::{Alias, TxSettings},
+        hooks::{check_step::CheckStep, query_validators::QueryValidatorSet},
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::redelegate_batch::TxRedelegateBatchParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -50,7 +51,7 @@ impl Step for RedelegateBatch {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         for idx in 0..self.sources.len() {
             state.insert_redelegation_and_update_bonds(
                 &self.sources[idx],
@@ -61,11 +62,11 @@ impl Step for RedelegateBatch {
         state.decrease_account_fees(&self.tx_settings);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(CheckStep::new(step_index))]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         vec![Box::new(QueryValidatorSet::new())]
     }
 
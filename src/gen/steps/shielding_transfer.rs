@@ -1,16 +1,17 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::tx_shielding_transfer::TxShieldingTransferParametersDto,
-    utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, PaymentAddress, TxSettings},
-    hooks::{check_balance::CheckBalance, check_step::CheckStep, query_balance::QueryBalance},
-    state::State,
-    step::Step,
+    gen::{
+        entity::{Alias, PaymentAddress, TxSettings},
+        hooks::{check_balance::CheckBalance, check_step::CheckStep, query_balance::QueryBalance},
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::tx_shielding_transfer::TxShieldingTransferParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -35,7 +36,7 @@ impl Step for ShieldingTransfer {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.decrease_account_fees(&self.tx_settings);
         state.decrease_account_token_balance(&self.source, &self.token, self.amount);
         state.increase_shielded_account_token_balance(
@@ -45,7 +46,7 @@ impl Step for ShieldingTransfer {
         );
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         let check_balance_source = CheckBalance::new(
             step_index - 1,
             self.source.clone(),
@@ -58,7 +59,7 @@ impl Step for ShieldingTransfer {
         ]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         let query_balance_source = QueryBalance::new(self.source.to_owned(), Alias::native_token());
         vec![Box::new(query_balance_source)]
     }
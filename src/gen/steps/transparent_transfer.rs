@@ -1,16 +1,17 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::tx_transparent_transfer::TxTransparentTransferParametersDto,
-    utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, TxSettings},
-    hooks::{check_balance::CheckBalance, check_step::CheckStep, query_balance::QueryBalance},
-    state::State,
-    step::Step,
+    gen::{
+        entity::{Alias, TxSettings},
+        hooks::{check_balance::CheckBalance, check_step::CheckStep, query_balance::QueryBalance},
+        state::State,
+        step::Step,
+    },
+    scenario::StepType,
+    tasks::tx_transparent_transfer::TxTransparentTransferParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -35,13 +36,13 @@ impl Step for TransparentTransfer {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.decrease_account_fees(&self.tx_settings);
         state.decrease_account_token_balance(&self.source, &self.token, self.amount);
         state.increase_account_token_balance(&self.target, self.token.clone(), self.amount);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         let check_balance_source = CheckBalance::new(
             step_index - 2,
             self.source.clone(),
@@ -61,7 +62,7 @@ impl Step for TransparentTransfer {
         ]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         let query_balance_source = QueryBalance::new(self.source.to_owned(), Alias::native_token());
         let query_balance_target = QueryBalance::new(self.target.to_owned(), Alias::native_token());
         vec![
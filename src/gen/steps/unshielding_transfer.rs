@@ -1,19 +1,20 @@
 use std::fmt::Display;
 
 use derive_builder::Builder;
-use namada_scenario_tester::{
-    scenario::StepType, tasks::tx_unshielding_transfer::TxUnshieldingTransferParametersDto,
-    utils::value::Value,
-};
 
 use crate::{
-    entity::{Alias, SpendingKey, TxSettings},
-    hooks::{
-        check_balance::CheckBalance, check_step::CheckStep, query_balance::QueryBalance,
-        shielded_sync::ShieldedSync,
+    gen::{
+        entity::{Alias, SpendingKey, TxSettings},
+        hooks::{
+            check_balance::CheckBalance, check_step::CheckStep, query_balance::QueryBalance,
+            shielded_sync::ShieldedSync,
+        },
+        state::State,
+        step::Step,
     },
-    state::State,
-    step::Step,
+    scenario::StepType,
+    tasks::tx_unshielding_transfer::TxUnshieldingTransferParametersDto,
+    utils::value::Value,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -38,7 +39,7 @@ impl Step for UnshieldingTransfer {
         }
     }
 
-    fn update_state(&self, state: &mut crate::state::State) {
+    fn update_state(&self, state: &mut crate::gen::state::State) {
         state.decrease_account_fees(&self.tx_settings);
         state.increase_account_token_balance(&self.target, self.token.clone(), self.amount);
         let pa_alias = format!(
@@ -49,7 +50,7 @@ impl Step for UnshieldingTransfer {
         state.decrease_shielded_account_token_balance(&pa_alias, &self.token, self.amount);
     }
 
-    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn post_hooks(&self, step_index: u64, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         let check_balance_target = CheckBalance::new(
             step_index - 2,
             self.target.clone(),
@@ -62,7 +63,7 @@ impl Step for UnshieldingTransfer {
         ]
     }
 
-    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::step::Hook>> {
+    fn pre_hooks(&self, _state: &State) -> Vec<Box<dyn crate::gen::step::Hook>> {
         let query_balance_source = QueryBalance::new(self.target.to_owned(), Alias::native_token());
         vec![
             Box::new(query_balance_source),
@@ -1,12 +1,26 @@
+pub mod bootstrap;
+pub mod checkpoint;
 pub mod checks;
 pub mod config;
 pub mod entity;
+pub mod expectation;
+pub mod flow;
+pub mod gen;
+pub mod logging;
+pub mod metrics;
+pub mod policy;
 pub mod queries;
 pub mod report;
 pub mod runner;
 pub mod scenario;
+pub mod scheduler;
 pub mod sdk;
+pub mod selection;
+pub mod shutdown;
+pub mod sinks;
+pub mod soak;
 pub mod state;
 pub mod tasks;
 pub mod utils;
+pub mod validator;
 pub mod waits;
@@ -0,0 +1,21 @@
+use tracing_subscriber::{filter::ParseError, fmt, EnvFilter};
+
+use crate::config::LogFormat;
+
+/// Installs the subscriber writing the logs to stdout. `level` is either a
+/// level (e.g. `debug`) or a list of directives as in `RUST_LOG` (e.g.
+/// `info,namada_scenario_tester::sdk=debug`).
+pub fn init(format: LogFormat, level: &str) -> Result<(), ParseError> {
+    let filter = EnvFilter::try_new(level)?;
+    let subscriber = fmt().with_env_filter(filter);
+
+    match format {
+        LogFormat::Pretty => subscriber.init(),
+        LogFormat::Json => subscriber
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+    Ok(())
+}
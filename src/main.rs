@@ -1,76 +1,278 @@
-use clap::Parser;
-use namada_scenario_tester::{config::AppConfig, runner::Runner, scenario::Scenario};
-use namada_sdk::control_flow::{install_shutdown_signal, ShutdownSignal};
+use clap::{Parser, ValueEnum};
+use namada_scenario_tester::{
+    bootstrap::{self, BootstrapReport},
+    checkpoint::Checkpoint,
+    config::{AppConfig, Cli, Command},
+    logging, metrics,
+    runner::Runner,
+    scenario::Scenario,
+    selection::{self, Assignment},
+    shutdown::{Shutdown, INTERRUPTED_EXIT_CODE},
+    sinks, soak, validator,
+};
 use rand::Rng;
-use std::{env, fs, io::Read, path::PathBuf};
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    process,
+};
+use tracing::{error, info, info_span, Instrument};
 
 #[tokio::main]
 async fn main() {
-    let config = AppConfig::parse();
+    let cli = Cli::parse();
+
+    let (mut config, soak_config) = match cli.command {
+        Some(Command::Validate { scenarios }) => validate(scenarios),
+        Some(Command::Soak { config, soak }) => (*config, Some(*soak)),
+        None => (
+            cli.config
+                .expect("Config should be present without a subcommand."),
+            None,
+        ),
+    };
+    if let Err(e) = logging::init(config.log_format, &config.log_level) {
+        println!("Invalid log level: {}", e);
+        process::exit(1);
+    }
+    // the workers derive their own seed from it
+    if config.seed.is_none() {
+        config.seed = Some(rand::thread_rng().gen());
+    }
+
+    if let Err(e) = sinks::from_config(&config) {
+        error!(error = %e, "Invalid report sinks");
+        process::exit(1);
+    }
+    if config.resume && config.workers > 1 {
+        error!("Only a single worker can resume a run");
+        process::exit(1);
+    }
+    if soak_config.is_some() && (config.scenario.is_some() || config.resume) {
+        error!("A soak runs generated scenarios, it can't run or resume a given one");
+        process::exit(1);
+    }
+    if let Some(addr) = config.metrics_addr {
+        if let Err(e) = metrics::serve(addr) {
+            error!(error = %e, "Can't serve metrics");
+            process::exit(1);
+        }
+    }
+
+    // fund a sub-faucet per worker so they don't share the faucet account
+    let bootstrap = match config.faucet_budget {
+        Some(budget) => match bootstrap::bootstrap(&config, budget).await {
+            Ok(bootstrap) => {
+                info!(
+                    sub_faucets = bootstrap.report.sub_faucets.len(),
+                    budget,
+                    tx_hash = %bootstrap.report.tx_hash,
+                    "Funded the sub-faucets"
+                );
+                Some(bootstrap)
+            }
+            Err(e) => {
+                error!(error = %e, "Can't fund the sub-faucets");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    if let Some(soak_config) = soak_config {
+        let shutdown = Shutdown::install();
+        let summary = soak::soak(config, soak_config, shutdown.clone(), bootstrap).await;
+        if shutdown.is_requested() {
+            process::exit(INTERRUPTED_EXIT_CODE);
+        }
+        process::exit(if summary.failed > 0 { 1 } else { 0 });
+    }
+
+    // the scenarios of each worker, unless a single one is given or resumed
+    let assignment = if config.scenario.is_some() || config.resume {
+        None
+    } else {
+        let assignment = selection::assign(
+            config.selection,
+            &config.scenarios,
+            config.selection_manifest.as_deref(),
+            config.workers,
+            config.seed.unwrap_or_default(),
+        )
+        .unwrap_or_else(|e| {
+            error!(error = %e, "Can't select the scenarios");
+            process::exit(1);
+        });
+        for (worker_id, scenarios) in assignment.iter().enumerate() {
+            info!(worker_id, ?scenarios, "Assigned scenarios");
+        }
+        Some(assignment)
+    };
+
+    let shutdown = Shutdown::install();
 
     let mut workers = vec![];
     for worker_id in 0..config.workers {
-        workers.push(async move { run(worker_id).await });
+        let mut config = config.clone();
+        if let Some(bootstrap) = &bootstrap {
+            config.faucet_sk = bootstrap.faucet_sk(worker_id);
+        }
+        let bootstrap = bootstrap.as_ref().map(|bootstrap| bootstrap.report.clone());
+        let scenarios = assignment
+            .as_ref()
+            .map(|assignment| assignment[worker_id as usize].clone());
+        let shutdown = shutdown.clone();
+        workers.push(
+            run(worker_id, config, shutdown, bootstrap, scenarios)
+                .instrument(info_span!("worker", worker_id)),
+        );
     }
 
     futures::future::join_all(workers).await;
-}
 
-async fn run(worker_id: u64) {
-    let config = AppConfig::parse();
+    if shutdown.is_requested() {
+        process::exit(INTERRUPTED_EXIT_CODE);
+    }
+}
 
-    let (scenario_json, scenario_path) = if let Some(scenario) = config.scenario.clone() {
-        (fs::read_to_string(&scenario).unwrap(), scenario)
+fn validate(scenarios: Vec<String>) -> ! {
+    let paths = if scenarios.is_empty() {
+        selection::scenario_files("scenarios").unwrap_or_else(|e| {
+            println!("{}", e);
+            process::exit(1);
+        })
     } else {
-        let paths = fs::read_dir("scenarios")
-            .unwrap()
-            .filter_map(|res| {
-                let file = res.unwrap();
-                if file.file_type().unwrap().is_file()
-                    && file.path().extension().is_some()
-                    && file
-                        .path()
-                        .extension()
-                        .unwrap()
-                        .eq_ignore_ascii_case("json")
-                {
-                    Some(file.path())
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<PathBuf>>();
-        let scenario_index = rand::thread_rng().gen_range(0..paths.len());
-
-        let scenario_file_path = paths.get(scenario_index).unwrap().clone();
-
-        let mut file = fs::File::open(&scenario_file_path).unwrap();
-        let mut content = String::new();
-        file.read_to_string(&mut content).unwrap();
-
-        (content, scenario_file_path.to_string_lossy().to_string())
+        scenarios.into_iter().map(PathBuf::from).collect()
     };
 
-    let scenario: Scenario = serde_json::from_str(&scenario_json).unwrap();
-    if let Ok(folder) = env::var("ANTITHESIS_OUTPUT_DIR") {
-        let output_path = format!("{}/scenario-{}.json", folder, worker_id);
-        fs::write(
-            output_path,
-            format!("{}\n", serde_json::to_string(&scenario).unwrap()),
+    let mut total_invalid = 0;
+    for path in &paths {
+        let scenario = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<Scenario>(&json).map_err(|e| e.to_string()));
+        let issues = match scenario {
+            Ok(scenario) => validator::validate(&scenario)
+                .into_iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<String>>(),
+            Err(e) => vec![format!("can't load scenario: {}", e)],
+        };
+
+        if issues.is_empty() {
+            println!("{}: ok", path.to_string_lossy());
+        } else {
+            total_invalid += 1;
+            println!("{}: {} issue(s)", path.to_string_lossy(), issues.len());
+            for issue in issues {
+                println!("  - {}", issue);
+            }
+        }
+    }
+
+    println!(
+        "Validated {} scenario(s), {} invalid.",
+        paths.len(),
+        total_invalid
+    );
+    process::exit(if total_invalid > 0 { 1 } else { 0 })
+}
+
+async fn run(
+    worker_id: u64,
+    config: AppConfig,
+    shutdown: Shutdown,
+    bootstrap: Option<BootstrapReport>,
+    scenarios: Option<Vec<String>>,
+) {
+    if let (true, Some(work_dir)) = (config.resume, config.worker_dir(worker_id)) {
+        let checkpoint = Checkpoint::load(&work_dir).unwrap_or_else(|e| {
+            error!(error = %e, "Can't resume run");
+            process::exit(1);
+        });
+        if checkpoint.chain_id != config.chain_id {
+            error!(
+                chain_id = %checkpoint.chain_id,
+                "Can't resume run, it was started on another chain"
+            );
+            process::exit(1);
+        }
+
+        let scenario = checkpoint.scenario.clone();
+        let scenario_path = checkpoint.scenario_name.clone();
+        let runner = Runner::new(shutdown, bootstrap, None);
+        run_scenario(
+            runner,
+            worker_id,
+            &config,
+            scenario,
+            scenario_path,
+            Some(checkpoint),
         )
-        .unwrap();
+        .await;
+        return;
     }
 
-    let scenario: Scenario = serde_json::from_str(&scenario_json).unwrap();
+    let (scenario_paths, assignment) = match scenarios {
+        Some(scenarios) => {
+            let assignment = Assignment {
+                strategy: config
+                    .selection
+                    .to_possible_value()
+                    .unwrap()
+                    .get_name()
+                    .to_string(),
+                scenarios: scenarios.clone(),
+            };
+            (scenarios, Some(assignment))
+        }
+        None => (config.scenario.clone().into_iter().collect(), None),
+    };
 
-    let mut runner = Runner::default();
-    let run_future = runner.run(worker_id, scenario, &config, scenario_path);
+    for scenario_path in scenario_paths {
+        if shutdown.is_requested() {
+            break;
+        }
+        // a scenario that can't be loaded is skipped, the worker runs the next one
+        let scenario = fs::read_to_string(&scenario_path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<Scenario>(&json).map_err(|e| e.to_string()));
+        let scenario = match scenario {
+            Ok(scenario) => scenario,
+            Err(e) => {
+                error!(scenario = %scenario_path, error = %e, "Can't load scenario");
+                continue;
+            }
+        };
 
-    let mut shutdown_signal = install_shutdown_signal(true);
-    let interrupt_future = shutdown_signal.wait_for_shutdown();
+        let runner = Runner::new(shutdown.clone(), bootstrap.clone(), assignment.clone());
+        run_scenario(runner, worker_id, &config, scenario, scenario_path, None).await;
+    }
+}
 
-    tokio::select! {
-        _ = run_future => {}
-        _ = interrupt_future => {}
+async fn run_scenario(
+    mut runner: Runner,
+    worker_id: u64,
+    config: &AppConfig,
+    scenario: Scenario,
+    scenario_path: String,
+    checkpoint: Option<Checkpoint>,
+) {
+    if let Ok(folder) = env::var("ANTITHESIS_OUTPUT_DIR") {
+        let output_path = format!("{}/scenario-{}.json", folder, worker_id);
+        // a worker may run several scenarios, one per line
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(output_path)
+            .unwrap();
+        writeln!(file, "{}", serde_json::to_string(&scenario).unwrap()).unwrap();
     }
+
+    let span = info_span!("scenario", name = %scenario_path);
+    runner
+        .run(worker_id, scenario, config, scenario_path, checkpoint)
+        .instrument(span)
+        .await;
 }
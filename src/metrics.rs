@@ -0,0 +1,166 @@
+use std::{convert::Infallible, net::SocketAddr, sync::OnceLock, time::Duration};
+
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use prometheus::{
+    exponential_buckets, Encoder, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge,
+    Opts, Registry, TextEncoder,
+};
+use thiserror::Error;
+use tracing::{error, info};
+
+use crate::state::state::StepOutcome;
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+#[derive(Error, Debug)]
+pub enum MetricsError {
+    #[error("can't listen on `{0}`: {1}")]
+    Bind(SocketAddr, String),
+}
+
+/// Live view of the workers, exposed on `/metrics` with `--metrics-addr`.
+/// Metrics are recorded whether or not they are exposed.
+pub struct Metrics {
+    registry: Registry,
+    steps: IntCounterVec,
+    step_duration: HistogramVec,
+    tx_inclusion: Histogram,
+    rpc_errors: IntCounterVec,
+    height: IntGauge,
+    epoch: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("scenario_tester".to_string()), None)
+            .expect("Registry should be created.");
+        // from half a second to about 4 minutes
+        let buckets = exponential_buckets(0.5, 2.0, 10).unwrap();
+
+        let steps = IntCounterVec::new(
+            Opts::new("steps_total", "Steps executed, by type and outcome"),
+            &["step_type", "outcome"],
+        )
+        .unwrap();
+        let step_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "step_duration_seconds",
+                "Time to run a step, retries included",
+            )
+            .buckets(buckets.clone()),
+            &["step_type", "outcome"],
+        )
+        .unwrap();
+        let tx_inclusion = Histogram::with_opts(
+            HistogramOpts::new(
+                "tx_inclusion_seconds",
+                "Time from the broadcast of a tx to its inclusion in a block",
+            )
+            .buckets(buckets),
+        )
+        .unwrap();
+        let rpc_errors = IntCounterVec::new(
+            Opts::new(
+                "rpc_errors_total",
+                "Requests an RPC endpoint failed to serve",
+            ),
+            &["endpoint"],
+        )
+        .unwrap();
+        let height = IntGauge::new("block_height", "Last block height seen").unwrap();
+        let epoch = IntGauge::new("epoch", "Last epoch seen").unwrap();
+
+        for collector in [
+            Box::new(steps.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(step_duration.clone()),
+            Box::new(tx_inclusion.clone()),
+            Box::new(rpc_errors.clone()),
+            Box::new(height.clone()),
+            Box::new(epoch.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("Metric should be registered.");
+        }
+
+        Self {
+            registry,
+            steps,
+            step_duration,
+            tx_inclusion,
+            rpc_errors,
+            height,
+            epoch,
+        }
+    }
+
+    pub fn record_step(&self, step_type: &str, outcome: &StepOutcome, duration: Duration) {
+        let labels = [step_type, outcome.kind()];
+        self.steps.with_label_values(&labels).inc();
+        self.step_duration
+            .with_label_values(&labels)
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn record_tx_inclusion(&self, duration: Duration) {
+        self.tx_inclusion.observe(duration.as_secs_f64());
+    }
+
+    pub fn record_rpc_error(&self, endpoint: &str) {
+        self.rpc_errors.with_label_values(&[endpoint]).inc();
+    }
+
+    pub fn record_chain_info(&self, height: u64, epoch: u64) {
+        self.height.set(height as i64);
+        self.epoch.set(epoch as i64);
+    }
+
+    fn encode(&self) -> (String, Vec<u8>) {
+        let encoder = TextEncoder::new();
+        let mut buffer = vec![];
+        encoder
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Metrics should be encoded.");
+        (encoder.format_type().to_string(), buffer)
+    }
+}
+
+pub fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// Serves the metrics on `/metrics` in the background.
+pub fn serve(addr: SocketAddr) -> Result<(), MetricsError> {
+    let server = Server::try_bind(&addr)
+        .map_err(|e| MetricsError::Bind(addr, e.to_string()))?
+        .serve(make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(handle))
+        }));
+
+    info!(%addr, "Serving metrics");
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            error!(error = %e, "Metrics server stopped");
+        }
+    });
+    Ok(())
+}
+
+async fn handle(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if request.uri().path() != "/metrics" {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NOT_FOUND;
+        return Ok(response);
+    }
+
+    let (content_type, body) = metrics().encode();
+    let mut response = Response::new(Body::from(body));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, content_type.parse().unwrap());
+    Ok(response)
+}
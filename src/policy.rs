@@ -0,0 +1,95 @@
+use std::{fmt::Display, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::scenario::StepResult;
+
+/// Seconds before the first retry of a step, doubled after each retry.
+pub const DEFAULT_BACKOFF: u64 = 5;
+
+/// Why a step failed, for the failures that may go away when the step is
+/// retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailureKind {
+    /// The step didn't complete in time, or its tx wasn't applied in time
+    Timeout,
+    /// The node couldn't be queried or the tx couldn't be submitted
+    RpcError,
+    /// The tx was applied but rejected by a validity predicate
+    VpRejection,
+    /// The wrapper of the tx was rejected, e.g. by the fee checks
+    WrapperRejection,
+    /// The step wrote outputs its schema doesn't declare, or values that
+    /// don't match their type
+    InvalidOutput,
+}
+
+impl Display for FailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureKind::Timeout => write!(f, "timeout"),
+            FailureKind::RpcError => write!(f, "rpc-error"),
+            FailureKind::VpRejection => write!(f, "vp-rejection"),
+            FailureKind::WrapperRejection => write!(f, "wrapper-rejection"),
+            FailureKind::InvalidOutput => write!(f, "invalid-output"),
+        }
+    }
+}
+
+/// How the runner runs a step: how long an attempt can take and which
+/// failures are retried.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StepPolicy {
+    /// Seconds after which an attempt is abandoned and fails with a timeout
+    pub timeout: Option<u64>,
+    /// Attempts made after the first one failed
+    #[serde(default)]
+    pub retries: u64,
+    #[serde(default = "default_backoff")]
+    pub backoff: u64,
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<FailureKind>,
+}
+
+impl Default for StepPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            retries: 0,
+            backoff: default_backoff(),
+            retry_on: default_retry_on(),
+        }
+    }
+}
+
+impl StepPolicy {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// Whether the step is run again after its `attempt`-th attempt (starting
+    /// at 1) resulted in `result`.
+    pub fn should_retry(&self, attempt: u64, result: &StepResult) -> bool {
+        attempt <= self.retries
+            && result.is_fail()
+            && result
+                .failure
+                .is_some_and(|failure| self.retry_on.contains(&failure))
+    }
+
+    /// How long to wait after the `attempt`-th attempt before retrying.
+    pub fn backoff(&self, attempt: u64) -> Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1) as u32);
+        Duration::from_secs(self.backoff.saturating_mul(factor))
+    }
+}
+
+fn default_backoff() -> u64 {
+    DEFAULT_BACKOFF
+}
+
+// VP rejections are usually deterministic, so they are only retried on demand
+fn default_retry_on() -> Vec<FailureKind> {
+    vec![FailureKind::Timeout, FailureKind::RpcError]
+}
@@ -4,11 +4,14 @@ use namada_sdk::rpc;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::value::Value,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
 };
 
 pub enum AccountQueryStorageKeys {
@@ -46,6 +49,15 @@ impl AccountQuery {
 impl Query for AccountQuery {
     type P = AccountQueryParameters;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(AccountQueryStorageKeys::Address, FieldType::Address),
+            OutputField::new(AccountQueryStorageKeys::Threshold, FieldType::Number),
+            OutputField::new(AccountQueryStorageKeys::TotalPublicKeys, FieldType::Number),
+            OutputField::new("public_key_at_index-{}", FieldType::PublicKey),
+        ])
+    }
+
     async fn execute(&self, sdk: &Sdk, parameters: Self::P, _state: &Storage) -> StepResult {
         let owner_address = parameters.address.to_namada_address(sdk).await;
 
@@ -54,7 +66,7 @@ impl Query for AccountQuery {
         let account_info = match account_info {
             Ok(Some(account)) => account,
             Ok(None) => return StepResult::fail("No epoch found".to_string()),
-            Err(e) => return StepResult::fail(e.to_string()),
+            Err(e) => return StepResult::fail_rpc(e.to_string()),
         };
 
         let mut storage = StepStorage::default();
@@ -94,27 +106,13 @@ pub struct AccountQueryParameters {
 impl QueryParam for AccountQueryParameters {
     type D = AccountQueryParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self {
-        let address = match dto.address {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(address) = resolver.account("address", dto.address)? else {
+            return Ok(None);
         };
 
-        Self { address }
+        Ok(Some(Self { address }))
     }
 }
@@ -3,11 +3,14 @@ use namada_sdk::rpc;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::value::Value,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
 };
 
 use super::{Query, QueryParam};
@@ -41,6 +44,14 @@ impl BalanceQuery {
 impl Query for BalanceQuery {
     type P = BalanceQueryParameters;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(BalanceQueryStorageKeys::Address, FieldType::Address),
+            OutputField::new(BalanceQueryStorageKeys::Amount, FieldType::Amount),
+            OutputField::new(BalanceQueryStorageKeys::TokenAddress, FieldType::Address),
+        ])
+    }
+
     async fn execute(&self, sdk: &Sdk, parameters: Self::P, _state: &Storage) -> StepResult {
         let owner_address = parameters.address.to_namada_address(sdk).await;
         let token_address = parameters.token.to_namada_address(sdk).await;
@@ -55,7 +66,7 @@ impl Query for BalanceQuery {
 
         let balance = match balance {
             Ok(balance) => balance.to_string(),
-            Err(e) => return StepResult::fail(e.to_string()),
+            Err(e) => return StepResult::fail_rpc(e.to_string()),
         };
 
         let mut storage = StepStorage::default();
@@ -91,35 +102,16 @@ pub struct BalanceQueryParameters {
 impl QueryParam for BalanceQueryParameters {
     type D = BalanceQueryParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self {
-        let address = match dto.address {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(address) = resolver.account("address", dto.address)? else {
+            return Ok(None);
         };
-        let token = match dto.token {
-            Value::Ref { value, field } => {
-                let address = state.get_step_item(&value, &field);
-                AccountIndentifier::Address(address)
-            }
-            Value::Value { value } => AccountIndentifier::Alias(value),
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(token) = resolver.account("token", dto.token)? else {
+            return Ok(None);
         };
 
-        Self { address, token }
+        Ok(Some(Self { address, token }))
     }
 }
@@ -5,8 +5,11 @@ use serde::{Deserialize, Serialize};
 use crate::{
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::value::Value,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
 };
 
 use super::{Query, QueryParam};
@@ -27,7 +30,7 @@ impl ToString for BondQueryStorageKeys {
         match self {
             BondQueryStorageKeys::Epoch => "epoch".to_string(),
             BondQueryStorageKeys::BondsTotal => "amount".to_string(),
-            BondQueryStorageKeys::UnbondsTotal => "token-address".to_string(),
+            BondQueryStorageKeys::UnbondsTotal => "unbond-amount".to_string(),
             // BondQueryStorageKeys::WithdrawableTotal => todo!(),
             BondQueryStorageKeys::Bond(validator, delegator) => {
                 format!("{}-{}-bond", validator, delegator).to_string()
@@ -58,6 +61,18 @@ impl BondedStakeQuery {
 impl Query for BondedStakeQuery {
     type P = BondedStakeQueryParameters;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(BondQueryStorageKeys::Epoch, FieldType::Epoch),
+            OutputField::new(BondQueryStorageKeys::BondsTotal, FieldType::Amount),
+            OutputField::new(BondQueryStorageKeys::UnbondsTotal, FieldType::Amount),
+            OutputField::new("{}-{}-bond", FieldType::Amount),
+            OutputField::new("{}-{}-bond-epoch", FieldType::Epoch),
+            OutputField::new("{}-{}-unbond", FieldType::Amount),
+            OutputField::new("{}-{}-unbond-epoch", FieldType::Epoch),
+        ])
+    }
+
     async fn execute(&self, sdk: &Sdk, parameters: Self::P, _state: &Storage) -> StepResult {
         let epoch = if let Some(epoch) = parameters.epoch {
             Epoch::from(epoch)
@@ -72,7 +87,7 @@ impl Query for BondedStakeQuery {
 
         let bonds_and_unbonds = match bonds_and_unbonds {
             Ok(bonds_and_unbonds) => bonds_and_unbonds,
-            Err(e) => return StepResult::fail(e.to_string()),
+            Err(e) => return StepResult::fail_rpc(e.to_string()),
         };
 
         let mut storage = StepStorage::default();
@@ -136,17 +151,13 @@ pub struct BondedStakeQueryParameters {
 impl QueryParam for BondedStakeQueryParameters {
     type D = BondedStakeQueryParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self {
-        let epoch = match dto.epoch {
-            Some(Value::Ref { value, field }) => {
-                let epoch = state.get_step_item(&value, &field);
-                epoch.parse::<u64>().ok()
-            }
-            Some(Value::Value { value }) => value.parse::<u64>().ok(),
-            Some(Value::Fuzz { .. }) => unimplemented!(),
-            _ => None,
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(epoch) = resolver.optional("epoch", dto.epoch, Resolver::epoch)? else {
+            return Ok(None);
         };
 
-        Self { epoch }
+        Ok(Some(Self { epoch }))
     }
 }
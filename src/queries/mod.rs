@@ -1,6 +1,11 @@
 use async_trait::async_trait;
 
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::{schema::OutputSchema, state::Storage},
+    utils::value::ParameterError,
+};
 
 pub mod account;
 pub mod balance;
@@ -13,6 +18,8 @@ pub mod validators;
 pub trait Query {
     type P: QueryParam;
 
+    fn outputs() -> OutputSchema;
+
     async fn execute(&self, sdk: &Sdk, paramaters: Self::P, state: &Storage) -> StepResult;
 
     async fn run(
@@ -20,15 +27,19 @@ pub trait Query {
         sdk: &Sdk,
         dto: <<Self as Query>::P as QueryParam>::D,
         state: &Storage,
-    ) -> StepResult {
-        let parameters = Self::P::from_dto(dto, state);
-
-        self.execute(sdk, parameters, state).await
+    ) -> Result<StepResult, ParameterError> {
+        let parameters = if let Some(parameters) = Self::P::from_dto(dto, state)? {
+            parameters
+        } else {
+            return Ok(StepResult::no_op());
+        };
+
+        Ok(self.execute(sdk, parameters, state).await)
     }
 }
 
-pub trait QueryParam {
+pub trait QueryParam: Sized {
     type D;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self;
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError>;
 }
@@ -5,8 +5,11 @@ use serde::{Deserialize, Serialize};
 use crate::{
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::value::Value,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
 };
 
 use super::{Query, QueryParam};
@@ -46,6 +49,22 @@ impl ProposalQuery {
 impl Query for ProposalQuery {
     type P = ProposalQueryParameters;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(
+                ProposalQueryStorageKeys::ProposalStatus,
+                FieldType::ProposalStatus,
+            ),
+            OutputField::new(ProposalQueryStorageKeys::StartEpoch, FieldType::Epoch),
+            OutputField::new(ProposalQueryStorageKeys::EndEpoch, FieldType::Epoch),
+            OutputField::new(ProposalQueryStorageKeys::GraceEpoch, FieldType::Epoch),
+            OutputField::new(
+                ProposalQueryStorageKeys::ProposerAddress,
+                FieldType::Address,
+            ),
+        ])
+    }
+
     async fn execute(&self, sdk: &Sdk, parameters: Self::P, _state: &Storage) -> StepResult {
         let epoch = if let Some(epoch) = parameters.epoch {
             Epoch::from(epoch)
@@ -111,25 +130,16 @@ pub struct ProposalQueryParameters {
 impl QueryParam for ProposalQueryParameters {
     type D = ProposalQueryParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self {
-        let epoch = match dto.epoch {
-            Some(Value::Ref { value, field }) => {
-                let epoch = state.get_step_item(&value, &field);
-                epoch.parse::<u64>().ok()
-            }
-            Some(Value::Value { value }) => value.parse::<u64>().ok(),
-            Some(Value::Fuzz { .. }) => unimplemented!(),
-            _ => None,
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(epoch) = resolver.optional("epoch", dto.epoch, Resolver::epoch)? else {
+            return Ok(None);
         };
-        let proposal_id = match dto.proposal_id {
-            Value::Ref { value, field } => {
-                let proposal_id = state.get_step_item(&value, &field);
-                proposal_id.parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(proposal_id) = resolver.number("proposal_id", dto.proposal_id)? else {
+            return Ok(None);
         };
 
-        Self { proposal_id, epoch }
+        Ok(Some(Self { proposal_id, epoch }))
     }
 }
@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 use crate::{
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
 };
 
 use super::{Query, QueryParam};
@@ -49,6 +52,17 @@ impl ProposalsQuery {
 impl Query for ProposalsQuery {
     type P = ProposalsQueryParameters;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(ProposalsQueryStorageKeys::Total, FieldType::Number),
+            OutputField::new("proposal-status-{}", FieldType::ProposalStatus),
+            OutputField::new("proposal-start-epoch-{}", FieldType::Epoch),
+            OutputField::new("proposal-end-epoch-{}", FieldType::Epoch),
+            OutputField::new("proposal-grace-epoch-{}", FieldType::Epoch),
+            OutputField::new("proposal-proposer-address-{}", FieldType::Address),
+        ])
+    }
+
     async fn execute(&self, sdk: &Sdk, _parameters: Self::P, _state: &Storage) -> StepResult {
         let epoch = rpc::query_epoch(&sdk.namada.clone_client())
             .await
@@ -114,7 +128,7 @@ pub struct ProposalsQueryParameters {}
 impl QueryParam for ProposalsQueryParameters {
     type D = ProposalsQueryParametersDto;
 
-    fn from_dto(_dto: Self::D, _state: &Storage) -> Self {
-        Self {}
+    fn from_dto(_dto: Self::D, _state: &Storage) -> Result<Option<Self>, ParameterError> {
+        Ok(Some(Self {}))
     }
 }
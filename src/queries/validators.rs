@@ -8,8 +8,14 @@ use serde::{Deserialize, Serialize};
 use crate::{
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{misc::ValidatorState, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        misc::ValidatorState,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Query, QueryParam};
@@ -43,6 +49,17 @@ impl ValidatorsQuery {
 impl Query for ValidatorsQuery {
     type P = ValidatorsQueryParameters;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(
+                ValidatorsQueryStorageKeys::TotalValidator,
+                FieldType::Number,
+            ),
+            OutputField::new("validator-{}-address", FieldType::Address),
+            OutputField::new("validator-{}-state", FieldType::ValidatorState),
+        ])
+    }
+
     async fn execute(&self, sdk: &Sdk, parameters: Self::P, _state: &Storage) -> StepResult {
         let current_epoch = match parameters.epoch {
             Some(value) => namada_sdk::storage::Epoch::from(value),
@@ -100,16 +117,13 @@ pub struct ValidatorsQueryParameters {
 impl QueryParam for ValidatorsQueryParameters {
     type D = ValidatorsQueryParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self {
-        let epoch = dto.epoch.map(|value| match value {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                data.parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-
-        Self { epoch }
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(epoch) = resolver.optional("epoch", dto.epoch, Resolver::epoch)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self { epoch }))
     }
 }
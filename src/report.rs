@@ -1,39 +1,106 @@
 use std::{
-    fs::File,
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
     path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use itertools::Itertools;
 use markdown_gen::markdown::{AsMarkdown, List, Markdown};
-use minio::s3::{args::UploadObjectArgs, client::Client, creds::StaticProvider, http::BaseUrl};
 use serde::Serialize;
+use serde_json::Value as Json;
 
-use crate::{config::AppConfig, scenario::Scenario, state::state::Storage};
+use crate::{
+    bootstrap::BootstrapReport,
+    config::AppConfig,
+    policy::FailureKind,
+    scenario::Scenario,
+    selection::Assignment,
+    state::state::{StepOutcome, Storage},
+};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct JsonReport {
+    pub chain_id: String,
+    pub rpc: String,
+    pub scenario: String,
+    pub software_version: String,
+    /// txs were only simulated, see `--dry-run`
+    pub dry_run: bool,
+    /// rerunning with `--seed` picks the same fuzzed values for this worker
+    pub seed: u64,
+    pub worker_id: u64,
+    /// the run was stopped by a signal, see the steps that were not run
+    pub interrupted: bool,
+    /// the sub-faucets funded before the run, see `--faucet-budget`
+    pub bootstrap: Option<BootstrapReport>,
+    /// the scenarios the worker was assigned, see `--selection`
+    pub assignment: Option<Assignment>,
+    pub outcome: String,
+    pub steps: Vec<StepReport>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StepReport {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub step_type: String,
+    pub status: String,
+    pub outcome: String,
+    pub parameters: Json,
+    pub data: BTreeMap<String, String>,
+    /// values picked for fuzzed parameters and generated aliases
+    pub fuzzed: BTreeMap<String, String>,
+    /// unix timestamp in milliseconds
+    pub started_at: Option<u128>,
+    pub duration_ms: Option<u128>,
+    pub height: Option<String>,
+    pub epoch: Option<String>,
+    pub tx_hash: Option<String>,
+    pub wrapper_hash: Option<String>,
+    /// height of the block the tx was applied in
+    pub inclusion_height: Option<String>,
+    pub gas_used: Option<String>,
+    pub vp_errors: Option<Json>,
+    /// RPC endpoints that served the requests of the step
+    pub endpoints: Vec<String>,
+    pub attempts: Vec<AttemptReport>,
+}
 
 #[derive(Clone, Debug, Serialize)]
-pub struct StatusBody {
-    #[serde(rename(serialize = "commit_sha"))]
-    pub sha: String,
-    #[serde(rename(serialize = "repo_owner"))]
-    pub owner: String,
-    pub repo: String,
-    pub state: String,
-    pub description: String,
-    pub context: String,
+pub struct AttemptReport {
+    pub status: String,
+    pub outcome: String,
+    pub failure: Option<FailureKind>,
+    /// unix timestamp in milliseconds
+    pub started_at: Option<u128>,
+    pub duration_ms: u128,
+    pub endpoints: Vec<String>,
 }
 
 pub struct Report {
     pub config: AppConfig,
     pub storage: Storage,
     pub scenario: Scenario,
+    pub bootstrap: Option<BootstrapReport>,
+    pub assignment: Option<Assignment>,
 }
 
 impl Report {
-    pub fn new(config: &AppConfig, storage: Storage, scenario: Scenario) -> Self {
+    pub fn new(
+        config: &AppConfig,
+        storage: Storage,
+        scenario: Scenario,
+        bootstrap: Option<BootstrapReport>,
+        assignment: Option<Assignment>,
+    ) -> Self {
         Self {
             config: config.clone(),
             storage,
             scenario,
+            bootstrap,
+            assignment,
         }
     }
     pub fn generate_report(
@@ -50,16 +117,33 @@ impl Report {
         md.write("Info".heading(2)).unwrap();
 
         let outcome = self.storage.is_succesful().to_string();
-        let info_list = List::new(false)
+        let rpc = self.config.rpc.join(", ");
+        let mut info_list = List::new(false)
             .item("Chain ID: ".paragraph().append(self.config.chain_id.code()))
-            .item("RPC url: ".paragraph().append(self.config.rpc.code()))
+            .item("RPC url: ".paragraph().append(rpc.code()))
             .item("Outcome: ".paragraph().append(outcome.code()))
             .item("Scenario: ".paragraph().append(scenario_name.code()))
+            .item(
+                "Dry run: "
+                    .paragraph()
+                    .append(self.config.dry_run.to_string().code()),
+            )
+            .item(
+                "Seed: "
+                    .paragraph()
+                    .append(self.storage.fuzzer.seed().to_string().code()),
+            )
             .item(
                 "Software version: "
                     .paragraph()
                     .append(env!("VERGEN_GIT_SHA").code()),
             );
+        if let Some(sub_faucet) = self.sub_faucet() {
+            info_list = info_list.item("Sub-faucet: ".paragraph().append(sub_faucet.code()));
+        }
+        if let Some(selection) = self.selection() {
+            info_list = info_list.item("Selection: ".paragraph().append(selection.code()));
+        }
 
         md.write(info_list).unwrap();
         md.write("\n").unwrap();
@@ -71,16 +155,26 @@ impl Report {
             .iter()
             .sorted_by(|a, b| a.0.cmp(b.0))
         {
-            let step = self.scenario.steps.get(*id as usize).unwrap();
+            let step = self.scenario.step(*id).unwrap();
             let step_type = step.config.to_string();
 
             let outcome = step_outcome.to_string();
             let step_id = id.to_string();
+            let total_attempts = self
+                .storage
+                .step_executions
+                .get(id)
+                .map(|execution| execution.attempts.len())
+                .unwrap_or_default();
+            let attempts = total_attempts.to_string();
 
-            let step_list = List::new(false)
+            let mut step_list = List::new(false)
                 .title("Step id: ".paragraph().append(step_id.code()))
                 .item("Type: ".paragraph().append(step_type.code()))
                 .item("Outcome: ".paragraph().append(outcome.code()));
+            if total_attempts > 1 {
+                step_list = step_list.item("Attempts: ".paragraph().append(attempts.code()));
+            }
 
             md.write(step_list).unwrap();
             md.write("\n").unwrap();
@@ -89,63 +183,248 @@ impl Report {
         (report_path, outcome)
     }
 
-    pub async fn upload_report(
-        minio_url: &str,
-        minio_access_key: &str,
-        minio_secret_key: &str,
-        name: &str,
-        report_path: &Path,
-    ) {
-        let base_url = minio_url.parse::<BaseUrl>().unwrap();
-
-        let static_provider = StaticProvider::new(minio_access_key, minio_secret_key, None);
-
-        let client = Client::new(
-            base_url.clone(),
-            Some(Box::new(static_provider)),
-            None,
-            None,
-        )
-        .unwrap();
-
-        client
-            .upload_object(
-                &UploadObjectArgs::new(
-                    "scenario-testing-results",
-                    name,
-                    report_path.to_str().unwrap(),
-                )
-                .unwrap(),
+    /// The address the worker signed with instead of the faucet, if any.
+    fn sub_faucet(&self) -> Option<&str> {
+        let worker_id = self.storage.fuzzer.worker_id() as usize;
+        self.bootstrap
+            .as_ref()
+            .and_then(|bootstrap| bootstrap.sub_faucets.get(worker_id))
+            .map(String::as_str)
+    }
+
+    /// The selection strategy and the scenarios the worker was assigned, if any.
+    fn selection(&self) -> Option<String> {
+        self.assignment.as_ref().map(|assignment| {
+            format!(
+                "{} ({})",
+                assignment.strategy,
+                assignment.scenarios.join(", ")
             )
-            .await
-            .unwrap();
+        })
     }
 
-    pub async fn update_commit_status(
-        report_url: &str,
-        artifacts_url: &str,
-        state: &str,
-        sha: &str,
-        report_name: &str,
+    pub fn step_reports(&self) -> Vec<StepReport> {
+        self.storage
+            .step_results
+            .iter()
+            .sorted_by(|a, b| a.0.cmp(b.0))
+            .map(|(id, step_outcome)| {
+                let step = self.scenario.step(*id).unwrap();
+                let data: BTreeMap<String, String> = self
+                    .storage
+                    .step_states
+                    .get(id)
+                    .map(|step_storage| step_storage.storage.clone().into_iter().collect())
+                    .unwrap_or_default();
+                let execution = self.storage.step_executions.get(id);
+
+                StepReport {
+                    id: *id,
+                    step_type: step.config.to_string(),
+                    status: step_outcome.kind().to_string(),
+                    outcome: step_outcome.to_string(),
+                    parameters: execution
+                        .map(|execution| execution.parameters.clone())
+                        .unwrap_or_default(),
+                    fuzzed: execution
+                        .map(|execution| execution.fuzzed.clone().into_iter().collect())
+                        .unwrap_or_default(),
+                    started_at: execution.and_then(|execution| unix_millis(execution.started_at)),
+                    duration_ms: execution.map(|execution| execution.duration.as_millis()),
+                    height: data.get("height").cloned(),
+                    epoch: data.get("epoch").cloned(),
+                    tx_hash: data.get("tx-hash").cloned(),
+                    wrapper_hash: data.get("wrapper-hash").cloned(),
+                    inclusion_height: data.get("inclusion-height").cloned(),
+                    gas_used: data.get("gas-used").cloned(),
+                    // VP errors are serialized as json, unless the tx failed for another reason
+                    vp_errors: data.get("vp-errors").map(|errors| {
+                        serde_json::from_str(errors)
+                            .unwrap_or_else(|_| Json::String(errors.clone()))
+                    }),
+                    endpoints: execution
+                        .map(|execution| {
+                            execution
+                                .attempts
+                                .iter()
+                                .flat_map(|attempt| attempt.endpoints.clone())
+                                .unique()
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    attempts: execution
+                        .map(|execution| {
+                            execution
+                                .attempts
+                                .iter()
+                                .map(|attempt| AttemptReport {
+                                    status: attempt.outcome.kind().to_string(),
+                                    outcome: attempt.outcome.to_string(),
+                                    failure: attempt.failure,
+                                    started_at: unix_millis(attempt.started_at),
+                                    duration_ms: attempt.duration.as_millis(),
+                                    endpoints: attempt.endpoints.clone(),
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    data,
+                }
+            })
+            .collect()
+    }
+
+    pub fn generate_json_report(
+        &self,
+        base_dir: &Path,
+        name: &str,
         scenario_name: &str,
-    ) {
-        let client = reqwest::Client::new();
-
-        let status_url = format!("{}/v1/report/status", report_url);
-        let status_body = StatusBody {
-            sha: sha.to_string(),
-            owner: "anoma".to_string(),
-            repo: "namada".to_string(),
-            state: state.to_string(),
-            description: format!("{}/scenario-testing-results/{}", artifacts_url, report_name),
-            context: format!("Scenario {}", scenario_name),
+    ) -> PathBuf {
+        fs::create_dir_all(base_dir).unwrap();
+        let report_path = base_dir.join(name);
+
+        let report = JsonReport {
+            chain_id: self.config.chain_id.clone(),
+            rpc: self.config.rpc.join(","),
+            scenario: scenario_name.to_string(),
+            software_version: env!("VERGEN_GIT_SHA").to_string(),
+            dry_run: self.config.dry_run,
+            seed: self.storage.fuzzer.seed(),
+            worker_id: self.storage.fuzzer.worker_id(),
+            interrupted: self
+                .storage
+                .step_results
+                .values()
+                .any(StepOutcome::is_not_run),
+            bootstrap: self.bootstrap.clone(),
+            assignment: self.assignment.clone(),
+            outcome: self.storage.is_succesful().to_string(),
+            steps: self.step_reports(),
         };
 
-        client
-            .post(&status_url)
-            .json(&status_body)
-            .send()
-            .await
-            .unwrap();
+        let file_report = File::create(&report_path).unwrap();
+        serde_json::to_writer_pretty(file_report, &report).unwrap();
+
+        report_path
     }
+
+    /// A test suite for the scenario with a test case per step. No-op, not run
+    /// and ignored check steps are skipped, steps that weren't successful fail.
+    pub fn generate_junit_report(
+        &self,
+        base_dir: &Path,
+        name: &str,
+        scenario_name: &str,
+    ) -> PathBuf {
+        fs::create_dir_all(base_dir).unwrap();
+        let report_path = base_dir.join(name);
+
+        let steps = self.step_reports();
+        let total_skipped = steps
+            .iter()
+            .filter(|step| matches!(step.status.as_str(), "no-op" | "check-skip" | "not-run"))
+            .count();
+        let total_failures =
+            steps.iter().filter(|step| step.status != "success").count() - total_skipped;
+        let total_time = steps
+            .iter()
+            .filter_map(|step| step.duration_ms)
+            .sum::<u128>() as f64
+            / 1000.0;
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            steps.len(),
+            total_failures,
+            total_skipped,
+            total_time
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            escape_xml(scenario_name),
+            steps.len(),
+            total_failures,
+            total_skipped,
+            total_time
+        ));
+        let rpc = self.config.rpc.join(",");
+        let dry_run = self.config.dry_run.to_string();
+        let seed = self.storage.fuzzer.seed().to_string();
+        let worker_id = self.storage.fuzzer.worker_id().to_string();
+        let sub_faucet = self.sub_faucet().unwrap_or_default();
+        let selection = self.selection().unwrap_or_default();
+        xml.push_str("    <properties>\n");
+        for (property, value) in [
+            ("chain-id", self.config.chain_id.as_str()),
+            ("rpc", rpc.as_str()),
+            ("software-version", env!("VERGEN_GIT_SHA")),
+            ("dry-run", dry_run.as_str()),
+            ("seed", seed.as_str()),
+            ("worker-id", worker_id.as_str()),
+            ("sub-faucet", sub_faucet),
+            ("selection", selection.as_str()),
+        ] {
+            xml.push_str(&format!(
+                "      <property name=\"{}\" value=\"{}\"/>\n",
+                property,
+                escape_xml(value)
+            ));
+        }
+        xml.push_str("    </properties>\n");
+
+        for step in &steps {
+            xml.push_str(&format!(
+                "    <testcase name=\"{} ({})\" classname=\"{}\" time=\"{:.3}\">\n",
+                escape_xml(&step.step_type),
+                step.id,
+                escape_xml(scenario_name),
+                step.duration_ms.unwrap_or_default() as f64 / 1000.0
+            ));
+            match step.status.as_str() {
+                "success" => (),
+                "no-op" | "check-skip" | "not-run" => xml.push_str(&format!(
+                    "      <skipped message=\"{}\"/>\n",
+                    escape_xml(&step.outcome)
+                )),
+                _ => xml.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                    escape_xml(&step.outcome),
+                    step.status,
+                    escape_xml(
+                        &step
+                            .vp_errors
+                            .as_ref()
+                            .map(|errors| errors.to_string())
+                            .unwrap_or_default()
+                    )
+                )),
+            }
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&serde_json::to_string_pretty(step).unwrap())
+            ));
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+
+        let mut file_report = File::create(&report_path).unwrap();
+        file_report.write_all(xml.as_bytes()).unwrap();
+
+        report_path
+    }
+}
+
+pub(crate) fn unix_millis(time: SystemTime) -> Option<u128> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|time| time.as_millis())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
 }
@@ -1,65 +1,159 @@
 use std::{
-    str::FromStr,
-    thread,
-    time::{Duration, Instant},
+    collections::BTreeSet,
+    fs, thread,
+    time::{Duration, SystemTime},
 };
 
-use namada_sdk::{
-    io::NullIo, masp::fs::FsShieldedUtils, rpc::is_public_key_revealed, signing::default_sign,
-    wallet::fs::FsWalletUtils, Namada,
+use futures::{
+    future::{join_all, LocalBoxFuture},
+    stream::FuturesUnordered,
+    FutureExt, StreamExt,
 };
+use namada_sdk::{io::NullIo, masp::fs::FsShieldedUtils, wallet::fs::FsWalletUtils, Namada};
+use rand::Rng;
 use tempfile::tempdir;
-use tendermint_rpc::{Client, HttpClient, Url};
+use tendermint_rpc::Client;
+use tokio::time::sleep;
+use tracing::{error, info, info_span, warn, Instrument, Span};
 
 use crate::{
-    config::AppConfig, report::Report, scenario::Scenario, sdk::namada::Sdk, state::state::Storage,
+    bootstrap::BootstrapReport,
+    checkpoint::Checkpoint,
+    config::{AppConfig, InvalidScenarioPolicy, Schedule},
+    flow::{
+        IfParametersDto, IfStep, ParallelParametersDto, ParallelStep, RepeatParameters,
+        RepeatParametersDto, RepeatStep,
+    },
+    metrics::metrics,
+    policy::FailureKind,
+    report::Report,
+    scenario::{Scenario, Step, StepResult, StepType},
+    scheduler::StepGraph,
+    sdk::{
+        namada::Sdk,
+        rpc::{self, RpcPool},
+    },
+    selection::Assignment,
+    shutdown::Shutdown,
+    sinks::{self, RunReports},
+    state::state::{StepExecution, StepStorage, Storage},
+    utils::fuzz::Fuzzer,
 };
-use namada_sdk::args::TxBuilder;
 
 #[derive(Clone, Debug, Default)]
 pub struct Runner {
     storage: Storage,
+    // top level steps completed before the run was resumed
+    resumed_steps: BTreeSet<u64>,
+    shutdown: Shutdown,
+    bootstrap: Option<BootstrapReport>,
+    assignment: Option<Assignment>,
 }
 
 impl Runner {
+    pub fn new(
+        shutdown: Shutdown,
+        bootstrap: Option<BootstrapReport>,
+        assignment: Option<Assignment>,
+    ) -> Self {
+        Self {
+            shutdown,
+            bootstrap,
+            assignment,
+            ..Default::default()
+        }
+    }
+
+    /// Runs every try of `scenario` and publishes the reports of each one,
+    /// returns the reports of the tries that ran.
     pub async fn run(
         &mut self,
         worker_id: u64,
         scenario: Scenario,
         config: &AppConfig,
         scenario_name: String,
-    ) {
-        let base_dir = tempdir().unwrap().path().to_path_buf();
-        println!("Using directory: {}", base_dir.to_string_lossy());
-        println!("Using scenario: {}", scenario_name);
+        checkpoint: Option<Checkpoint>,
+    ) -> Vec<RunReports> {
+        // a temporary directory is removed once the run is done, unless it was
+        // interrupted and can be resumed
+        let mut temp_dir = None;
+        let base_dir = match config.worker_dir(worker_id) {
+            Some(work_dir) => {
+                fs::create_dir_all(&work_dir).expect("Work directory should be created.");
+                work_dir
+            }
+            None => {
+                let dir = tempdir().expect("Temporary directory should be created.");
+                let path = dir.path().to_path_buf();
+                temp_dir = Some(dir);
+                path
+            }
+        };
+        info!(directory = %base_dir.to_string_lossy(), "Using directory");
 
-        let url = Url::from_str(&config.rpc).expect("invalid RPC address");
-        let http_client = HttpClient::new(url).unwrap();
+        let rpc_pool = RpcPool::new(&config.rpc, config.rpc_selection);
 
         // Setup wallet storage
         let wallet_path = base_dir.join("wallet");
-        let wallet = FsWalletUtils::new(wallet_path);
+        let mut wallet = FsWalletUtils::new(wallet_path);
 
         // Setup shielded context storage
         let shielded_ctx_path = base_dir.as_path().to_owned();
-        let shielded_ctx = FsShieldedUtils::new(shielded_ctx_path);
+        let mut shielded_ctx = FsShieldedUtils::new(shielded_ctx_path);
+
+        let start_try_index = match &checkpoint {
+            Some(checkpoint) => {
+                info!(try_index = checkpoint.try_index, "Resuming try");
+                wallet
+                    .load()
+                    .expect("Wallet should be loaded from the work directory.");
+                shielded_ctx
+                    .load()
+                    .await
+                    .expect("Shielded context should be loaded from the work directory.");
+                self.storage = Checkpoint::load_storage(&base_dir)
+                    .expect("Storage should be loaded from the work directory.");
+                self.resumed_steps = scenario
+                    .steps
+                    .iter()
+                    .map(|step| step.id)
+                    .filter(|id| {
+                        self.storage
+                            .step_results
+                            .get(id)
+                            .is_some_and(|outcome| !outcome.is_not_run())
+                    })
+                    .collect();
+                checkpoint.try_index
+            }
+            None => {
+                let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+                self.storage.fuzzer = Fuzzer::new(seed, worker_id);
+                0
+            }
+        };
+        info!(seed = self.storage.fuzzer.seed(), "Using seed");
 
         let io = NullIo;
 
         let sdk = Sdk::new(
             config,
             &base_dir,
-            http_client.clone(),
+            rpc_pool.clone(),
             wallet,
             shielded_ctx,
             io,
         )
         .await;
         let scenario_settings = &scenario.settings;
+        let report_sinks = sinks::from_config(config).expect("Report sinks should be configured.");
 
-        // Wait for the first 3 blocks
+        // Wait for the first 3 blocks, nothing ran yet if the run is interrupted
         loop {
-            let latest_blocked = http_client.latest_block().await;
+            if self.shutdown.is_requested() {
+                return vec![];
+            }
+            let latest_blocked = rpc_pool.latest_block().await;
             if let Ok(block) = latest_blocked {
                 if block.block.header.height.value() > 2 {
                     break;
@@ -69,169 +163,495 @@ impl Runner {
             }
         }
 
-        let faucet_address = sdk
-            .namada
-            .wallet
-            .read()
-            .await
-            .find_address("faucet")
-            .unwrap()
-            .into_owned();
+        sdk.reveal_faucet_pk().await;
 
-        loop {
-            let is_faucet_pk_revealed =
-                is_public_key_revealed(&sdk.namada.clone_client(), &faucet_address).await;
-
-            if let Ok(is_revealed) = is_faucet_pk_revealed {
-                if !is_revealed {
-                    let faucet_pk = sdk
-                        .namada
-                        .wallet
-                        .read()
-                        .await
-                        .find_public_key("faucet")
-                        .unwrap();
-
-                    let reveal_pk_tx_builder = sdk
-                        .namada
-                        .new_reveal_pk(faucet_pk.clone())
-                        .signing_keys(vec![faucet_pk.clone()])
-                        .wrapper_fee_payer(faucet_pk); // workaround due to scenario generator limitation
-
-                    let (mut reveal_tx, signing_data) =
-                        reveal_pk_tx_builder.build(&sdk.namada).await.unwrap();
-
-                    sdk.namada
-                        .sign(
-                            &mut reveal_tx,
-                            &reveal_pk_tx_builder.tx,
-                            signing_data,
-                            default_sign,
-                            (),
-                        )
-                        .await
-                        .unwrap();
-
-                    sdk.namada
-                        .submit(reveal_tx.clone(), &reveal_pk_tx_builder.tx)
-                        .await
-                        .unwrap();
-                }
+        let mut run_reports = vec![];
+        for try_index in start_try_index..=scenario_settings.retry_for.unwrap_or_default() {
+            if self.shutdown.is_requested() {
                 break;
-            } else {
-                thread::sleep(Duration::from_secs(2));
             }
+            let checkpoint = Checkpoint {
+                chain_id: config.chain_id.clone(),
+                scenario_name: scenario_name.clone(),
+                scenario: scenario.clone(),
+                try_index,
+            };
+            checkpoint
+                .save(&base_dir)
+                .expect("Checkpoint should be saved to the work directory.");
+
+            match config.schedule {
+                Schedule::Sequential => self.run_steps(&scenario.steps, &sdk, config).await,
+                Schedule::Dag => self.run_dag(&scenario.steps, &sdk, config).await,
+            };
+            self.resumed_steps.clear();
+            if self.shutdown.is_requested() {
+                self.skip_interrupted_steps(&scenario.steps);
+            }
+
+            let scenario_name = scenario_name.replace(".json", "").replace("scenarios/", "");
+            let report = Report::new(
+                config,
+                self.storage.clone(),
+                scenario.clone(),
+                self.bootstrap.clone(),
+                self.assignment.clone(),
+            );
+            let report_name = match &config.sha {
+                Some(sha) => format!(
+                    "report-{}-{}-{}-{}-{}",
+                    config.chain_id,
+                    scenario_name,
+                    &sha[..sha.len().min(8)],
+                    worker_id,
+                    try_index
+                ),
+                None => format!(
+                    "report-{}-{}-{}-{}",
+                    config.chain_id, scenario_name, worker_id, try_index
+                ),
+            };
+
+            info!(try_index, "Building reports");
+
+            let (markdown, outcome) =
+                report.generate_report(&base_dir, &format!("{}.md", report_name), &scenario_name);
+            let json = report.generate_json_report(
+                &base_dir,
+                &format!("{}.json", report_name),
+                &scenario_name,
+            );
+            let junit = report.generate_junit_report(
+                &base_dir,
+                &format!("{}.xml", report_name),
+                &scenario_name,
+            );
+            let reports = RunReports {
+                name: report_name,
+                scenario_name,
+                worker_id,
+                try_index,
+                outcome,
+                succeeded: self.storage.is_succesful().is_succesful(),
+                markdown,
+                json,
+                junit,
+            };
+
+            for sink in &report_sinks {
+                info!(sink = sink.name(), "Publishing reports");
+                if let Err(e) = sink.publish(&reports).await {
+                    error!(sink = sink.name(), error = %e, "Can't publish reports");
+                }
+            }
+            run_reports.push(reports);
         }
 
-        for try_index in 0..=scenario_settings.retry_for.unwrap_or_default() {
-            for step in &scenario.steps {
-                println!(
-                    "Worker id {} running step {} ({})...",
-                    worker_id, step.config, step.id
-                );
-                let now = Instant::now();
-                let result = step.run(&self.storage, &sdk, config.avoid_check).await;
-                let elapsed = now.elapsed().as_secs();
-                if result.is_strict_succesful() {
-                    println!(
-                        "Worker id {} step {} executed succesfully ({}s).",
-                        worker_id, step.config, elapsed
-                    );
-                    self.storage.save_step_result(step.id, result)
-                } else if result.is_noop() {
-                    println!(
-                        "Worker id {} step {} was a no-op ({}).",
-                        worker_id, step.config, elapsed
-                    );
-                    self.storage.save_step_result(step.id, result)
-                } else if result.is_fail() {
-                    println!(
-                        "Worker id {} step {} errored bepbop: error is <{}> ({}).",
-                        worker_id,
-                        step.config,
-                        result.fail_error(),
-                        elapsed
-                    );
-                    self.storage.save_step_result(step.id, result)
-                } else if result.is_skip() {
-                    println!(
-                        "Check was {}, but we result will be ignored ({}).",
-                        if result.outcome.get_skip_outcome() {
-                            "successful"
-                        } else {
-                            "unsuccessful"
-                        },
-                        elapsed
-                    );
-                    self.storage.save_step_result(step.id, result)
+        if self.shutdown.is_requested() {
+            if let Some(temp_dir) = temp_dir {
+                let work_dir = temp_dir.into_path();
+                info!(directory = %work_dir.to_string_lossy(), "Keeping directory to resume the run");
+            }
+        }
+        info!("Done");
+        run_reports
+    }
+
+    /// Runs `steps` in order, returns `false` if the run has to stop.
+    fn run_steps<'a>(
+        &'a mut self,
+        steps: &'a [Step],
+        sdk: &'a Sdk,
+        config: &'a AppConfig,
+    ) -> LocalBoxFuture<'a, bool> {
+        async move {
+            for step in steps {
+                if self.shutdown.is_requested() {
+                    return false;
+                }
+                if self.resumed_steps.contains(&step.id) {
+                    continue;
+                }
+                let span = step_span(step);
+                span.in_scope(|| info!("Running step"));
+                self.storage.fuzzer.start(step.id);
+                let (result, execution, proceed) = if step.config.is_control_flow() {
+                    let execution = StepExecution::start(step.resolved_parameters(&self.storage));
+                    let (result, proceed) = match &step.config {
+                        StepType::If { parameters } => {
+                            self.run_if(step, parameters, sdk, config)
+                                .instrument(span.clone())
+                                .await
+                        }
+                        StepType::Repeat { parameters } => {
+                            self.run_repeat(step, parameters, sdk, config)
+                                .instrument(span.clone())
+                                .await
+                        }
+                        StepType::Parallel { parameters } => {
+                            self.run_parallel(parameters, sdk, config)
+                                .instrument(span.clone())
+                                .await
+                        }
+                        _ => unreachable!(),
+                    };
+                    // a block that was interrupted is run again from its start
+                    // when the run is resumed
+                    let result = if !proceed && self.shutdown.is_requested() {
+                        StepResult::not_run()
+                    } else {
+                        step.config.outputs().enforce(result)
+                    };
+                    let execution = execution.finish(self.storage.fuzzer.take_values());
+                    (result, execution, proceed)
                 } else {
-                    println!(
-                        "Worker id {} step check {} errored riprip: {} ({}).",
-                        worker_id, step.config, result.outcome, elapsed
-                    );
-                    self.storage.save_step_result(step.id, result);
+                    let (result, execution) =
+                        Self::run_step(step, &self.storage, sdk, config, &self.shutdown)
+                            .instrument(span)
+                            .await;
+                    (result, execution, true)
+                };
+                let proceed = self.save_result(step, result, execution, config) && proceed;
+                self.checkpoint(sdk).await;
+                if !proceed {
+                    return false;
+                }
+            }
+            true
+        }
+        .boxed_local()
+    }
+
+    /// Runs `steps` as soon as the steps they depend on are done, see
+    /// `StepGraph`. Returns `false` if the run has to stop, the steps already
+    /// running are awaited first.
+    async fn run_dag(&mut self, steps: &[Step], sdk: &Sdk, config: &AppConfig) -> bool {
+        let graph = StepGraph::new(steps);
+        let mut done = steps
+            .iter()
+            .map(|step| self.resumed_steps.contains(&step.id))
+            .collect::<Vec<bool>>();
+        let mut started = done.clone();
+        let mut running = FuturesUnordered::new();
+        let mut proceed = true;
+
+        loop {
+            for (index, step) in steps.iter().enumerate() {
+                if !proceed || self.shutdown.is_requested() {
                     break;
                 }
-                println!();
-            }
-
-            if let (
-                Some(report_url),
-                Some(sha),
-                Some(minio_url),
-                Some(minio_access_key),
-                Some(minio_secret_key),
-                Some(artifacts_url),
-            ) = (
-                &config.report_url,
-                &config.sha,
-                &config.minio_url,
-                &config.minio_access_key,
-                &config.minio_secret_key,
-                &config.artifacts_url,
-            ) {
-                let mut sha_short = sha.clone();
-                sha_short.truncate(8);
-                let scenario_name = &scenario_name.replace(".json", "").replace("scenarios/", "");
-                let report_name = format!(
-                    "report-{}-{}-{}-{}.md",
-                    config.chain_id, scenario_name, sha_short, try_index
+                let is_ready = graph
+                    .dependencies(index)
+                    .iter()
+                    .all(|dependency| done[*dependency]);
+                if started[index] || !is_ready {
+                    continue;
+                }
+                started[index] = true;
+
+                if step.config.is_control_flow() {
+                    // every other step is either done or waiting for this one
+                    proceed = self
+                        .run_steps(std::slice::from_ref(step), sdk, config)
+                        .await;
+                    done[index] = true;
+                    continue;
+                }
+
+                // the steps this one depends on are done, so their results
+                // are already in storage
+                self.storage.fuzzer.start(step.id);
+                let storage = self.storage.clone();
+                let shutdown = self.shutdown.clone();
+                running.push(
+                    async move {
+                        info!("Running step");
+                        let (result, execution) =
+                            Self::run_step(step, &storage, sdk, config, &shutdown).await;
+                        (index, result, execution)
+                    }
+                    .instrument(step_span(step)),
                 );
+            }
 
-                println!("Building report...");
-
-                let (report_path, outcome) = Report::new(
-                    config,
-                    self.storage.clone(),
-                    scenario.clone(),
-                )
-                .generate_report(&base_dir, &report_name, scenario_name);
-
-                println!("Uploading report...");
-
-                Report::upload_report(
-                    minio_url,
-                    minio_access_key,
-                    minio_secret_key,
-                    &report_name,
-                    &report_path,
-                )
-                .await;
-
-                Report::update_commit_status(
-                    report_url,
-                    artifacts_url,
-                    &outcome,
-                    sha,
-                    &report_name,
-                    scenario_name,
-                )
-                .await;
-            } else {
-                println!("Skipping report submission.");
+            match running.next().await {
+                Some((index, result, execution)) => {
+                    proceed &= self.save_result(&steps[index], result, execution, config);
+                    // running steps may hold the wallet or the shielded context
+                    if running.is_empty() {
+                        self.checkpoint(sdk).await;
+                    }
+                    done[index] = true;
+                }
+                None => break,
             }
         }
 
-        println!("Done.");
+        proceed
+    }
+
+    async fn run_if(
+        &mut self,
+        step: &Step,
+        parameters: &IfParametersDto,
+        sdk: &Sdk,
+        config: &AppConfig,
+    ) -> (StepResult, bool) {
+        match parameters.condition.holds(&self.storage) {
+            Ok(true) => {
+                let proceed = self.run_steps(&parameters.steps, sdk, config).await;
+                (IfStep::result(true), proceed)
+            }
+            Ok(false) => {
+                self.skip_steps(&parameters.steps);
+                (IfStep::result(false), true)
+            }
+            Err(e) => {
+                self.skip_steps(&parameters.steps);
+                (StepResult::invalid_scenario(step.id, e), true)
+            }
+        }
     }
+
+    async fn run_repeat(
+        &mut self,
+        step: &Step,
+        parameters: &RepeatParametersDto,
+        sdk: &Sdk,
+        config: &AppConfig,
+    ) -> (StepResult, bool) {
+        let repeat = match RepeatParameters::from_dto(parameters.clone(), &self.storage) {
+            Ok(Some(repeat)) => repeat,
+            Ok(None) => {
+                self.skip_steps(&parameters.steps);
+                return (StepResult::no_op(), true);
+            }
+            Err(e) => {
+                self.skip_steps(&parameters.steps);
+                return (StepResult::invalid_scenario(step.id, e), true);
+            }
+        };
+        if repeat.iterations == 0 {
+            self.skip_steps(&parameters.steps);
+        }
+
+        // the results of the last iteration are the ones kept in storage
+        let mut iterations = 0;
+        let mut condition_met = repeat.until.is_none();
+        while iterations < repeat.iterations {
+            iterations += 1;
+            let proceed = self.run_steps(&parameters.steps, sdk, config).await;
+            if !proceed {
+                return (RepeatStep::result(iterations, condition_met), false);
+            }
+            if let Some(until) = &repeat.until {
+                match until.holds(&self.storage) {
+                    Ok(true) => {
+                        condition_met = true;
+                        break;
+                    }
+                    Ok(false) => (),
+                    Err(e) => return (StepResult::invalid_scenario(step.id, e), true),
+                }
+            }
+        }
+
+        (RepeatStep::result(iterations, condition_met), true)
+    }
+
+    async fn run_parallel(
+        &mut self,
+        parameters: &ParallelParametersDto,
+        sdk: &Sdk,
+        config: &AppConfig,
+    ) -> (StepResult, bool) {
+        // every step of the block sees the storage as it was before the block,
+        // each with its own fuzzer
+        let shutdown = &self.shutdown;
+        let results = join_all(parameters.steps.iter().map(|step| {
+            self.storage.fuzzer.start(step.id);
+            let storage = self.storage.clone();
+            async move {
+                info!("Running step in parallel");
+                Self::run_step(step, &storage, sdk, config, shutdown).await
+            }
+            .instrument(step_span(step))
+        }))
+        .await;
+
+        let mut proceed = true;
+        for (step, (result, execution)) in parameters.steps.iter().zip(results) {
+            proceed &= self.save_result(step, result, execution, config);
+        }
+
+        (ParallelStep::result(parameters.steps.len()), proceed)
+    }
+
+    /// Runs a step that isn't a control flow step, each attempt is abandoned
+    /// after the timeout of its policy and retried if the policy allows it.
+    /// The step isn't run if it's aborted by a shutdown.
+    async fn run_step(
+        step: &Step,
+        storage: &Storage,
+        sdk: &Sdk,
+        config: &AppConfig,
+        shutdown: &Shutdown,
+    ) -> (StepResult, StepExecution) {
+        let policy = step.policy.clone().unwrap_or_default();
+        let mut execution = StepExecution::start(step.resolved_parameters(storage));
+
+        let mut attempt = 1;
+        loop {
+            let started_at = SystemTime::now();
+            let run = step.run(storage, sdk, config.avoid_check || config.dry_run);
+            let run = async {
+                match policy.timeout() {
+                    Some(timeout) => {
+                        tokio::time::timeout(timeout, run)
+                            .await
+                            .unwrap_or_else(|_| {
+                                StepResult::fail_with_kind(
+                                    FailureKind::Timeout,
+                                    format!("step didn't complete in {}s", timeout.as_secs()),
+                                    StepStorage::default(),
+                                )
+                            })
+                    }
+                    None => run.await,
+                }
+            };
+            let (result, endpoints) = tokio::select! {
+                (result, endpoints) = rpc::track(run) => {
+                    let result = step.config.outputs().enforce(result);
+                    let result = match &step.expect {
+                        Some(expect) => expect
+                            .check(result)
+                            .unwrap_or_else(|e| StepResult::invalid_scenario(step.id, e)),
+                        None => result,
+                    };
+                    (result, endpoints)
+                }
+                _ = shutdown.aborted() => (StepResult::not_run(), vec![]),
+            };
+            execution.add_attempt(started_at, &result, endpoints);
+
+            if shutdown.is_requested() || !policy.should_retry(attempt, &result) {
+                let execution = execution.finish(storage.fuzzer.take_values());
+                return (result, execution);
+            }
+
+            let backoff = policy.backoff(attempt);
+            warn!(
+                attempt,
+                failure = ?result.failure,
+                error = %result.fail_error(),
+                backoff_secs = backoff.as_secs(),
+                "Attempt failed, retrying"
+            );
+            sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// Records the steps that didn't run because the run was interrupted, top
+    /// level ones run again if the run is resumed.
+    fn skip_interrupted_steps(&mut self, steps: &[Step]) {
+        for step in Step::flatten(steps) {
+            if !self.storage.step_results.contains_key(&step.id) {
+                self.storage
+                    .save_step_result(step.id, StepResult::not_run());
+            }
+        }
+    }
+
+    /// Records the steps of a block that didn't run as no-ops, so steps
+    /// referencing them are no-ops too.
+    fn skip_steps(&mut self, steps: &[Step]) {
+        for step in Step::flatten(steps) {
+            self.storage.save_step_result(step.id, StepResult::no_op());
+        }
+    }
+
+    /// Saves the storage, the wallet and the shielded context to the work
+    /// directory, the run can be resumed from there with `--resume`.
+    async fn checkpoint(&self, sdk: &Sdk) {
+        Checkpoint::save_storage(&sdk.base_dir, &self.storage)
+            .expect("Storage should be saved to the work directory.");
+        sdk.namada
+            .wallet
+            .read()
+            .await
+            .save()
+            .expect("Wallet should be saved to the work directory.");
+        sdk.namada
+            .shielded()
+            .await
+            .save()
+            .await
+            .expect("Shielded context should be saved to the work directory.");
+    }
+
+    /// Saves the result of a step, returns `false` if the run has to stop.
+    fn save_result(
+        &mut self,
+        step: &Step,
+        result: StepResult,
+        execution: StepExecution,
+        config: &AppConfig,
+    ) -> bool {
+        let data = &result.data.storage;
+        let _span = info_span!(
+            "result",
+            step_id = step.id,
+            step_type = %step.config,
+            outcome = %result.outcome,
+            tx_hash = data.get("tx-hash").map(String::as_str),
+            height = data.get("height").map(String::as_str),
+            epoch = data.get("epoch").map(String::as_str),
+            elapsed_secs = execution.duration.as_secs(),
+        )
+        .entered();
+        metrics().record_step(
+            &step.config.to_string(),
+            &result.outcome,
+            execution.duration,
+        );
+        self.storage.save_step_execution(step.id, execution);
+
+        if result.is_strict_succesful() {
+            info!("Step executed successfully");
+            self.storage.save_step_result(step.id, result)
+        } else if result.is_noop() {
+            info!("Step was a no-op");
+            self.storage.save_step_result(step.id, result)
+        } else if result.is_fail() {
+            error!(error = %result.fail_error(), "Step failed");
+            self.storage.save_step_result(step.id, result)
+        } else if result.is_invalid_scenario() {
+            error!("Step was not executed, the scenario is invalid");
+            self.storage.save_step_result(step.id, result);
+            if config.on_invalid_scenario == InvalidScenarioPolicy::Abort {
+                return false;
+            }
+        } else if result.is_not_run() {
+            warn!("Step was not run, the run was interrupted");
+            self.storage.save_step_result(step.id, result);
+            return false;
+        } else if result.is_skip() {
+            info!(
+                check_succeeded = result.outcome.get_skip_outcome(),
+                "Check result is ignored"
+            );
+            self.storage.save_step_result(step.id, result)
+        } else {
+            error!("Check failed");
+            self.storage.save_step_result(step.id, result);
+            return false;
+        }
+        true
+    }
+}
+
+fn step_span(step: &Step) -> Span {
+    info_span!("step", step_id = step.id, step_type = %step.config)
 }
@@ -11,6 +11,12 @@ use crate::{
         storage::{StorageCheck, StorageCheckParametersDto},
         Check,
     },
+    expectation::StepExpectation,
+    flow::{
+        IfParametersDto, IfStep, ParallelParametersDto, ParallelStep, RepeatParametersDto,
+        RepeatStep,
+    },
+    policy::{FailureKind, StepPolicy},
     queries::{
         account::{AccountQuery, AccountQueryParametersDto},
         balance::{BalanceQuery, BalanceQueryParametersDto},
@@ -21,7 +27,10 @@ use crate::{
         Query,
     },
     sdk::namada::Sdk,
-    state::state::{StateAddress, StepOutcome, StepStorage, Storage},
+    state::{
+        schema::OutputSchema,
+        state::{StateAddress, StepOutcome, StepStorage, Storage},
+    },
     tasks::{
         become_validator::{BecomeValidatorParametersDto, TxBecomeValidator}, bond::{TxBond, TxBondParametersDto}, bond_batch::{TxBondBatch, TxBondBatchParametersDto}, change_consensus_key::{TxChangeConsensusKey, TxChangeConsensusKeyParametersDto}, change_metadata::{TxChangeMetadata, TxChangeMetadataParametersDto}, claim_rewards::{TxClaimRewards, TxClaimRewardsteParametersDto}, deactivate_validator::{DeactivateValidatorParametersDto, TxDeactivateValidator}, init_account::{TxInitAccount, TxInitAccountParametersDto}, init_default_proposal::{TxInitDefaultProposal, TxInitDefaultProposalParametersDto}, init_pgf_funding_proposal::{
             TxInitPgfFundingProposal, TxInitPgfFundingProposalParametersDto,
@@ -29,12 +38,16 @@ use crate::{
             TxInitPgfStewardProposal, TxInitPgfStewardProposalParametersDto,
         }, reactivate_validator::{ReactivateValidatorParametersDto, TxReactivateValidator}, redelegate::{TxRedelegate, TxRedelegateParametersDto}, redelegate_batch::{TxRedelegateBatch, TxRedelegateBatchParametersDto}, reveal_pk::{RevealPkParametersDto, TxRevealPk}, shielded_sync::{ShieldedSync, ShieldedSyncParametersDto}, transparent_transfer_batch::{
             TxTransparentTransferBatch, TxTransparentTransferBatchParametersDto,
-        }, tx_shielded_transfer::{TxShieldedTransfer, TxShieldedTransferParametersDto}, tx_shielded_transfer_batch::{TxShieldingTransferBatch, TxShieldingTransferBatchParametersDto}, tx_shielding_transfer::{TxShieldingTransfer, TxShieldingTransferParametersDto}, tx_transparent_transfer::{TxTransparentTransfer, TxTransparentTransferParametersDto}, tx_unshielding_transfer::{TxUnshieldingTransfer, TxUnshieldingTransferParametersDto}, unbond::{TxUnbond, TxUnbondParametersDto}, update_account::{TxUpdateAccount, TxUpdateAccountParametersDto}, vote::{TxVoteProposal, TxVoteProposalParametersDto}, wallet_new_key::{WalletNewKey, WalletNewKeyParametersDto}, withdraw::{TxWithdraw, TxWithdrawParametersDto}, Task
+        }, tx_batch::{TxBatch, TxBatchParametersDto}, tx_shielded_transfer::{TxShieldedTransfer, TxShieldedTransferParametersDto}, tx_shielded_transfer_batch::{TxShieldingTransferBatch, TxShieldingTransferBatchParametersDto}, tx_shielding_transfer::{TxShieldingTransfer, TxShieldingTransferParametersDto}, tx_transparent_transfer::{TxTransparentTransfer, TxTransparentTransferParametersDto}, tx_unshielding_transfer::{TxUnshieldingTransfer, TxUnshieldingTransferParametersDto}, unbond::{TxUnbond, TxUnbondParametersDto}, update_account::{TxUpdateAccount, TxUpdateAccountParametersDto}, vote::{TxVoteProposal, TxVoteProposalParametersDto}, wallet_new_key::{WalletNewKey, WalletNewKeyParametersDto}, withdraw::{TxWithdraw, TxWithdrawParametersDto}, Task
+    },
+    utils::{
+        settings::TxSettingsDto,
+        value::{ParameterError, Resolver},
     },
-    utils::settings::TxSettingsDto,
     waits::{
         epoch::{EpochWait, EpochWaitParametersDto},
         height::{HeightWait, HeightWaitParametersDto},
+        tx::{TxWait, TxWaitParametersDto},
         Wait,
     },
 };
@@ -139,6 +152,8 @@ pub enum StepType {
     WaitUntillEpoch { parameters: EpochWaitParametersDto },
     #[serde(rename = "wait-height")]
     WaitUntillHeight { parameters: HeightWaitParametersDto },
+    #[serde(rename = "wait-tx")]
+    WaitTx { parameters: TxWaitParametersDto },
     #[serde(rename = "query-balance")]
     QueryAccountTokenBalance {
         parameters: BalanceQueryParametersDto,
@@ -218,6 +233,17 @@ pub enum StepType {
         parameters: TxShieldingTransferBatchParametersDto,
         settings: Option<TxSettingsDto>,
     },
+    #[serde(rename = "tx-batch")]
+    TxBatch {
+        parameters: TxBatchParametersDto,
+        settings: Option<TxSettingsDto>,
+    },
+    #[serde(rename = "if")]
+    If { parameters: IfParametersDto },
+    #[serde(rename = "repeat")]
+    Repeat { parameters: RepeatParametersDto },
+    #[serde(rename = "parallel")]
+    Parallel { parameters: ParallelParametersDto },
 }
 
 impl Display for StepType {
@@ -239,6 +265,7 @@ impl Display for StepType {
             StepType::CheckStepOutput { .. } => write!(f, "check-tx"),
             StepType::WaitUntillEpoch { .. } => write!(f, "wait-epoch"),
             StepType::WaitUntillHeight { .. } => write!(f, "wait-height"),
+            StepType::WaitTx { .. } => write!(f, "wait-tx"),
             StepType::QueryAccountTokenBalance { .. } => write!(f, "query-balance"),
             StepType::QueryAccount { .. } => write!(f, "query-account"),
             StepType::QueryBondedStake { .. } => write!(f, "query-bonded-stake"),
@@ -263,22 +290,100 @@ impl Display for StepType {
             StepType::BondBatch { .. } => write!(f, "bond-batch"),
             StepType::RedelegateBatch { .. } => write!(f, "redelegate-batch"),
             StepType::ShieldingBatch { .. } => write!(f, "shielding-batch"),
+            StepType::TxBatch { .. } => write!(f, "tx-batch"),
+            StepType::If { .. } => write!(f, "if"),
+            StepType::Repeat { .. } => write!(f, "repeat"),
+            StepType::Parallel { .. } => write!(f, "parallel"),
+        }
+    }
+}
+
+impl StepType {
+    pub fn outputs(&self) -> OutputSchema {
+        match self {
+            StepType::ShieldedSync => ShieldedSync::outputs(),
+            StepType::WalletNewKey { .. } => WalletNewKey::outputs(),
+            StepType::InitAccount { .. } => TxInitAccount::outputs(),
+            StepType::UpdateAccount { .. } => TxUpdateAccount::outputs(),
+            StepType::TransparentTransfer { .. } => TxTransparentTransfer::outputs(),
+            StepType::ShieldingTransfer { .. } => TxShieldingTransfer::outputs(),
+            StepType::ShieldedTransfer { .. } => TxShieldedTransfer::outputs(),
+            StepType::UnshieldingTransfer { .. } => TxUnshieldingTransfer::outputs(),
+            StepType::RevealPk { .. } => TxRevealPk::outputs(),
+            StepType::Bond { .. } => TxBond::outputs(),
+            StepType::Unbond { .. } => TxUnbond::outputs(),
+            StepType::Withdraw { .. } => TxWithdraw::outputs(),
+            StepType::BecomeValidator { .. } => TxBecomeValidator::outputs(),
+            StepType::ChangeMetadata { .. } => TxChangeMetadata::outputs(),
+            StepType::ChangeConsensusKey { .. } => TxChangeConsensusKey::outputs(),
+            StepType::DeactivateValidator { .. } => TxDeactivateValidator::outputs(),
+            StepType::ReactivateValidator { .. } => TxReactivateValidator::outputs(),
+            StepType::ClaimRewards { .. } => TxClaimRewards::outputs(),
+            StepType::CheckBalance { .. } => BalanceCheck::outputs(),
+            StepType::CheckStepOutput { .. } => StepCheck::outputs(),
+            StepType::WaitUntillEpoch { .. } => EpochWait::outputs(),
+            StepType::WaitUntillHeight { .. } => HeightWait::outputs(),
+            StepType::WaitTx { .. } => TxWait::outputs(),
+            StepType::QueryAccountTokenBalance { .. } => BalanceQuery::outputs(),
+            StepType::QueryAccount { .. } => AccountQuery::outputs(),
+            StepType::QueryBondedStake { .. } => BondedStakeQuery::outputs(),
+            StepType::Redelegate { .. } => TxRedelegate::outputs(),
+            StepType::CheckBonds { .. } => BondsCheck::outputs(),
+            StepType::CheckRevealPk { .. } => RevealPkCheck::outputs(),
+            StepType::InitProposal { .. } => TxInitDefaultProposal::outputs(),
+            StepType::InitStewardProposal { .. } => TxInitPgfStewardProposal::outputs(),
+            StepType::InitFundingProposal { .. } => TxInitPgfFundingProposal::outputs(),
+            StepType::QueryProposal { .. } => ProposalQuery::outputs(),
+            StepType::VoteProposal { .. } => TxVoteProposal::outputs(),
+            StepType::CheckStorage { .. } => StorageCheck::outputs(),
+            StepType::QueryValidators { .. } => ValidatorsQuery::outputs(),
+            StepType::QueryProposals { .. } => ProposalsQuery::outputs(),
+            StepType::TransparentTransferBatch { .. } => TxTransparentTransferBatch::outputs(),
+            StepType::BondBatch { .. } => TxBondBatch::outputs(),
+            StepType::RedelegateBatch { .. } => TxRedelegateBatch::outputs(),
+            StepType::ShieldingBatch { .. } => TxShieldingTransferBatch::outputs(),
+            StepType::TxBatch { parameters, .. } => TxBatch::batch_outputs(parameters),
+            StepType::If { .. } => IfStep::outputs(),
+            StepType::Repeat { .. } => RepeatStep::outputs(),
+            StepType::Parallel { .. } => ParallelStep::outputs(),
+        }
+    }
+
+    /// The block of a control flow step, empty for any other step.
+    pub fn nested_steps(&self) -> &[Step] {
+        match self {
+            StepType::If { parameters } => &parameters.steps,
+            StepType::Repeat { parameters } => &parameters.steps,
+            StepType::Parallel { parameters } => &parameters.steps,
+            _ => &[],
         }
     }
+
+    pub fn is_control_flow(&self) -> bool {
+        matches!(
+            self,
+            StepType::If { .. } | StepType::Repeat { .. } | StepType::Parallel { .. }
+        )
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Step {
     pub id: u64,
     pub config: StepType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<StepPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<StepExpectation>,
 }
 
 impl Step {
     pub async fn run(&self, storage: &Storage, sdk: &Sdk, avoid_check: bool) -> StepResult {
-        match self.config.to_owned() {
+        let expect = self.expect.is_some();
+        let result = match self.config.to_owned() {
             StepType::ShieldedSync => {
                 ShieldedSync::default()
-                    .run(sdk, ShieldedSyncParametersDto, Default::default(), storage)
+                    .run(sdk, ShieldedSyncParametersDto, Default::default(), storage, expect)
                     .await
             }
             StepType::WalletNewKey {
@@ -286,7 +391,7 @@ impl Step {
                 settings,
             } => {
                 WalletNewKey::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::UpdateAccount {
@@ -294,7 +399,7 @@ impl Step {
                 settings,
             } => {
                 TxUpdateAccount::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::InitAccount {
@@ -302,7 +407,7 @@ impl Step {
                 settings,
             } => {
                 TxInitAccount::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::TransparentTransfer {
@@ -310,7 +415,7 @@ impl Step {
                 settings,
             } => {
                 TxTransparentTransfer::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::ShieldingTransfer {
@@ -318,7 +423,7 @@ impl Step {
                 settings,
             } => {
                 TxShieldingTransfer::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::ShieldedTransfer {
@@ -326,7 +431,7 @@ impl Step {
                 settings,
             } => {
                 TxShieldedTransfer::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::UnshieldingTransfer {
@@ -334,31 +439,31 @@ impl Step {
                 settings,
             } => {
                 TxUnshieldingTransfer::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::RevealPk {
                 parameters: dto,
                 settings,
-            } => TxRevealPk::default().run(sdk, dto, settings, storage).await,
+            } => TxRevealPk::default().run(sdk, dto, settings, storage, expect).await,
             StepType::Bond {
                 parameters: dto,
                 settings,
-            } => TxBond::default().run(sdk, dto, settings, storage).await,
+            } => TxBond::default().run(sdk, dto, settings, storage, expect).await,
             StepType::Unbond {
                 parameters: dto,
                 settings,
-            } => TxUnbond::default().run(sdk, dto, settings, storage).await,
+            } => TxUnbond::default().run(sdk, dto, settings, storage, expect).await,
             StepType::Withdraw {
                 parameters: dto,
                 settings,
-            } => TxWithdraw::default().run(sdk, dto, settings, storage).await,
+            } => TxWithdraw::default().run(sdk, dto, settings, storage, expect).await,
             StepType::BecomeValidator {
                 parameters: dto,
                 settings,
             } => {
                 TxBecomeValidator::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::ChangeMetadata {
@@ -366,7 +471,7 @@ impl Step {
                 settings,
             } => {
                 TxChangeMetadata::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::ChangeConsensusKey {
@@ -374,7 +479,7 @@ impl Step {
                 settings,
             } => {
                 TxChangeConsensusKey::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::DeactivateValidator {
@@ -382,7 +487,7 @@ impl Step {
                 settings,
             } => {
                 TxDeactivateValidator::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::ReactivateValidator {
@@ -390,7 +495,7 @@ impl Step {
                 settings,
             } => {
                 TxReactivateValidator::default()
-                    .run(sdk, dto, settings, storage)
+                    .run(sdk, dto, settings, storage, expect)
                     .await
             }
             StepType::CheckBalance { parameters: dto } => {
@@ -409,6 +514,7 @@ impl Step {
             StepType::WaitUntillHeight { parameters: dto } => {
                 HeightWait::default().run(sdk, dto, storage).await
             }
+            StepType::WaitTx { parameters: dto } => TxWait::default().run(sdk, dto, storage).await,
             StepType::QueryAccountTokenBalance { parameters: dto } => {
                 BalanceQuery::default().run(sdk, dto, storage).await
             }
@@ -423,7 +529,7 @@ impl Step {
                 settings,
             } => {
                 TxClaimRewards::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::Redelegate {
@@ -431,7 +537,7 @@ impl Step {
                 settings,
             } => {
                 TxRedelegate::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::CheckBonds { parameters } => {
@@ -444,7 +550,7 @@ impl Step {
                 settings,
             } => {
                 TxInitDefaultProposal::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::InitStewardProposal {
@@ -452,7 +558,7 @@ impl Step {
                 settings,
             } => {
                 TxInitPgfStewardProposal::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::InitFundingProposal {
@@ -460,7 +566,7 @@ impl Step {
                 settings,
             } => {
                 TxInitPgfFundingProposal::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::QueryProposal { parameters } => {
@@ -476,7 +582,7 @@ impl Step {
                 settings,
             } => {
                 TxVoteProposal::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::QueryValidators { parameters } => {
@@ -499,7 +605,7 @@ impl Step {
                 settings,
             } => {
                 TxTransparentTransferBatch::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::BondBatch {
@@ -507,7 +613,7 @@ impl Step {
                 settings,
             } => {
                 TxBondBatch::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::RedelegateBatch {
@@ -515,7 +621,7 @@ impl Step {
                 settings,
             } => {
                 TxRedelegateBatch::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
             StepType::ShieldingBatch {
@@ -523,10 +629,52 @@ impl Step {
                 settings,
             } => {
                 TxShieldingTransferBatch::default()
-                    .run(sdk, parameters, settings, storage)
+                    .run(sdk, parameters, settings, storage, expect)
                     .await
             }
+            StepType::TxBatch {
+                parameters,
+                settings,
+            } => {
+                TxBatch::default()
+                    .run(sdk, parameters, settings, storage, expect)
+                    .await
+            }
+            // control flow steps are run by the `Runner`, they only end up
+            // here when nested in a parallel block
+            StepType::If { .. } | StepType::Repeat { .. } | StepType::Parallel { .. } => Err(
+                ParameterError::new("steps", "control flow steps can't run in parallel"),
+            ),
+        };
+
+        result.unwrap_or_else(|e| StepResult::invalid_scenario(self.id, e))
+    }
+
+    /// `steps` and the steps nested in them, each control flow step followed by
+    /// its block.
+    pub fn flatten(steps: &[Step]) -> Vec<&Step> {
+        steps
+            .iter()
+            .flat_map(|step| std::iter::once(step).chain(Step::flatten(step.config.nested_steps())))
+            .collect()
+    }
+
+    /// The parameters and settings of the step as they resolve against
+    /// `storage`, without the blocks of control flow steps.
+    pub fn resolved_parameters(&self, storage: &Storage) -> serde_json::Value {
+        let mut config =
+            serde_json::to_value(&self.config).expect("Step should be serializable.");
+        if let Some(config) = config.as_object_mut() {
+            config.remove("type");
         }
+        if let Some(parameters) = config
+            .get_mut("parameters")
+            .and_then(|parameters| parameters.as_object_mut())
+        {
+            parameters.remove("steps");
+        }
+
+        Resolver::new(storage).parameters(config)
     }
 }
 
@@ -546,6 +694,8 @@ pub struct StepResult {
     pub outcome: StepOutcome,
     pub data: StepStorage,
     pub accounts: Vec<StateAddress>,
+    /// set for the failures a step policy can retry
+    pub failure: Option<FailureKind>,
 }
 
 impl Default for StepResult {
@@ -554,6 +704,7 @@ impl Default for StepResult {
             outcome: StepOutcome::success(),
             data: Default::default(),
             accounts: Default::default(),
+            failure: None,
         }
     }
 }
@@ -579,6 +730,14 @@ impl StepResult {
         self.outcome.is_skip()
     }
 
+    pub fn is_invalid_scenario(&self) -> bool {
+        self.outcome.is_invalid_scenario()
+    }
+
+    pub fn is_not_run(&self) -> bool {
+        self.outcome.is_not_run()
+    }
+
     pub fn fail_error(&self) -> String {
         match &self.outcome {
             StepOutcome::Success => panic!(),
@@ -586,6 +745,8 @@ impl StepResult {
             StepOutcome::CheckSkip(_) => panic!(),
             StepOutcome::CheckFail(_, _) => panic!(),
             StepOutcome::NoOp => panic!(),
+            StepOutcome::InvalidScenario { .. } => panic!(),
+            StepOutcome::NotRun => panic!(),
         }
     }
 
@@ -594,6 +755,7 @@ impl StepResult {
             outcome: StepOutcome::success(),
             data,
             accounts: Vec::new(),
+            failure: None,
         }
     }
 
@@ -602,6 +764,7 @@ impl StepResult {
             outcome: StepOutcome::skip_check(outcome),
             data: StepStorage::default(),
             accounts: Vec::new(),
+            failure: None,
         }
     }
 
@@ -610,6 +773,16 @@ impl StepResult {
             outcome: StepOutcome::no_op(),
             data: StepStorage::default(),
             accounts: Vec::new(),
+            failure: None,
+        }
+    }
+
+    pub fn not_run() -> Self {
+        Self {
+            outcome: StepOutcome::not_run(),
+            data: StepStorage::default(),
+            accounts: Vec::new(),
+            failure: None,
         }
     }
 
@@ -618,6 +791,7 @@ impl StepResult {
             outcome: StepOutcome::success(),
             data: StepStorage::default(),
             accounts: Vec::new(),
+            failure: None,
         }
     }
 
@@ -626,6 +800,7 @@ impl StepResult {
             outcome: StepOutcome::success(),
             data,
             accounts,
+            failure: None,
         }
     }
 
@@ -634,6 +809,29 @@ impl StepResult {
             outcome: StepOutcome::fail(error),
             data: StepStorage::default(),
             accounts: Vec::new(),
+            failure: None,
+        }
+    }
+
+    pub fn fail_with_data(error: String, data: StepStorage) -> Self {
+        Self {
+            outcome: StepOutcome::fail(error),
+            data,
+            accounts: Vec::new(),
+            failure: None,
+        }
+    }
+
+    pub fn fail_rpc(error: String) -> Self {
+        Self::fail_with_kind(FailureKind::RpcError, error, StepStorage::default())
+    }
+
+    pub fn fail_with_kind(failure: FailureKind, error: String, data: StepStorage) -> Self {
+        Self {
+            outcome: StepOutcome::fail(error),
+            data,
+            accounts: Vec::new(),
+            failure: Some(failure),
         }
     }
 
@@ -642,6 +840,16 @@ impl StepResult {
             outcome: StepOutcome::check_fail(actual, expected),
             data: StepStorage::default(),
             accounts: Vec::new(),
+            failure: None,
+        }
+    }
+
+    pub fn invalid_scenario(step_id: u64, error: ParameterError) -> Self {
+        Self {
+            outcome: StepOutcome::invalid_scenario(step_id, error),
+            data: StepStorage::default(),
+            accounts: Vec::new(),
+            failure: None,
         }
     }
 }
@@ -651,3 +859,11 @@ pub struct Scenario {
     pub settings: ScenarioSettings,
     pub steps: Vec<Step>,
 }
+
+impl Scenario {
+    pub fn step(&self, id: u64) -> Option<&Step> {
+        Step::flatten(&self.steps)
+            .into_iter()
+            .find(|step| step.id == id)
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde_json::Value as Json;
+
+use crate::{
+    scenario::{Step, StepType},
+    utils::value::Value,
+    validator::collect_values,
+};
+
+// parameters and settings holding the alias or the address of an account,
+// tokens are left out since they don't sign anything
+const ACCOUNT_PARAMETERS: [&str; 17] = [
+    "alias",
+    "address",
+    "source",
+    "sources",
+    "target",
+    "targets",
+    "voter",
+    "signer",
+    "delegator",
+    "delegate",
+    "validator",
+    "src_validator",
+    "src_validators",
+    "dest_validator",
+    "dest_validators",
+    "gas-payer",
+    "signers",
+];
+
+// parameters holding the account signing the tx of a step, which pays its fees
+// unless its settings say otherwise
+const SIGNER_PARAMETERS: [&str; 4] = ["source", "sources", "voter", "signer"];
+
+// pays the fees of `reveal-pk` steps and of the txs without a signer
+const DEFAULT_GAS_PAYER: &str = "faucet";
+
+// fields written by the steps creating an account which identify that account
+const ACCOUNT_FIELDS: [&str; 6] = [
+    "alias",
+    "address",
+    "public-key",
+    "private-key",
+    "payment-address",
+    "spending-key",
+];
+
+/// Dependencies between the top level steps of a scenario.
+///
+/// A step depends on:
+/// - the steps it references, through a `Value::Ref`, a fuzzed parameter or a
+///   condition
+/// - the last step before it using one of its accounts, so that steps
+///   signing with the same account never run at the same time
+/// - every step before it if it's a barrier (control flow, wait and
+///   shielded-sync steps), in which case every step after it depends on it
+#[derive(Clone, Debug, Default)]
+pub struct StepGraph {
+    dependencies: Vec<BTreeSet<usize>>,
+}
+
+impl StepGraph {
+    pub fn new(steps: &[Step]) -> Self {
+        // nested steps are run along with the step containing them
+        let positions = steps
+            .iter()
+            .enumerate()
+            .flat_map(|(index, step)| {
+                Step::flatten(std::slice::from_ref(step))
+                    .into_iter()
+                    .map(move |step| (step.id, index))
+            })
+            .collect::<HashMap<u64, usize>>();
+        let aliases = account_aliases(steps);
+
+        let mut dependencies = vec![];
+        let mut last_users = HashMap::new();
+        let mut last_barrier = None;
+        for (index, step) in steps.iter().enumerate() {
+            let (references, accounts) = step_resources(step, &aliases);
+
+            let mut step_dependencies = BTreeSet::new();
+            if is_barrier(&step.config) {
+                step_dependencies.extend(0..index);
+                last_barrier = Some(index);
+            } else if let Some(barrier) = last_barrier {
+                step_dependencies.insert(barrier);
+            }
+            step_dependencies.extend(
+                references
+                    .iter()
+                    .filter_map(|target| positions.get(target))
+                    .filter(|position| **position < index),
+            );
+            for account in accounts {
+                if let Some(last_user) = last_users.insert(account, index) {
+                    step_dependencies.insert(last_user);
+                }
+            }
+
+            dependencies.push(step_dependencies);
+        }
+
+        Self { dependencies }
+    }
+
+    pub fn dependencies(&self, index: usize) -> &BTreeSet<usize> {
+        &self.dependencies[index]
+    }
+}
+
+fn is_barrier(step_type: &StepType) -> bool {
+    step_type.is_control_flow()
+        || matches!(
+            step_type,
+            StepType::ShieldedSync
+                | StepType::WaitUntillEpoch { .. }
+                | StepType::WaitUntillHeight { .. }
+        )
+}
+
+/// The alias of the account created by each `wallet-new-key` and
+/// `tx-init-account` step, also when it's given as a reference.
+fn account_aliases(steps: &[Step]) -> HashMap<u64, String> {
+    let mut aliases = HashMap::new();
+    for step in Step::flatten(steps) {
+        let alias = match &step.config {
+            StepType::WalletNewKey { parameters, .. } => &parameters.alias,
+            StepType::InitAccount { parameters, .. } => &parameters.alias,
+            _ => continue,
+        };
+        let alias = match alias {
+            Value::Value { value } => value.clone(),
+            Value::Ref { value, field } => account_name(&aliases, *value, field),
+            _ => continue,
+        };
+        aliases.insert(step.id, alias);
+    }
+    aliases
+}
+
+/// The name of the account a referenced field resolves to: the alias of the
+/// account created by the referenced step, or the reference itself.
+fn account_name(aliases: &HashMap<u64, String>, step_id: u64, field: &str) -> String {
+    match aliases.get(&step_id) {
+        Some(alias) if ACCOUNT_FIELDS.contains(&field) => alias.clone(),
+        _ => format!("{}.{}", step_id, field),
+    }
+}
+
+/// The account a `Value` of an account parameter resolves to, if it can be
+/// told before running the scenario.
+fn account(value: &Value, aliases: &HashMap<u64, String>) -> Option<String> {
+    match value {
+        Value::Ref { value, field } => Some(account_name(aliases, *value, field)),
+        Value::Value { value } => Some(value.clone()),
+        Value::Fuzz { .. } | Value::Expr { .. } => None,
+    }
+}
+
+/// The account paying the fees of a tx step: its `gas-payer`, its first
+/// signer or the account signing its tx, see `Task::add_settings`.
+fn gas_payer(step: &Step, config: &Json, aliases: &HashMap<u64, String>) -> Option<String> {
+    if let StepType::RevealPk { .. } = step.config {
+        return Some(DEFAULT_GAS_PAYER.to_string());
+    }
+    let settings = config.get("settings");
+    let parameters = config.get("parameters");
+    // unset settings are serialized as `null`
+    let payer = settings
+        .and_then(|settings| settings.get("gas-payer"))
+        .filter(|payer| !payer.is_null())
+        .or_else(|| settings.and_then(|settings| settings.get("signers")?.get(0)))
+        .or_else(|| {
+            SIGNER_PARAMETERS.iter().find_map(|name| {
+                let value = parameters?.get(name)?;
+                if value.is_array() {
+                    value.get(0)
+                } else {
+                    Some(value)
+                }
+            })
+        });
+    match payer {
+        Some(payer) => account(&serde_json::from_value(payer.clone()).ok()?, aliases),
+        None => Some(DEFAULT_GAS_PAYER.to_string()),
+    }
+}
+
+/// The steps referenced by `step` and the accounts it uses, including the
+/// one paying the fees of its tx.
+fn step_resources(
+    step: &Step,
+    aliases: &HashMap<u64, String>,
+) -> (BTreeSet<u64>, BTreeSet<String>) {
+    let config = serde_json::to_value(&step.config).expect("Step should be serializable.");
+
+    let mut references = BTreeSet::new();
+    let mut accounts = BTreeSet::new();
+
+    let mut values = vec![];
+    if let Some(parameters) = config.get("parameters") {
+        collect_values(parameters, String::new(), &mut values);
+        // check-storage points at a step id outside of a `Value`
+        if let StepType::CheckStorage { .. } = step.config {
+            references.extend(parameters.get("step").and_then(Json::as_u64));
+        }
+    }
+    if let Some(settings) = config.get("settings") {
+        collect_values(settings, "settings".to_string(), &mut values);
+    }
+
+    while let Some((parameter, value)) = values.pop() {
+        let name = parameter.rsplit('.').next().unwrap_or_default();
+        let name = name.split('[').next().unwrap_or_default();
+        if ACCOUNT_PARAMETERS.contains(&name) {
+            accounts.extend(account(&value, aliases));
+        }
+        match value {
+            Value::Ref { value, .. } => {
+                references.insert(value);
+            }
+            Value::Value { value } => {
+                // check-step points at a step id through a plain value
+                if let (StepType::CheckStepOutput { .. }, "id") = (&step.config, name) {
+                    references.extend(value.parse::<u64>());
+                }
+            }
+            Value::Fuzz { value } => references.extend(value),
+            Value::Expr { args, .. } => {
+                values.extend(args.into_iter().map(|arg| (parameter.clone(), arg)))
+            }
+        }
+    }
+
+    // only the steps recording the result of a tx send one
+    if step.config.outputs().field("tx-hash").is_some() {
+        accounts.extend(gas_payer(step, &config, aliases));
+    }
+
+    (references, accounts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_account_conflicts() {
+        let steps: Vec<Step> = serde_json::from_value(serde_json::json!([
+            { "id": 0, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "alice" } } } },
+            { "id": 1, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "bob" } } } },
+            { "id": 2, "config": { "type": "tx-bond", "parameters": {
+                "source": { "type": "ref", "value": 0, "field": "address" },
+                "validator": { "type": "value", "value": "validator-0" },
+                "amount": { "type": "value", "value": "10" }
+            } } },
+            { "id": 3, "config": { "type": "tx-transparent-transfer", "parameters": {
+                "source": { "type": "value", "value": "alice" },
+                "target": { "type": "ref", "value": 1, "field": "address" },
+                "amount": { "type": "value", "value": "1" },
+                "token": { "type": "value", "value": "nam" }
+            } } },
+            { "id": 4, "config": { "type": "tx-transparent-transfer", "parameters": {
+                "source": { "type": "ref", "value": 1, "field": "alias" },
+                "target": { "type": "value", "value": "carol" },
+                "amount": { "type": "value", "value": "1" },
+                "token": { "type": "value", "value": "nam" }
+            } } },
+            { "id": 5, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "value", "value": "carol" } } } },
+            { "id": 6, "config": { "type": "tx-transparent-transfer", "parameters": {
+                "source": { "type": "value", "value": "faucet" },
+                "target": { "type": "value", "value": "dave" },
+                "amount": { "type": "value", "value": "1.5" },
+                "token": { "type": "value", "value": "nam" }
+            } } },
+            { "id": 7, "config": { "type": "tx-transparent-transfer", "parameters": {
+                "source": { "type": "value", "value": "erin" },
+                "target": { "type": "value", "value": "frank" },
+                "amount": { "type": "value", "value": "1.5" },
+                "token": { "type": "value", "value": "nam" }
+            } } },
+            { "id": 8, "config": { "type": "tx-transparent-transfer", "parameters": {
+                "source": { "type": "value", "value": "gina" },
+                "target": { "type": "value", "value": "hank" },
+                "amount": { "type": "value", "value": "1" },
+                "token": { "type": "value", "value": "nam" }
+            }, "settings": { "gas-payer": { "type": "value", "value": "faucet" } } } }
+        ]))
+        .unwrap();
+        let graph = StepGraph::new(&steps);
+
+        assert_eq!(graph.dependencies(2), &BTreeSet::from([0]));
+        assert_eq!(graph.dependencies(3), &BTreeSet::from([1, 2]));
+        assert_eq!(graph.dependencies(4), &BTreeSet::from([1, 3]));
+        // reveal-pk pays its fees with the faucet
+        assert_eq!(graph.dependencies(5), &BTreeSet::from([4]));
+        assert_eq!(graph.dependencies(6), &BTreeSet::from([5]));
+        // amounts and tokens aren't accounts
+        assert_eq!(graph.dependencies(7), &BTreeSet::new());
+        assert_eq!(graph.dependencies(8), &BTreeSet::from([6]));
+    }
+}
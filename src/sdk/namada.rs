@@ -1,32 +1,42 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    fs,
+    path::PathBuf,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
 
 use namada_sdk::{
     address::{Address, ImplicitAddress},
-    args::TxBuilder,
+    args::{self, TxBuilder},
     chain::ChainId,
+    error::Error,
     io::NullIo,
     key::common::{PublicKey, SecretKey},
     masp::fs::FsShieldedUtils,
-    rpc,
+    rpc::{self, is_public_key_revealed},
+    signing::default_sign,
+    tx::{ProcessTxResponse, Tx},
     wallet::{fs::FsWalletUtils, Wallet},
     Namada, NamadaImpl, ShieldedWallet,
 };
-use tendermint_rpc::HttpClient;
 
-use crate::config::AppConfig;
+use crate::{config::AppConfig, metrics::metrics, sdk::rpc::RpcPool};
 
 pub struct Sdk {
     pub base_dir: PathBuf,
     pub chain_id: String,
     pub rpc: String,
-    pub namada: NamadaImpl<HttpClient, FsWalletUtils, FsShieldedUtils, NullIo>,
+    pub dry_run: bool,
+    pub dump_tx_dir: Option<PathBuf>,
+    pub namada: NamadaImpl<RpcPool, FsWalletUtils, FsShieldedUtils, NullIo>,
 }
 
 impl Sdk {
     pub async fn new(
         config: &AppConfig,
         base_dir: &PathBuf,
-        http_client: HttpClient,
+        rpc_pool: RpcPool,
         wallet: Wallet<FsWalletUtils>,
         shielded_ctx: ShieldedWallet<FsShieldedUtils>,
         io: NullIo,
@@ -36,7 +46,7 @@ impl Sdk {
         let public_key = sk.to_public();
         let address = Address::Implicit(ImplicitAddress::from(&public_key));
 
-        let namada = NamadaImpl::new(http_client, wallet, shielded_ctx, io)
+        let namada = NamadaImpl::new(rpc_pool, wallet, shielded_ctx, io)
             .await
             .expect("unable to construct Namada object")
             .chain_id(ChainId::from_str(&config.chain_id).unwrap());
@@ -57,11 +67,96 @@ impl Sdk {
         Self {
             base_dir: base_dir.to_owned(),
             chain_id: config.chain_id.to_owned(),
-            rpc: config.rpc.to_owned(),
+            rpc: config.rpc.join(","),
+            dry_run: config.dry_run,
+            dump_tx_dir: config.dump_tx_dir.to_owned(),
             namada,
         }
     }
 
+    /// Submits a signed tx. In dry-run mode the tx is only simulated by the
+    /// node, including the fee payment, and written to `dump_tx_dir` if set.
+    pub async fn submit(&self, tx: Tx, args: &args::Tx) -> Result<ProcessTxResponse, Error> {
+        if !self.dry_run {
+            let broadcast_at = Instant::now();
+            let response = self.namada.submit(tx, args).await;
+            if let Ok(ProcessTxResponse::Applied(_)) = response {
+                metrics().record_tx_inclusion(broadcast_at.elapsed());
+            }
+            return response;
+        }
+
+        if let Some(dump_tx_dir) = &self.dump_tx_dir {
+            let tx_path = dump_tx_dir.join(format!("{}.tx", tx.header_hash()));
+            fs::create_dir_all(dump_tx_dir)
+                .and_then(|_| fs::write(&tx_path, tx.to_bytes()))
+                .expect("Tx should be written to the dump folder.");
+        }
+
+        let args = args::Tx {
+            dry_run_wrapper: true,
+            ..args.clone()
+        };
+        self.namada.submit(tx, &args).await
+    }
+
+    /// Reveals the public key of the faucet unless it already is, retrying
+    /// until the node can be queried.
+    pub async fn reveal_faucet_pk(&self) {
+        let faucet_address = self
+            .namada
+            .wallet
+            .read()
+            .await
+            .find_address("faucet")
+            .unwrap()
+            .into_owned();
+
+        loop {
+            let is_faucet_pk_revealed =
+                is_public_key_revealed(&self.namada.clone_client(), &faucet_address).await;
+
+            if let Ok(is_revealed) = is_faucet_pk_revealed {
+                if !is_revealed {
+                    let faucet_pk = self
+                        .namada
+                        .wallet
+                        .read()
+                        .await
+                        .find_public_key("faucet")
+                        .unwrap();
+
+                    let reveal_pk_tx_builder = self
+                        .namada
+                        .new_reveal_pk(faucet_pk.clone())
+                        .signing_keys(vec![faucet_pk.clone()])
+                        .wrapper_fee_payer(faucet_pk); // workaround due to scenario generator limitation
+
+                    let (mut reveal_tx, signing_data) =
+                        reveal_pk_tx_builder.build(&self.namada).await.unwrap();
+
+                    self.namada
+                        .sign(
+                            &mut reveal_tx,
+                            &reveal_pk_tx_builder.tx,
+                            signing_data,
+                            default_sign,
+                            (),
+                        )
+                        .await
+                        .unwrap();
+
+                    self.submit(reveal_tx.clone(), &reveal_pk_tx_builder.tx)
+                        .await
+                        .unwrap();
+                }
+                break;
+            } else {
+                thread::sleep(Duration::from_secs(2));
+            }
+        }
+    }
+
     pub async fn find_secret_key(&self, alias: impl AsRef<str>) -> SecretKey {
         let mut wallet = self.namada.wallet.write().await;
         wallet.find_secret_key(alias, None).unwrap()
@@ -0,0 +1,208 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    future::Future,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::{de::DeserializeOwned, Serialize};
+use tendermint_rpc::{error::ErrorDetail, Client, Error, HttpClient, SimpleRequest, Url};
+use tracing::warn;
+
+use crate::{config::RpcSelection, metrics::metrics};
+
+/// How long the health of the endpoints is trusted before probing them again
+const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+/// Blocks an endpoint can lag behind the highest one and still be healthy
+const MAX_HEIGHT_LAG: u64 = 5;
+
+tokio::task_local! {
+    // endpoints that served the requests of the running step, see `track`
+    static SERVED_BY: RefCell<BTreeSet<String>>;
+}
+
+#[derive(Clone, Debug)]
+struct Endpoint {
+    url: String,
+    client: HttpClient,
+}
+
+#[derive(Debug, Default)]
+struct PoolHealth {
+    healthy: Vec<bool>,
+    probed_at: Option<Instant>,
+}
+
+/// Tendermint RPC client over several nodes. Endpoints that are down,
+/// catching up or lagging behind are skipped, and a request failing because
+/// its endpoint can't be reached is sent to the next one.
+#[derive(Clone, Debug)]
+pub struct RpcPool {
+    endpoints: Arc<Vec<Endpoint>>,
+    selection: RpcSelection,
+    health: Arc<Mutex<PoolHealth>>,
+    // the current endpoint when sticky, the next one when round robin
+    next: Arc<AtomicUsize>,
+}
+
+impl RpcPool {
+    pub fn new(urls: &[String], selection: RpcSelection) -> Self {
+        let endpoints = urls
+            .iter()
+            .map(|url| Endpoint {
+                url: url.clone(),
+                client: HttpClient::new(Url::from_str(url).expect("invalid RPC address")).unwrap(),
+            })
+            .collect::<Vec<Endpoint>>();
+        assert!(
+            !endpoints.is_empty(),
+            "at least one RPC address is required"
+        );
+
+        Self {
+            health: Arc::new(Mutex::new(PoolHealth {
+                healthy: vec![true; endpoints.len()],
+                probed_at: None,
+            })),
+            endpoints: Arc::new(endpoints),
+            selection,
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Probes the status of every endpoint if it wasn't done recently.
+    async fn probe(&self) {
+        {
+            let mut health = self.health.lock().unwrap();
+            if health
+                .probed_at
+                .is_some_and(|probed_at| probed_at.elapsed() < PROBE_INTERVAL)
+            {
+                return;
+            }
+            health.probed_at = Some(Instant::now());
+        }
+
+        let statuses = join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.client.status()),
+        )
+        .await;
+        let heights = statuses
+            .iter()
+            .map(|status| match status {
+                Ok(status) if !status.sync_info.catching_up => {
+                    Some(status.sync_info.latest_block_height.value())
+                }
+                _ => None,
+            })
+            .collect::<Vec<Option<u64>>>();
+        let max_height = heights.iter().flatten().max().copied().unwrap_or_default();
+
+        self.health.lock().unwrap().healthy = heights
+            .iter()
+            .map(|height| height.is_some_and(|height| height + MAX_HEIGHT_LAG >= max_height))
+            .collect();
+    }
+
+    /// The endpoints to send a request to, in order: healthy ones first from
+    /// the selected one, unhealthy ones as a last resort.
+    fn order(&self) -> Vec<usize> {
+        let total = self.endpoints.len();
+        let start = match self.selection {
+            RpcSelection::Sticky => self.next.load(Ordering::Relaxed),
+            RpcSelection::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed),
+        };
+
+        let healthy = self.health.lock().unwrap().healthy.clone();
+        let (mut order, unhealthy): (Vec<usize>, Vec<usize>) = (0..total)
+            .map(|offset| (start % total + offset) % total)
+            .partition(|index| healthy[*index]);
+        order.extend(unhealthy);
+
+        if self.selection == RpcSelection::Sticky {
+            self.next.store(order[0], Ordering::Relaxed);
+        }
+        order
+    }
+
+    fn fail(&self, index: usize) {
+        metrics().record_rpc_error(&self.endpoints[index].url);
+        self.health.lock().unwrap().healthy[index] = false;
+        if self.selection == RpcSelection::Sticky {
+            let _ = self.next.compare_exchange(
+                index,
+                (index + 1) % self.endpoints.len(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    fn served(&self, index: usize) {
+        let url = &self.endpoints[index].url;
+        let _ = SERVED_BY.try_with(|served| served.borrow_mut().insert(url.clone()));
+    }
+}
+
+#[async_trait]
+impl Client for RpcPool {
+    async fn perform<R>(&self, request: R) -> Result<R::Output, Error>
+    where
+        R: SimpleRequest,
+    {
+        self.probe().await;
+
+        let mut last_error = None;
+        for index in self.order() {
+            match self.endpoints[index].client.perform(replay(&request)).await {
+                Ok(output) => {
+                    self.served(index);
+                    return Ok(output);
+                }
+                // the node answered, another one would most likely answer the same
+                Err(e) if matches!(e.detail(), ErrorDetail::Response(_)) => {
+                    self.served(index);
+                    return Err(e);
+                }
+                Err(e) => {
+                    warn!(
+                        endpoint = %self.endpoints[index].url,
+                        error = %e,
+                        "RPC endpoint failed, failing over"
+                    );
+                    self.fail(index);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.expect("Pool should have an endpoint."))
+    }
+}
+
+/// Runs `future` and returns the endpoints that served its requests.
+pub async fn track<F: Future>(future: F) -> (F::Output, Vec<String>) {
+    SERVED_BY
+        .scope(Default::default(), async {
+            let output = future.await;
+            let served = SERVED_BY.with(|served| served.take());
+            (output, served.into_iter().collect())
+        })
+        .await
+}
+
+// requests aren't `Clone`, but they are serializable
+fn replay<R: Serialize + DeserializeOwned>(request: &R) -> R {
+    serde_json::to_value(request)
+        .and_then(serde_json::from_value)
+        .expect("Request should be serializable.")
+}
@@ -0,0 +1,221 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::MatchOptions;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{config::SelectionStrategy, utils::fuzz::Fuzzer};
+
+#[derive(Error, Debug)]
+pub enum SelectionError {
+    #[error("invalid scenario pattern `{0}`: {1}")]
+    Pattern(String, String),
+    #[error("no scenario matches `{0}`")]
+    NoScenario(String),
+    #[error("the {0} strategy requires --selection-manifest")]
+    MissingManifest(&'static str),
+    #[error("can't read manifest `{0}`: {1}")]
+    Manifest(PathBuf, String),
+    #[error("the manifest has no {0}")]
+    EmptyManifest(&'static str),
+}
+
+/// Scenarios listed for the strategies that don't pick from `--scenarios`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Manifest {
+    /// weight of each scenario for the weighted strategy
+    #[serde(default)]
+    pub weights: BTreeMap<String, u64>,
+    /// scenarios each worker runs, in order, for the list strategy
+    #[serde(default)]
+    pub workers: Vec<Vec<String>>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, SelectionError> {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+            .map_err(|e| SelectionError::Manifest(path.to_owned(), e))
+    }
+}
+
+/// The scenarios a worker was assigned, recorded in its reports.
+#[derive(Clone, Debug, Serialize)]
+pub struct Assignment {
+    pub strategy: String,
+    pub scenarios: Vec<String>,
+}
+
+/// Scenario files in the `pattern` directory, or matching the `pattern` glob,
+/// sorted so the same seed picks the same scenarios.
+pub fn scenario_files(pattern: &str) -> Result<Vec<PathBuf>, SelectionError> {
+    let pattern = if Path::new(pattern).is_dir() {
+        format!("{}/*.json", pattern.trim_end_matches('/'))
+    } else {
+        pattern.to_string()
+    };
+
+    let options = MatchOptions {
+        case_sensitive: false,
+        ..MatchOptions::new()
+    };
+    let mut paths = glob::glob_with(&pattern, options)
+        .map_err(|e| SelectionError::Pattern(pattern.clone(), e.to_string()))?
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(SelectionError::NoScenario(pattern));
+    }
+    Ok(paths)
+}
+
+/// Assigns the scenarios each of the `workers` runs, in order.
+pub fn assign(
+    strategy: SelectionStrategy,
+    pattern: &str,
+    manifest: Option<&Path>,
+    workers: u64,
+    seed: u64,
+) -> Result<Vec<Vec<String>>, SelectionError> {
+    let load_manifest = |strategy| match manifest {
+        Some(path) => Manifest::load(path),
+        None => Err(SelectionError::MissingManifest(strategy)),
+    };
+    let files = || -> Result<Vec<String>, SelectionError> {
+        Ok(scenario_files(pattern)?
+            .into_iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect())
+    };
+
+    let assignment = match strategy {
+        SelectionStrategy::Random => {
+            let files = files()?;
+            (0..workers)
+                .map(|worker_id| {
+                    let index = Fuzzer::new(seed, worker_id).rng().gen_range(0..files.len());
+                    vec![files[index].clone()]
+                })
+                .collect()
+        }
+        SelectionStrategy::RoundRobin => {
+            let files = files()?;
+            (0..workers)
+                .map(|worker_id| vec![files[worker_id as usize % files.len()].clone()])
+                .collect()
+        }
+        SelectionStrategy::Once => {
+            let files = files()?;
+            (0..workers)
+                .map(|worker_id| {
+                    files
+                        .iter()
+                        .skip(worker_id as usize)
+                        .step_by(workers as usize)
+                        .cloned()
+                        .collect()
+                })
+                .collect()
+        }
+        SelectionStrategy::Weighted => {
+            let weights = load_manifest("weighted")?.weights;
+            let total_weight = weights.values().sum::<u64>();
+            if total_weight == 0 {
+                return Err(SelectionError::EmptyManifest("weights"));
+            }
+            (0..workers)
+                .map(|worker_id| {
+                    let mut pick = Fuzzer::new(seed, worker_id)
+                        .rng()
+                        .gen_range(0..total_weight);
+                    let (scenario, _) = weights
+                        .iter()
+                        .find(|(_, weight)| {
+                            if pick < **weight {
+                                return true;
+                            }
+                            pick -= **weight;
+                            false
+                        })
+                        .expect("Pick should be below the total weight.");
+                    vec![scenario.clone()]
+                })
+                .collect()
+        }
+        SelectionStrategy::List => {
+            let lists = load_manifest("list")?.workers;
+            if lists.is_empty() {
+                return Err(SelectionError::EmptyManifest("workers"));
+            }
+            // workers without a list of their own don't run anything
+            (0..workers)
+                .map(|worker_id| lists.get(worker_id as usize).cloned().unwrap_or_default())
+                .collect()
+        }
+    };
+
+    Ok(assignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.json", "b.json", "c.json", "notes.txt"] {
+            fs::write(dir.path().join(name), "{}").unwrap();
+        }
+        let pattern = dir.path().to_string_lossy().to_string();
+        let names = |assignment: Vec<Vec<String>>| {
+            assignment
+                .into_iter()
+                .map(|scenarios| {
+                    scenarios
+                        .iter()
+                        .map(|scenario| scenario.rsplit('/').next().unwrap().to_string())
+                        .collect::<Vec<String>>()
+                })
+                .collect::<Vec<Vec<String>>>()
+        };
+
+        let assignment = assign(SelectionStrategy::RoundRobin, &pattern, None, 4, 0).unwrap();
+        assert_eq!(
+            names(assignment),
+            vec![
+                vec!["a.json"],
+                vec!["b.json"],
+                vec!["c.json"],
+                vec!["a.json"]
+            ]
+        );
+
+        let assignment = assign(SelectionStrategy::Once, &pattern, None, 2, 0).unwrap();
+        assert_eq!(
+            names(assignment),
+            vec![vec!["a.json", "c.json"], vec!["b.json"]]
+        );
+
+        let assignment = assign(SelectionStrategy::Random, &pattern, None, 3, 42).unwrap();
+        assert!(assignment.iter().all(|scenarios| scenarios.len() == 1));
+        assert_eq!(
+            assignment,
+            assign(SelectionStrategy::Random, &pattern, None, 3, 42).unwrap()
+        );
+
+        assert!(matches!(
+            assign(SelectionStrategy::Weighted, &pattern, None, 1, 0),
+            Err(SelectionError::MissingManifest("weighted"))
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use std::future;
+
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+};
+use tracing::warn;
+
+/// Exit code of a run interrupted by a signal.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Shutdown requested with SIGINT or SIGTERM. After the first signal the
+/// runner doesn't start new steps and waits for the running ones, after the
+/// second it aborts them too.
+#[derive(Clone, Debug)]
+pub struct Shutdown {
+    signals: watch::Receiver<u64>,
+}
+
+impl Default for Shutdown {
+    /// A shutdown that is never requested.
+    fn default() -> Self {
+        Self {
+            signals: watch::channel(0).1,
+        }
+    }
+}
+
+impl Shutdown {
+    pub fn install() -> Self {
+        let (sender, signals) = watch::channel(0);
+        let mut terminate =
+            signal(SignalKind::terminate()).expect("SIGTERM handler should be installed.");
+        let mut interrupt =
+            signal(SignalKind::interrupt()).expect("SIGINT handler should be installed.");
+
+        tokio::spawn(async move {
+            let mut total_signals = 0;
+            loop {
+                tokio::select! {
+                    _ = terminate.recv() => (),
+                    _ = interrupt.recv() => (),
+                }
+                total_signals += 1;
+                if total_signals == 1 {
+                    warn!("Stopping after the running steps, signal again to abort them");
+                } else {
+                    warn!("Aborting the running steps");
+                }
+                let _ = sender.send(total_signals);
+            }
+        });
+
+        Self { signals }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.signals.borrow() > 0
+    }
+
+    /// Completes once the running steps have to be aborted.
+    pub async fn aborted(&self) {
+        let mut signals = self.signals.clone();
+        while *signals.borrow() < 2 {
+            if signals.changed().await.is_err() {
+                // the signals are no longer listened to
+                future::pending::<()>().await
+            }
+        }
+    }
+}
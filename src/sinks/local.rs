@@ -0,0 +1,38 @@
+use std::{fs, path::PathBuf};
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::config::AppConfig;
+
+use super::{ReportSink, RunReports, SinkError};
+
+pub struct LocalSink {
+    dir: PathBuf,
+}
+
+impl LocalSink {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            dir: config.report_dir.clone(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl ReportSink for LocalSink {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn publish(&self, reports: &RunReports) -> Result<(), SinkError> {
+        fs::create_dir_all(&self.dir).map_err(|e| SinkError::Io(e.to_string()))?;
+        for file in reports.files() {
+            let target = self.dir.join(file.file_name().unwrap());
+            fs::copy(file, &target).map_err(|e| SinkError::Io(e.to_string()))?;
+            info!(path = %target.to_string_lossy(), "Report written");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::config::{AppConfig, ReportSinkKind};
+
+use self::{local::LocalSink, s3::S3Sink, stdout::StdoutSink, webhook::WebhookSink};
+
+pub mod local;
+pub mod s3;
+pub mod stdout;
+pub mod webhook;
+
+#[derive(Error, Debug)]
+pub enum SinkError {
+    #[error("missing option `--{0}`")]
+    MissingOption(&'static str),
+    #[error("io error `{0}`")]
+    Io(String),
+    #[error("upload error `{0}`")]
+    Upload(String),
+    #[error("webhook error `{0}`")]
+    Webhook(String),
+}
+
+/// The reports of a run of a scenario.
+#[derive(Clone, Debug)]
+pub struct RunReports {
+    /// file name of the reports, without extension
+    pub name: String,
+    pub scenario_name: String,
+    pub worker_id: u64,
+    pub try_index: u64,
+    pub outcome: String,
+    /// every step succeeded or was a no-op
+    pub succeeded: bool,
+    pub markdown: PathBuf,
+    pub json: PathBuf,
+    pub junit: PathBuf,
+}
+
+impl RunReports {
+    pub fn files(&self) -> [&PathBuf; 3] {
+        [&self.markdown, &self.json, &self.junit]
+    }
+}
+
+#[async_trait(?Send)]
+pub trait ReportSink {
+    fn name(&self) -> &'static str;
+
+    async fn publish(&self, reports: &RunReports) -> Result<(), SinkError>;
+}
+
+/// The sinks selected by `--report-sinks`, in order, see
+/// `AppConfig::report_sinks`.
+pub fn from_config(config: &AppConfig) -> Result<Vec<Box<dyn ReportSink>>, SinkError> {
+    config
+        .report_sinks()
+        .iter()
+        .map(|kind| -> Result<Box<dyn ReportSink>, SinkError> {
+            match kind {
+                ReportSinkKind::Local => Ok(Box::new(LocalSink::new(config))),
+                ReportSinkKind::S3 => Ok(Box::new(S3Sink::new(config)?)),
+                ReportSinkKind::Webhook => Ok(Box::new(WebhookSink::new(config)?)),
+                ReportSinkKind::Stdout => Ok(Box::new(StdoutSink)),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use minio::s3::{args::UploadObjectArgs, client::Client, creds::StaticProvider, http::BaseUrl};
+use tracing::info;
+
+use crate::config::AppConfig;
+
+use super::{ReportSink, RunReports, SinkError};
+
+pub struct S3Sink {
+    url: String,
+    access_key: String,
+    secret_key: String,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3Sink {
+    pub fn new(config: &AppConfig) -> Result<Self, SinkError> {
+        Ok(Self {
+            url: config
+                .minio_url
+                .clone()
+                .ok_or(SinkError::MissingOption("minio-url"))?,
+            access_key: config
+                .minio_access_key
+                .clone()
+                .ok_or(SinkError::MissingOption("minio-access-key"))?,
+            secret_key: config
+                .minio_secret_key
+                .clone()
+                .ok_or(SinkError::MissingOption("minio-secret-key"))?,
+            bucket: config.minio_bucket.clone(),
+            prefix: config.minio_prefix.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReportSink for S3Sink {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn publish(&self, reports: &RunReports) -> Result<(), SinkError> {
+        let base_url = self
+            .url
+            .parse::<BaseUrl>()
+            .map_err(|e| SinkError::Upload(e.to_string()))?;
+
+        let static_provider = StaticProvider::new(&self.access_key, &self.secret_key, None);
+
+        let client = Client::new(base_url, Some(Box::new(static_provider)), None, None)
+            .map_err(|e| SinkError::Upload(e.to_string()))?;
+
+        for file in reports.files() {
+            let object_name = format!(
+                "{}{}",
+                self.prefix,
+                file.file_name().unwrap().to_string_lossy()
+            );
+            let args = UploadObjectArgs::new(&self.bucket, &object_name, &file.to_string_lossy())
+                .map_err(|e| SinkError::Upload(e.to_string()))?;
+            client
+                .upload_object(&args)
+                .await
+                .map_err(|e| SinkError::Upload(e.to_string()))?;
+            info!(bucket = %self.bucket, object = %object_name, "Report uploaded");
+        }
+
+        Ok(())
+    }
+}
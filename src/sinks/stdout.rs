@@ -0,0 +1,22 @@
+use std::fs;
+
+use async_trait::async_trait;
+
+use super::{ReportSink, RunReports, SinkError};
+
+pub struct StdoutSink;
+
+#[async_trait(?Send)]
+impl ReportSink for StdoutSink {
+    fn name(&self) -> &'static str {
+        "stdout"
+    }
+
+    async fn publish(&self, reports: &RunReports) -> Result<(), SinkError> {
+        let markdown =
+            fs::read_to_string(&reports.markdown).map_err(|e| SinkError::Io(e.to_string()))?;
+        println!("{}", markdown);
+
+        Ok(())
+    }
+}
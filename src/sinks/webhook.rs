@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+
+use crate::config::AppConfig;
+
+use super::{ReportSink, RunReports, SinkError};
+
+/// Posts a JSON body to a url once the reports are published by the previous
+/// sinks, e.g. to update a commit status.
+///
+/// The body is a template, `{scenario}`, `{outcome}`, `{state}` (`success` or
+/// `failure`, as expected by a commit status), `{chain_id}`, `{sha}`,
+/// `{worker_id}`, `{try}`, `{report}` (the file name of the reports without
+/// extension), `{bucket}`, `{prefix}` and `{artifacts_url}` are replaced by
+/// their JSON escaped value, or an empty string if not set.
+pub struct WebhookSink {
+    url: String,
+    body: String,
+    chain_id: String,
+    sha: String,
+    bucket: String,
+    prefix: String,
+    artifacts_url: String,
+}
+
+impl WebhookSink {
+    pub fn new(config: &AppConfig) -> Result<Self, SinkError> {
+        Ok(Self {
+            url: config
+                .webhook_url()
+                .cloned()
+                .ok_or(SinkError::MissingOption("webhook-url"))?,
+            body: config.webhook_body.clone(),
+            chain_id: config.chain_id.clone(),
+            sha: config.sha.clone().unwrap_or_default(),
+            bucket: config.minio_bucket.clone(),
+            prefix: config.minio_prefix.clone().unwrap_or_default(),
+            artifacts_url: config.artifacts_url.clone().unwrap_or_default(),
+        })
+    }
+
+    fn render(&self, reports: &RunReports) -> String {
+        let worker_id = reports.worker_id.to_string();
+        let try_index = reports.try_index.to_string();
+        let state = if reports.succeeded {
+            "success"
+        } else {
+            "failure"
+        };
+        let variables = [
+            ("scenario", reports.scenario_name.as_str()),
+            ("outcome", reports.outcome.as_str()),
+            ("state", state),
+            ("chain_id", self.chain_id.as_str()),
+            ("sha", self.sha.as_str()),
+            ("worker_id", worker_id.as_str()),
+            ("try", try_index.as_str()),
+            ("report", reports.name.as_str()),
+            ("bucket", self.bucket.as_str()),
+            ("prefix", self.prefix.as_str()),
+            ("artifacts_url", self.artifacts_url.as_str()),
+        ];
+
+        variables
+            .into_iter()
+            .fold(self.body.clone(), |body, (variable, value)| {
+                let value = serde_json::to_string(value).unwrap();
+                body.replace(&format!("{{{}}}", variable), &value[1..value.len() - 1])
+            })
+    }
+}
+
+#[async_trait(?Send)]
+impl ReportSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn publish(&self, reports: &RunReports) -> Result<(), SinkError> {
+        let client = reqwest::Client::new();
+
+        client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(self.render(reports))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| SinkError::Webhook(e.to_string()))?;
+
+        Ok(())
+    }
+}
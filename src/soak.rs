@@ -0,0 +1,283 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use futures::future::join_all;
+use serde::Serialize;
+use tracing::{error, info, info_span, warn, Instrument};
+
+use crate::{
+    bootstrap::{Bootstrap, BootstrapReport},
+    config::{AppConfig, SoakConfig},
+    report::unix_millis,
+    runner::Runner,
+    scenario::Scenario,
+    shutdown::Shutdown,
+    sinks::RunReports,
+    utils::fuzz::mix,
+};
+
+/// A generated scenario that didn't succeed, saved with its reports.
+#[derive(Clone, Debug, Serialize)]
+pub struct SoakFailure {
+    pub iteration: u64,
+    pub worker_id: u64,
+    pub scenario: String,
+    /// the seed the scenario ran with, fuzzed values depend on the worker too
+    pub seed: u64,
+    /// folder with the scenario and its reports
+    pub artifacts: PathBuf,
+}
+
+/// Results of the scenarios run by every worker, saved to `summary.json` in
+/// the artifacts folder.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SoakSummary {
+    pub iterations: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    /// the soak was stopped by a signal, the interrupted scenarios aren't counted
+    pub interrupted: bool,
+    /// unix timestamp in milliseconds
+    pub started_at: Option<u128>,
+    pub duration_ms: u128,
+    pub failures: Vec<SoakFailure>,
+}
+
+impl SoakSummary {
+    /// Counts a scenario that ran to completion, returns whether it succeeded.
+    /// A scenario succeeds if every try built its reports and succeeded.
+    fn record(&mut self, reports: &[RunReports]) -> bool {
+        let succeeded = !reports.is_empty() && reports.iter().all(|reports| reports.succeeded);
+        self.iterations += 1;
+        if succeeded {
+            self.succeeded += 1;
+        } else {
+            self.failed += 1;
+        }
+        succeeded
+    }
+}
+
+// shared by the workers, which all run on the same task
+struct SoakState {
+    soak: SoakConfig,
+    shutdown: Shutdown,
+    deadline: Option<Instant>,
+    next_iteration: AtomicU64,
+    summary: Mutex<SoakSummary>,
+}
+
+impl SoakState {
+    /// The index of the next scenario to run, if the soak isn't over.
+    fn next_iteration(&self) -> Option<u64> {
+        if self.shutdown.is_requested()
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return None;
+        }
+        let iteration = self.next_iteration.fetch_add(1, Ordering::Relaxed);
+        match self.soak.iterations {
+            Some(iterations) if iteration >= iterations => None,
+            _ => Some(iteration),
+        }
+    }
+}
+
+/// Generates and runs scenarios on every worker until `--duration` seconds
+/// passed, `--iterations` scenarios ran or the soak is interrupted. The
+/// scenarios running when the duration is over run to completion.
+pub async fn soak(
+    config: AppConfig,
+    soak: SoakConfig,
+    shutdown: Shutdown,
+    bootstrap: Option<Bootstrap>,
+) -> SoakSummary {
+    let started_at = SystemTime::now();
+    let state = SoakState {
+        deadline: soak
+            .duration
+            .map(|duration| Instant::now() + Duration::from_secs(duration)),
+        soak,
+        shutdown,
+        next_iteration: AtomicU64::new(0),
+        summary: Mutex::new(SoakSummary {
+            started_at: unix_millis(started_at),
+            ..Default::default()
+        }),
+    };
+
+    let workers = (0..config.workers).map(|worker_id| {
+        let mut config = config.clone();
+        if let Some(bootstrap) = &bootstrap {
+            config.faucet_sk = bootstrap.faucet_sk(worker_id);
+        }
+        let bootstrap = bootstrap.as_ref().map(|bootstrap| bootstrap.report.clone());
+        run_worker(&state, worker_id, config, bootstrap).instrument(info_span!("worker", worker_id))
+    });
+    join_all(workers).await;
+
+    let mut summary = state.summary.into_inner().unwrap();
+    summary.interrupted = state.shutdown.is_requested();
+    summary.duration_ms = started_at.elapsed().unwrap_or_default().as_millis();
+    info!(
+        iterations = summary.iterations,
+        succeeded = summary.succeeded,
+        failed = summary.failed,
+        "Soak done"
+    );
+
+    let summary_path = state.soak.artifacts_dir.join("summary.json");
+    if let Err(e) = fs::create_dir_all(&state.soak.artifacts_dir).and_then(|_| {
+        let summary_json = serde_json::to_string_pretty(&summary)?;
+        fs::write(&summary_path, summary_json)
+    }) {
+        error!(path = %summary_path.to_string_lossy(), error = %e, "Can't save the soak summary");
+    }
+
+    summary
+}
+
+async fn run_worker(
+    state: &SoakState,
+    worker_id: u64,
+    config: AppConfig,
+    bootstrap: Option<BootstrapReport>,
+) {
+    while let Some(iteration) = state.next_iteration() {
+        let mut builder = state.soak.weights.builder();
+        builder.generate(state.soak.steps);
+        let scenario = builder.build();
+        let scenario_name = builder.name();
+        if let Ok(folder) = env::var("ANTITHESIS_OUTPUT_DIR") {
+            export_scenario(&folder, worker_id, &scenario);
+        }
+
+        // every iteration picks its own fuzzed values, the fuzzer mixes in the
+        // worker on top of it
+        let seed = mix(config.seed.unwrap_or_default(), iteration);
+        let mut config = config.clone();
+        config.seed = Some(seed);
+        config.work_dir = config
+            .work_dir
+            .map(|work_dir| work_dir.join(format!("iteration-{}", iteration)));
+
+        let mut runner = Runner::new(state.shutdown.clone(), bootstrap.clone(), None);
+        let span = info_span!("scenario", name = %scenario_name, iteration);
+        let reports = runner
+            .run(
+                worker_id,
+                scenario.clone(),
+                &config,
+                scenario_name.clone(),
+                None,
+            )
+            .instrument(span)
+            .await;
+        if state.shutdown.is_requested() {
+            break;
+        }
+
+        let mut summary = state.summary.lock().unwrap();
+        if summary.record(&reports) {
+            info!(iteration, scenario = %scenario_name, "Scenario succeeded");
+            continue;
+        }
+
+        let artifacts = state.soak.artifacts_dir.join(&scenario_name);
+        warn!(
+            iteration,
+            scenario = %scenario_name,
+            artifacts = %artifacts.to_string_lossy(),
+            "Scenario failed"
+        );
+        if let Err(e) = save_artifacts(&artifacts, &scenario_name, &scenario, &reports) {
+            error!(error = %e, "Can't save the failed scenario");
+        }
+        summary.failures.push(SoakFailure {
+            iteration,
+            worker_id,
+            scenario: scenario_name,
+            seed,
+            artifacts,
+        });
+    }
+}
+
+// a worker runs several scenarios, one per line
+fn export_scenario(folder: &str, worker_id: u64, scenario: &Scenario) {
+    let output_path = format!("{}/scenario-{}.json", folder, worker_id);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output_path)
+        .unwrap();
+    writeln!(file, "{}", serde_json::to_string(scenario).unwrap()).unwrap();
+}
+
+fn save_artifacts(
+    dir: &Path,
+    scenario_name: &str,
+    scenario: &Scenario,
+    reports: &[RunReports],
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(format!("{}.json", scenario_name)),
+        serde_json::to_string_pretty(scenario)?,
+    )?;
+    for file in reports.iter().flat_map(RunReports::files) {
+        if let Some(file_name) = file.file_name() {
+            fs::copy(file, dir.join(file_name))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn reports(succeeded: bool) -> RunReports {
+        RunReports {
+            name: "report".to_string(),
+            scenario_name: "scenario".to_string(),
+            worker_id: 0,
+            try_index: 0,
+            outcome: if succeeded {
+                "success"
+            } else {
+                "error: failed"
+            }
+            .to_string(),
+            succeeded,
+            markdown: PathBuf::from("report.md"),
+            json: PathBuf::from("report.json"),
+            junit: PathBuf::from("report.xml"),
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let mut summary = SoakSummary::default();
+
+        assert!(summary.record(&[reports(true)]));
+        assert!(!summary.record(&[reports(true), reports(false)]));
+        // an interrupted scenario didn't build any report
+        assert!(!summary.record(&[]));
+
+        assert_eq!(summary.iterations, 3);
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.failed, 2);
+    }
+}
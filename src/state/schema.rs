@@ -0,0 +1,273 @@
+use std::{fmt::Display, str::FromStr};
+
+use namada_sdk::{
+    address::Address,
+    key::common::{PublicKey, SecretKey},
+    string_encoding::{MASP_EXT_SPENDING_KEY_HRP, MASP_PAYMENT_ADDRESS_HRP},
+};
+use serde_json::Value as Json;
+
+use crate::{policy::FailureKind, scenario::StepResult, utils::value::parse_amount};
+
+use super::state::StepStorage;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Address,
+    Alias,
+    PublicKey,
+    PrivateKey,
+    PaymentAddress,
+    SpendingKey,
+    Amount,
+    Epoch,
+    Height,
+    Number,
+    Bool,
+    ProposalStatus,
+    ValidatorState,
+    Text,
+    Json,
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldType::Address => write!(f, "address"),
+            FieldType::Alias => write!(f, "alias"),
+            FieldType::PublicKey => write!(f, "public-key"),
+            FieldType::PrivateKey => write!(f, "private-key"),
+            FieldType::PaymentAddress => write!(f, "payment-address"),
+            FieldType::SpendingKey => write!(f, "spending-key"),
+            FieldType::Amount => write!(f, "amount"),
+            FieldType::Epoch => write!(f, "epoch"),
+            FieldType::Height => write!(f, "height"),
+            FieldType::Number => write!(f, "number"),
+            FieldType::Bool => write!(f, "bool"),
+            FieldType::ProposalStatus => write!(f, "proposal-status"),
+            FieldType::ValidatorState => write!(f, "validator-state"),
+            FieldType::Text => write!(f, "text"),
+            FieldType::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FieldType {
+    /// Checks that `value` can be read back as this type by the steps
+    /// referencing it.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let valid = match self {
+            FieldType::Address => Address::from_str(value).is_ok(),
+            FieldType::Alias => !value.is_empty(),
+            FieldType::PublicKey => PublicKey::from_str(value).is_ok(),
+            FieldType::PrivateKey => SecretKey::from_str(value).is_ok(),
+            FieldType::PaymentAddress => value.starts_with(MASP_PAYMENT_ADDRESS_HRP),
+            FieldType::SpendingKey => value.starts_with(MASP_EXT_SPENDING_KEY_HRP),
+            FieldType::Amount => parse_amount(value).is_ok(),
+            FieldType::Epoch | FieldType::Height | FieldType::Number => {
+                value.parse::<u64>().is_ok()
+            }
+            FieldType::Bool => value.parse::<bool>().is_ok(),
+            FieldType::ProposalStatus => ["pending", "on-going", "ended"].contains(&value),
+            FieldType::ValidatorState => [
+                "consensus",
+                "below-capacity",
+                "below-threshold",
+                "inactive",
+                "jailed",
+                "unknown",
+            ]
+            .contains(&value),
+            FieldType::Text => true,
+            FieldType::Json => serde_json::from_str::<Json>(value).is_ok(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("`{}` isn't a valid {}", value, self))
+        }
+    }
+}
+
+/// A key written to `StepStorage`. `{}` in the key matches any non empty
+/// part, e.g. `validator-{}-address` for indexed outputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputField {
+    pub key: String,
+    pub field_type: FieldType,
+}
+
+impl OutputField {
+    pub fn new(key: impl ToString, field_type: FieldType) -> Self {
+        Self {
+            key: key.to_string(),
+            field_type,
+        }
+    }
+
+    pub fn matches(&self, key: &str) -> bool {
+        matches_pattern(&self.key, key)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OutputSchema {
+    pub fields: Vec<OutputField>,
+}
+
+impl OutputSchema {
+    pub fn new(fields: Vec<OutputField>) -> Self {
+        Self { fields }
+    }
+
+    // fields written by `Task::fetch_info` and `Task::tx_result`, see `TxStorageKeys`
+    pub fn with_tx_info(fields: Vec<OutputField>) -> Self {
+        let mut schema = Self::new(vec![
+            OutputField::new("epoch", FieldType::Epoch),
+            OutputField::new("height", FieldType::Height),
+            OutputField::new("tx-hash", FieldType::Text),
+            OutputField::new("wrapper-hash", FieldType::Text),
+            OutputField::new("atomic", FieldType::Bool),
+            OutputField::new("broadcast-only", FieldType::Bool),
+            OutputField::new("inclusion-height", FieldType::Height),
+            OutputField::new("gas-used", FieldType::Number),
+            OutputField::new("vp-errors", FieldType::Text),
+            OutputField::new("rejected-vps", FieldType::Text),
+            OutputField::new("inner-tx-{}-hash", FieldType::Text),
+            OutputField::new("inner-tx-{}-status", FieldType::Text),
+            OutputField::new("inner-tx-{}-vp-errors", FieldType::Text),
+            OutputField::new("inner-tx-{}-rejected-vps", FieldType::Text),
+            OutputField::new("inner-tx-{}-status-flags", FieldType::Text),
+            OutputField::new("inner-tx-{}-events", FieldType::Text),
+        ]);
+        schema.fields.extend(fields);
+        schema
+    }
+
+    pub fn field(&self, key: &str) -> Option<&OutputField> {
+        self.fields.iter().find(|field| field.matches(key))
+    }
+
+    pub fn undeclared_keys(&self, storage: &StepStorage) -> Vec<String> {
+        storage
+            .storage
+            .keys()
+            .filter(|key| self.field(key).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// The declared keys whose value doesn't match their `FieldType`.
+    pub fn invalid_values(&self, storage: &StepStorage) -> Vec<String> {
+        storage
+            .storage
+            .iter()
+            .filter_map(|(key, value)| {
+                let field = self.field(key)?;
+                field
+                    .field_type
+                    .validate(value)
+                    .err()
+                    .map(|e| format!("{} ({})", key, e))
+            })
+            .collect()
+    }
+
+    /// Fails a step that wrote undeclared keys or invalid values, keeping
+    /// what it wrote for the reports.
+    pub fn enforce(&self, result: StepResult) -> StepResult {
+        let undeclared_keys = self.undeclared_keys(&result.data);
+        if !undeclared_keys.is_empty() {
+            return StepResult::fail_with_kind(
+                FailureKind::InvalidOutput,
+                format!(
+                    "step wrote undeclared output keys: {}",
+                    undeclared_keys.join(", ")
+                ),
+                result.data,
+            );
+        }
+        let invalid_values = self.invalid_values(&result.data);
+        if !invalid_values.is_empty() {
+            return StepResult::fail_with_kind(
+                FailureKind::InvalidOutput,
+                format!(
+                    "step wrote invalid output values: {}",
+                    invalid_values.join(", ")
+                ),
+                result.data,
+            );
+        }
+        result
+    }
+}
+
+fn matches_pattern(pattern: &str, key: &str) -> bool {
+    match pattern.split_once("{}") {
+        None => pattern == key,
+        Some((prefix, rest)) => {
+            let key = match key.strip_prefix(prefix) {
+                Some(key) => key,
+                None => return false,
+            };
+            (1..=key.len())
+                .any(|split| key.is_char_boundary(split) && matches_pattern(rest, &key[split..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pattern_matching() {
+        let field = OutputField::new("validator-{}-address", FieldType::Address);
+        assert!(field.matches("validator-12-address"));
+        assert!(!field.matches("validator--address"));
+
+        let field = OutputField::new("{}-{}-bond", FieldType::Amount);
+        assert!(field.matches("tnam1a-tnam1b-bond"));
+        assert!(!field.matches("tnam1a-tnam1b-bond-epoch"));
+    }
+
+    #[test]
+    fn test_undeclared_keys() {
+        let schema =
+            OutputSchema::with_tx_info(vec![OutputField::new("amount-{}", FieldType::Amount)]);
+        let mut storage = StepStorage::default();
+        storage.add("epoch".to_string(), "1".to_string());
+        storage.add("amount-0".to_string(), "10".to_string());
+        storage.add("amount".to_string(), "10".to_string());
+
+        assert_eq!(schema.undeclared_keys(&storage), vec!["amount".to_string()]);
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let schema = OutputSchema::with_tx_info(vec![
+            OutputField::new("amount-{}", FieldType::Amount),
+            OutputField::new("state", FieldType::ValidatorState),
+            OutputField::new("data", FieldType::Json),
+        ]);
+        let mut storage = StepStorage::default();
+        storage.add("epoch".to_string(), "1".to_string());
+        storage.add("amount-0".to_string(), "10.000000".to_string());
+        storage.add("amount-1".to_string(), "tnam1a".to_string());
+        storage.add("state".to_string(), "jailed".to_string());
+        storage.add("data".to_string(), "{\"a\":1}".to_string());
+        storage.add("broadcast-only".to_string(), "yes".to_string());
+
+        assert_eq!(
+            schema.invalid_values(&storage),
+            vec![
+                "amount-1 (`tnam1a` isn't a valid amount)".to_string(),
+                "broadcast-only (`yes` isn't a valid bool)".to_string(),
+            ]
+        );
+
+        let result = schema.enforce(StepResult::success(storage));
+        assert_eq!(result.failure, Some(FailureKind::InvalidOutput));
+        assert_eq!(result.data.get_field("epoch"), Some("1".to_string()));
+    }
+}
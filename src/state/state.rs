@@ -1,17 +1,44 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    time::{Duration, SystemTime},
+};
 
 use indexmap::IndexMap as HashMap;
 use namada_sdk::storage::BlockHeight;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use thiserror::Error;
+
+use crate::{
+    policy::FailureKind,
+    scenario::StepResult,
+    utils::{fuzz::Fuzzer, value::ParameterError},
+};
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum StorageError {
+    #[error("step {0} has no result")]
+    MissingStep(u64),
+    #[error("step {0} has no field `{1}`")]
+    MissingField(u64, String),
+    #[error("no account with alias `{0}`")]
+    MissingAccount(String),
+}
 
-use crate::scenario::StepResult;
-
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum StepOutcome {
     Success,
     Fail(String),
     CheckFail(String, String), // actual, expected
     NoOp,
     CheckSkip(bool),
+    InvalidScenario {
+        step_id: u64,
+        parameter: String,
+        reason: String,
+    },
+    /// The run was interrupted before the step ran or while it was running
+    NotRun,
 }
 
 impl Display for StepOutcome {
@@ -34,11 +61,34 @@ impl Display for StepOutcome {
                 )
             }
             StepOutcome::NoOp => write!(f, "no op"),
+            StepOutcome::InvalidScenario {
+                step_id,
+                parameter,
+                reason,
+            } => write!(
+                f,
+                "invalid scenario: step {}, parameter `{}`: {}",
+                step_id, parameter, reason
+            ),
+            StepOutcome::NotRun => write!(f, "not run"),
         }
     }
 }
 
 impl StepOutcome {
+    /// The outcome without its details, as reported in the step status.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            StepOutcome::Success => "success",
+            StepOutcome::Fail(_) => "fail",
+            StepOutcome::CheckFail(_, _) => "check-fail",
+            StepOutcome::NoOp => "no-op",
+            StepOutcome::CheckSkip(_) => "check-skip",
+            StepOutcome::InvalidScenario { .. } => "invalid-scenario",
+            StepOutcome::NotRun => "not-run",
+        }
+    }
+
     pub fn is_succesful(&self) -> bool {
         matches!(self, Self::Success) || matches!(self, Self::NoOp)
     }
@@ -59,6 +109,14 @@ impl StepOutcome {
         matches!(self, Self::CheckSkip(_))
     }
 
+    pub fn is_invalid_scenario(&self) -> bool {
+        matches!(self, Self::InvalidScenario { .. })
+    }
+
+    pub fn is_not_run(&self) -> bool {
+        matches!(self, Self::NotRun)
+    }
+
     pub fn get_skip_outcome(&self) -> bool {
         match self {
             StepOutcome::CheckSkip(outcome) => *outcome,
@@ -85,9 +143,21 @@ impl StepOutcome {
     pub fn skip_check(outcome: bool) -> Self {
         Self::CheckSkip(outcome)
     }
+
+    pub fn invalid_scenario(step_id: u64, error: ParameterError) -> Self {
+        Self::InvalidScenario {
+            step_id,
+            parameter: error.parameter,
+            reason: error.reason,
+        }
+    }
+
+    pub fn not_run() -> Self {
+        Self::NotRun
+    }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct StepStorage {
     pub storage: HashMap<String, String>,
 }
@@ -97,21 +167,77 @@ impl StepStorage {
         self.storage.insert(key, value);
     }
 
-    pub fn get_field(&self, field: &str) -> String {
-        self.storage
-            .get(field)
-            .unwrap_or_else(|| panic!("Field should be present in data: {}.", field))
-            .to_owned()
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        self.storage.get(field).cloned()
+    }
+}
+
+/// How a step was run, recorded for the reports.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StepExecution {
+    /// parameters and settings as they resolved when the step started
+    pub parameters: Json,
+    pub started_at: SystemTime,
+    pub duration: Duration,
+    /// values picked by the fuzzer while the step ran
+    pub fuzzed: HashMap<String, String>,
+    /// more than one if the step was retried, see `StepPolicy`
+    pub attempts: Vec<StepAttempt>,
+}
+
+impl StepExecution {
+    pub fn start(parameters: Json) -> Self {
+        Self {
+            parameters,
+            started_at: SystemTime::now(),
+            duration: Duration::default(),
+            fuzzed: HashMap::default(),
+            attempts: Vec::new(),
+        }
+    }
+
+    pub fn add_attempt(
+        &mut self,
+        started_at: SystemTime,
+        result: &StepResult,
+        endpoints: Vec<String>,
+    ) {
+        self.attempts.push(StepAttempt {
+            outcome: result.outcome.clone(),
+            failure: result.failure,
+            started_at,
+            duration: started_at.elapsed().unwrap_or_default(),
+            endpoints,
+        });
+    }
+
+    pub fn finish(self, fuzzed: HashMap<String, String>) -> Self {
+        Self {
+            duration: self.started_at.elapsed().unwrap_or_default(),
+            fuzzed,
+            ..self
+        }
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StepAttempt {
+    pub outcome: StepOutcome,
+    pub failure: Option<FailureKind>,
+    pub started_at: SystemTime,
+    pub duration: Duration,
+    /// RPC endpoints that served the requests of the attempt
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum StateAddressType {
     Implicit,
     Enstablished,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct StateAddress {
     pub alias: String,
     pub address: String,
@@ -163,17 +289,21 @@ impl StateAddress {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Storage {
     pub step_results: HashMap<u64, StepOutcome>,
     pub step_states: HashMap<u64, StepStorage>,
+    pub step_executions: HashMap<u64, StepExecution>,
     pub accounts: HashMap<String, StateAddress>,
+    /// kept across tries, a retry picks new values
+    pub fuzzer: Fuzzer,
 }
 
 impl Storage {
     pub fn reset(&mut self) {
         self.step_results.clear();
         self.step_states.clear();
+        self.step_executions.clear();
         self.accounts.clear();
     }
 
@@ -195,29 +325,34 @@ impl Storage {
         self.step_states.insert(step_id, step_state);
     }
 
+    pub fn save_step_execution(&mut self, step_id: u64, step_execution: StepExecution) {
+        self.step_executions.insert(step_id, step_execution);
+    }
+
     pub fn save_account(&mut self, account: StateAddress) {
         self.accounts.insert(account.alias.clone(), account);
     }
 
-    pub fn get_step_item(&self, step_id: &u64, field: &str) -> String {
+    pub fn get_step_item(&self, step_id: &u64, field: &str) -> Result<String, StorageError> {
         self.step_states
             .get(step_id)
-            .expect("Step id should be there.")
+            .ok_or(StorageError::MissingStep(*step_id))?
             .get_field(field)
+            .ok_or_else(|| StorageError::MissingField(*step_id, field.to_string()))
     }
 
-    pub fn is_step_successful(&self, step_id: &u64) -> bool {
+    pub fn is_step_successful(&self, step_id: &u64) -> Result<bool, StorageError> {
         self.step_results
             .get(step_id)
-            .expect("Step id should exist.")
-            .is_strict_succesful()
+            .map(|outcome| outcome.is_strict_succesful())
+            .ok_or(StorageError::MissingStep(*step_id))
     }
 
-    pub fn is_step_noop(&self, step_id: &u64) -> bool {
+    pub fn is_step_noop(&self, step_id: &u64) -> Result<bool, StorageError> {
         self.step_results
             .get(step_id)
-            .expect("Step id should exist.")
-            .is_noop()
+            .map(|outcome| outcome.is_noop())
+            .ok_or(StorageError::MissingStep(*step_id))
     }
 
     pub fn save_step_result(&mut self, step_id: u64, step_result: StepResult) {
@@ -259,10 +394,10 @@ impl Storage {
             })
     }
 
-    pub fn get_address(&self, alias: &str) -> StateAddress {
+    pub fn get_address(&self, alias: &str) -> Result<StateAddress, StorageError> {
         self.accounts
             .get(alias)
-            .expect("Address should be there")
-            .to_owned()
+            .cloned()
+            .ok_or_else(|| StorageError::MissingAccount(alias.to_string()))
     }
 }
@@ -3,22 +3,28 @@ use async_trait::async_trait;
 use namada_sdk::{
     args::TxBecomeValidator as SdkBecomeValidatorTx,
     dec::Dec,
-    error::TxSubmitError,
     key::{RefTo, SchemeType},
     signing::default_sign,
     Namada,
 };
 
-use rand::{distributions::Alphanumeric, Rng};
+use rand::Rng;
 use rand_core::OsRng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        fuzz::Fuzzer,
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -45,14 +51,11 @@ impl TxBecomeValidator {
 }
 
 impl TxBecomeValidator {
-    pub fn generate_random_alias(&self, namespace: &str) -> String {
-        let random_suffix: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(5)
-            .map(char::from)
-            .collect();
-
-        format!("lt-addr-{}-{}", namespace, random_suffix)
+    pub fn generate_random_alias(&self, fuzzer: &Fuzzer, namespace: &str) -> String {
+        fuzzer.alias(
+            &format!("{}-key-alias", namespace),
+            &format!("lt-addr-{}", namespace),
+        )
     }
 }
 
@@ -61,20 +64,27 @@ impl Task for TxBecomeValidator {
     type P = BecomeValidatorParameters;
     type B = SdkBecomeValidatorTx;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![OutputField::new(
+            TxBecomeValidatorStorageKeys::ValidatorAddress,
+            FieldType::Address,
+        )])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
         parameters: Self::P,
         settings: TxSettings,
-        _state: &Storage,
+        state: &Storage,
     ) -> Result<StepResult, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
         let commission_rate = Dec::new(parameters.commission_rate as i128, 2).unwrap();
 
-        let consensus_key_alias = self.generate_random_alias("consensus");
-        let eth_cold_key_alias = self.generate_random_alias("eth-cold");
-        let eth_hot_key_alias = self.generate_random_alias("eth-hot");
-        let protocol_key = self.generate_random_alias("protocol");
+        let consensus_key_alias = self.generate_random_alias(&state.fuzzer, "consensus");
+        let eth_cold_key_alias = self.generate_random_alias(&state.fuzzer, "eth-cold");
+        let eth_hot_key_alias = self.generate_random_alias(&state.fuzzer, "eth-hot");
+        let protocol_key = self.generate_random_alias(&state.fuzzer, "protocol");
 
         let mut wallet = sdk.namada.wallet.write().await;
 
@@ -162,26 +172,14 @@ impl Task for TxBecomeValidator {
             .expect("unable to sign tx");
 
         let tx = sdk
-            .namada
             .submit(become_validator_tx.clone(), &become_validator_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&become_validator_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&become_validator_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&become_validator_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -210,40 +208,27 @@ pub struct BecomeValidatorParameters {
 impl TaskParam for BecomeValidatorParameters {
     type D = BecomeValidatorParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
-        };
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
 
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
+        };
         let commission_rate = match dto.commission_rate {
-            Value::Ref { .. } => unimplemented!(),
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => rand::thread_rng().gen_range(1..100),
+            Value::Fuzz { .. } => Some(
+                state
+                    .fuzzer
+                    .pick("commission_rate", |rng| rng.gen_range(1..100)),
+            ),
+            value => resolver.number("commission_rate", value)?,
+        };
+        let Some(commission_rate) = commission_rate else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             commission_rate,
-        })
+        }))
     }
 }
@@ -1,18 +1,22 @@
 use async_trait::async_trait;
-use namada_sdk::{args::Bond, error::TxSubmitError, signing::default_sign, token::Amount, Namada};
-use rand::Rng;
+use namada_sdk::{args::Bond, token::Amount, Namada};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
-    queries::validators::ValidatorsQueryStorageKeys,
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 
 pub enum TxBondStorageKeys {
     SourceAddress,
@@ -44,6 +48,14 @@ impl Task for TxBond {
     type P = TxBondParameters;
     type B = Bond;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxBondStorageKeys::SourceAddress, FieldType::Address),
+            OutputField::new(TxBondStorageKeys::ValidatorAddress, FieldType::Address),
+            OutputField::new(TxBondStorageKeys::Amount, FieldType::Amount),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -51,6 +63,16 @@ impl Task for TxBond {
         settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
         let amount = Amount::from(parameters.amount);
         let validator_address = parameters.validator.to_namada_address(sdk).await;
@@ -62,59 +84,29 @@ impl Task for TxBond {
 
         let bond_tx_builder = self.add_settings(sdk, bond_tx_builder, settings).await;
 
-        let (mut bond_tx, signing_data) = bond_tx_builder
+        let (bond_tx, signing_data) = bond_tx_builder
             .build(&sdk.namada)
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut bond_tx,
-                &bond_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-
-        let tx = sdk
-            .namada
-            .submit(bond_tx.clone(), &bond_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&bond_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&bond_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(
+        outputs.add(
             TxBondStorageKeys::ValidatorAddress.to_string(),
             validator_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxBondStorageKeys::SourceAddress.to_string(),
             source_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxBondStorageKeys::Amount.to_string(),
             amount.raw_amount().to_string(),
         );
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: bond_tx,
+            signing_data,
+            args: bond_tx_builder.tx,
+        }))
     }
 }
 
@@ -135,114 +127,31 @@ pub struct TxBondParameters {
 impl TaskParam for TxBondParameters {
     type D = TxBondParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
         let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { value } => {
-                let step_id = value.expect("Bond task requires fuzz for source to define the step id to a validator query step");
-                let total_validators = state
-                    .get_step_item(
-                        &step_id,
-                        ValidatorsQueryStorageKeys::TotalValidator
-                            .to_string()
-                            .as_str(),
-                    )
-                    .parse::<u64>()
-                    .unwrap();
-
-                let validator_idx = rand::thread_rng().gen_range(0..total_validators);
-
-                let validator_address = state.get_step_item(
-                    &step_id,
-                    ValidatorsQueryStorageKeys::Validator(validator_idx)
-                        .to_string()
-                        .as_str(),
-                );
-
-                AccountIndentifier::Address(validator_address)
-            }
+            Value::Fuzz { value } => resolver.random_validator("source", value)?,
+            value => resolver.account("source", value)?,
+        };
+        let Some(source) = source else {
+            return Ok(None);
         };
         let validator = match dto.validator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { value } => {
-                let step_id = value.expect("Bond task requires fuzz for source to define the step id to a validator query step");
-                let total_validators = state
-                    .get_step_item(
-                        &step_id,
-                        ValidatorsQueryStorageKeys::TotalValidator
-                            .to_string()
-                            .as_str(),
-                    )
-                    .parse::<u64>()
-                    .unwrap();
-
-                let validator_idx = rand::thread_rng().gen_range(0..total_validators);
-
-                let validator_address = state.get_step_item(
-                    &step_id,
-                    ValidatorsQueryStorageKeys::Validator(validator_idx)
-                        .to_string()
-                        .as_str(),
-                );
-
-                AccountIndentifier::Address(validator_address)
-            }
+            Value::Fuzz { value } => resolver.random_validator("validator", value)?,
+            value => resolver.account("validator", value)?,
+        };
+        let Some(validator) = validator else {
+            return Ok(None);
         };
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let amount = state.get_step_item(&value, &field);
-                amount.parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(amount) = resolver.amount("amount", dto.amount)? else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             validator,
             amount,
-        })
+        }))
     }
 }
@@ -1,22 +1,25 @@
 use async_trait::async_trait;
 use namada_sdk::{
     args::TxBuilder,
-    error::TxSubmitError,
     signing::{default_sign, SigningTxData},
     token,
-    tx::{self, data::GasLimit, Tx},
-    Namada, DEFAULT_GAS_LIMIT,
+    tx::{self, Tx},
+    Namada,
 };
-use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
-    queries::validators::ValidatorsQueryStorageKeys,
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -57,6 +60,16 @@ impl Task for TxBondBatch {
     type P = TxBondBatchParameters;
     type B = namada_sdk::args::Bond;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new("source-{}-address", FieldType::Address),
+            OutputField::new("validator-{}-address", FieldType::Address),
+            OutputField::new("amount-{}", FieldType::Amount),
+            OutputField::new(TxBondBatchStorageKeys::BatchSize, FieldType::Number),
+            OutputField::new(TxBondBatchStorageKeys::AtomicBatch, FieldType::Bool),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -116,11 +129,8 @@ impl Task for TxBondBatch {
             return Ok(StepResult::no_op());
         }
 
-        let tx_args = Self::default_tx_arg(sdk).await;
+        let tx_args = Self::default_tx_arg(sdk, &settings).await;
         let gas_payer = settings.clone().gas_payer.unwrap().to_public_key(sdk).await;
-        let tx_args = tx_args.gas_limit(GasLimit::from(
-            settings.clone().gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
-        ));
         let tx_args = tx_args.wrapper_fee_payer(gas_payer);
         let is_atomic = true;
 
@@ -135,23 +145,12 @@ impl Task for TxBondBatch {
                 .expect("unable to sign tx");
         }
 
-        let tx = sdk.namada.submit(batch_tx.clone(), &tx_args).await;
+        let tx = sdk.submit(batch_tx.clone(), &tx_args).await;
 
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&batch_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&batch_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&batch_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -184,125 +183,48 @@ pub struct TxBondBatchParameters {
 impl TaskParam for TxBondBatchParameters {
     type D = TxBondBatchParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        // entries referencing a failed step are dropped from the batch
         let batch_size = dto.sources.len();
         let batch = (0..batch_size)
-            .filter_map(|i| {
+            .map(|i| {
                 let source = match dto.sources[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
                     Value::Fuzz { value } => {
-                        let step_id = value.expect("Bond task requires fuzz for source to define the step id to a validator query step");
-                        let total_validators = state
-                            .get_step_item(
-                                &step_id,
-                                ValidatorsQueryStorageKeys::TotalValidator
-                                    .to_string()
-                                    .as_str(),
-                            )
-                            .parse::<u64>()
-                            .unwrap();
-
-                        let validator_idx = rand::thread_rng().gen_range(0..total_validators);
-
-                        let validator_address = state.get_step_item(
-                            &step_id,
-                            ValidatorsQueryStorageKeys::Validator(validator_idx)
-                                .to_string()
-                                .as_str(),
-                        );
-
-                        AccountIndentifier::Address(validator_address)
+                        resolver.random_validator(&format!("sources[{}]", i), value)?
                     }
+                    value => resolver.account(&format!("sources[{}]", i), value)?,
+                };
+                let Some(source) = source else {
+                    return Ok(None);
                 };
                 let target = match dto.targets[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
                     Value::Fuzz { value } => {
-                        let step_id = value.expect("Bond task requires fuzz for source to define the step id to a validator query step");
-                        let total_validators = state
-                            .get_step_item(
-                                &step_id,
-                                ValidatorsQueryStorageKeys::TotalValidator
-                                    .to_string()
-                                    .as_str(),
-                            )
-                            .parse::<u64>()
-                            .unwrap();
-
-                        let validator_idx = rand::thread_rng().gen_range(0..total_validators);
-
-                        let validator_address = state.get_step_item(
-                            &step_id,
-                            ValidatorsQueryStorageKeys::Validator(validator_idx)
-                                .to_string()
-                                .as_str(),
-                        );
-
-                        AccountIndentifier::Address(validator_address)
+                        resolver.random_validator(&format!("targets[{}]", i), value)?
                     }
+                    value => resolver.account(&format!("targets[{}]", i), value)?,
                 };
-                let amount = match dto.amounts[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let amount = state.get_step_item(&value, &field);
-                        amount.parse::<u64>().unwrap()
-                    }
-                    Value::Value { value } => value.parse::<u64>().unwrap(),
-                    Value::Fuzz { .. } => unimplemented!(),
+                let Some(target) = target else {
+                    return Ok(None);
+                };
+                let Some(amount) =
+                    resolver.amount(&format!("amounts[{}]", i), dto.amounts[i].clone())?
+                else {
+                    return Ok(None);
                 };
 
-                Some((source, target, amount))
+                Ok(Some((source, target, amount)))
             })
-            .collect::<Vec<(
-                AccountIndentifier,
-                AccountIndentifier,
-                u64,
-            )>>();
+            .collect::<Result<Vec<_>, ParameterError>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<(AccountIndentifier, AccountIndentifier, u64)>>();
 
-        Some(Self {
+        Ok(Some(Self {
             sources: batch.iter().map(|t| t.0.clone()).collect(),
             targets: batch.iter().map(|t| t.1.clone()).collect(),
             amounts: batch.iter().map(|t| t.2).collect(),
-        })
+        }))
     }
 }
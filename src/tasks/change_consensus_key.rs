@@ -2,22 +2,27 @@ use async_trait::async_trait;
 
 use namada_sdk::{
     args::ConsensusKeyChange,
-    error::TxSubmitError,
     key::{RefTo, SchemeType},
     signing::default_sign,
     Namada,
 };
 
-use rand::{distributions::Alphanumeric, Rng};
 use rand_core::OsRng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        fuzz::Fuzzer,
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -50,14 +55,11 @@ impl TxChangeConsensusKey {
 }
 
 impl TxChangeConsensusKey {
-    pub fn generate_random_alias(&self, namespace: &str) -> String {
-        let random_suffix: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(5)
-            .map(char::from)
-            .collect();
-
-        format!("lt-addr-{}-{}", namespace, random_suffix)
+    pub fn generate_random_alias(&self, fuzzer: &Fuzzer, namespace: &str) -> String {
+        fuzzer.alias(
+            &format!("{}-key-alias", namespace),
+            &format!("lt-addr-{}", namespace),
+        )
     }
 }
 
@@ -66,16 +68,37 @@ impl Task for TxChangeConsensusKey {
     type P = TxChangeConsensusKeyParameters;
     type B = ConsensusKeyChange;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(
+                TxChangeConsensusKeyStorageKeys::ValidatorAlias,
+                FieldType::Alias,
+            ),
+            OutputField::new(
+                TxChangeConsensusKeyStorageKeys::ValidatorAddress,
+                FieldType::Address,
+            ),
+            OutputField::new(
+                TxChangeConsensusKeyStorageKeys::ConsensusPrivateKey,
+                FieldType::PrivateKey,
+            ),
+            OutputField::new(
+                TxChangeConsensusKeyStorageKeys::ConsensusPublicKey,
+                FieldType::PublicKey,
+            ),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
         parameters: Self::P,
         settings: TxSettings,
-        _state: &Storage,
+        state: &Storage,
     ) -> Result<StepResult, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
 
-        let consensus_key_alias = self.generate_random_alias("consensus");
+        let consensus_key_alias = self.generate_random_alias(&state.fuzzer, "consensus");
 
         let mut wallet = sdk.namada.wallet.write().await;
 
@@ -119,7 +142,6 @@ impl Task for TxChangeConsensusKey {
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
         let tx = sdk
-            .namada
             .submit(
                 change_consensus_key_tx.clone(),
                 &change_consensus_key_tx_builder.tx,
@@ -129,20 +151,8 @@ impl Task for TxChangeConsensusKey {
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&change_consensus_key_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors =
-                        Self::get_tx_errors(&change_consensus_key_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&change_consensus_key_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -181,31 +191,13 @@ pub struct TxChangeConsensusKeyParameters {
 impl TaskParam for TxChangeConsensusKeyParameters {
     type D = TxChangeConsensusKeyParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
 
-        Some(Self { source })
+        Ok(Some(Self { source }))
     }
 }
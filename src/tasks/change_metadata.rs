@@ -6,15 +6,21 @@ use fake::{
     },
     Fake,
 };
-use namada_sdk::{args::MetaDataChange, error::TxSubmitError, signing::default_sign, Namada};
+use namada_sdk::{args::MetaDataChange, signing::default_sign, Namada};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -45,6 +51,13 @@ impl Task for TxChangeMetadata {
     type P = TxChangeMetadataParameters;
     type B = MetaDataChange;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![OutputField::new(
+            TxChangeMetadataStorageKeys::ValidatorAddress,
+            FieldType::Address,
+        )])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -85,26 +98,14 @@ impl Task for TxChangeMetadata {
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
         let tx = sdk
-            .namada
             .submit(metadata_tx.clone(), &metadata_change_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&metadata_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&metadata_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&metadata_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -140,74 +141,71 @@ pub struct TxChangeMetadataParameters {
 impl TaskParam for TxChangeMetadataParameters {
     type D = TxChangeMetadataParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { value: _ } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
         let email = match dto.email {
-            Some(Value::Ref { .. }) => unimplemented!(),
-            Some(Value::Value { value }) => value,
-            Some(Value::Fuzz { .. }) => FreeEmail().fake(),
-            _ => "".to_string(),
+            Some(Value::Fuzz { .. }) => Some(
+                state
+                    .fuzzer
+                    .pick("email", |rng| FreeEmail().fake_with_rng::<String, _>(rng)),
+            ),
+            value => resolver
+                .optional("email", value, Resolver::string)?
+                .map(Option::unwrap_or_default),
         };
         let avatar = match dto.avatar {
-            Some(Value::Ref { .. }) => unimplemented!(),
-            Some(Value::Value { value }) => value,
-            Some(Value::Fuzz { .. }) => Username().fake(),
-            _ => "".to_string(),
+            Some(Value::Fuzz { .. }) => Some(
+                state
+                    .fuzzer
+                    .pick("avatar", |rng| Username().fake_with_rng::<String, _>(rng)),
+            ),
+            value => resolver
+                .optional("avatar", value, Resolver::string)?
+                .map(Option::unwrap_or_default),
         };
         let description = match dto.description {
-            Some(Value::Ref { .. }) => unimplemented!(),
-            Some(Value::Value { value }) => value,
-            Some(Value::Fuzz { .. }) => {
-                let words: Vec<String> = Words(0..20).fake();
+            Some(Value::Fuzz { .. }) => Some(state.fuzzer.pick("description", |rng| {
+                let words: Vec<String> = Words(0..20).fake_with_rng(rng);
                 words.join(" ")
-            }
-            _ => "".to_string(),
+            })),
+            value => resolver
+                .optional("description", value, Resolver::string)?
+                .map(Option::unwrap_or_default),
         };
         let discord_handle = match dto.discord_handle {
-            Some(Value::Ref { .. }) => unimplemented!(),
-            Some(Value::Value { value }) => value,
-            Some(Value::Fuzz { .. }) => Username().fake(),
-            _ => "".to_string(),
+            Some(Value::Fuzz { .. }) => Some(state.fuzzer.pick("discord_handle", |rng| {
+                Username().fake_with_rng::<String, _>(rng)
+            })),
+            value => resolver
+                .optional("discord_handle", value, Resolver::string)?
+                .map(Option::unwrap_or_default),
         };
         let website = match dto.website {
-            Some(Value::Ref { .. }) => unimplemented!(),
-            Some(Value::Value { value }) => value,
-            Some(Value::Fuzz { .. }) => {
-                let words: Vec<String> = Words(0..5).fake();
+            Some(Value::Fuzz { .. }) => Some(state.fuzzer.pick("website", |rng| {
+                let words: Vec<String> = Words(0..5).fake_with_rng(rng);
                 words.join(" ")
-            }
-            _ => "".to_string(),
+            })),
+            value => resolver
+                .optional("website", value, Resolver::string)?
+                .map(Option::unwrap_or_default),
+        };
+        let (Some(email), Some(avatar), Some(description), Some(discord_handle), Some(website)) =
+            (email, avatar, description, discord_handle, website)
+        else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             email,
             avatar,
             description,
             discord_handle,
             website,
-        })
+        }))
     }
 }
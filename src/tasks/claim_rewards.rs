@@ -1,14 +1,20 @@
 use async_trait::async_trait;
-use namada_sdk::{args::ClaimRewards, error::TxSubmitError, signing::default_sign, Namada};
+use namada_sdk::{args::ClaimRewards, Namada};
 use serde::{Deserialize, Serialize};
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 pub enum TxClaimRewardsStorageKeys {
@@ -39,6 +45,19 @@ impl Task for TxClaimRewards {
     type P = TxClaimRewardsteParameters;
     type B = ClaimRewards;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(
+                TxClaimRewardsStorageKeys::ValidatorAddress,
+                FieldType::Address,
+            ),
+            OutputField::new(
+                TxClaimRewardsStorageKeys::DelegatorAddress,
+                FieldType::Address,
+            ),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -46,6 +65,16 @@ impl Task for TxClaimRewards {
         settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         let validator = parameters.source.to_namada_address(sdk).await;
         let delegator = parameters.delegator.to_namada_address(sdk).await;
 
@@ -56,54 +85,25 @@ impl Task for TxClaimRewards {
             .add_settings(sdk, claim_rewards_tx_builder, settings)
             .await;
 
-        let (mut claim_reward_tx, signing_data) = claim_rewards_tx_builder
+        let (claim_reward_tx, signing_data) = claim_rewards_tx_builder
             .build(&sdk.namada)
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut claim_reward_tx,
-                &claim_rewards_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-        let tx = sdk
-            .namada
-            .submit(claim_reward_tx.clone(), &claim_rewards_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&claim_reward_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&claim_reward_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(
+        outputs.add(
             TxClaimRewardsStorageKeys::ValidatorAddress.to_string(),
             validator.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxClaimRewardsStorageKeys::DelegatorAddress.to_string(),
             delegator.to_string(),
         );
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: claim_reward_tx,
+            signing_data,
+            args: claim_rewards_tx_builder.tx,
+        }))
     }
 }
 
@@ -122,54 +122,16 @@ pub struct TxClaimRewardsteParameters {
 impl TaskParam for TxClaimRewardsteParameters {
     type D = TxClaimRewardsteParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
-        let delegator = match dto.delegator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(delegator) = resolver.account("delegator", dto.delegator)? else {
+            return Ok(None);
         };
 
-        Some(Self { source, delegator })
+        Ok(Some(Self { source, delegator }))
     }
 }
@@ -1,18 +1,23 @@
 use async_trait::async_trait;
 
 use namada_sdk::{
-    args::TxDeactivateValidator as SdkDeactivateValidatorTx, error::TxSubmitError,
-    signing::default_sign, Namada,
+    args::TxDeactivateValidator as SdkDeactivateValidatorTx, signing::default_sign, Namada,
 };
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -43,6 +48,13 @@ impl Task for TxDeactivateValidator {
     type P = DeactivateValidatorParameters;
     type B = SdkDeactivateValidatorTx;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![OutputField::new(
+            TxDeactivateValidatorStorageKeys::ValidatorAddress,
+            FieldType::Address,
+        )])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -76,7 +88,6 @@ impl Task for TxDeactivateValidator {
             .expect("unable to sign tx");
 
         let tx = sdk
-            .namada
             .submit(
                 deactivate_validator_tx.clone(),
                 &deactivate_validator_tx_builder.tx,
@@ -86,20 +97,8 @@ impl Task for TxDeactivateValidator {
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&deactivate_validator_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors =
-                        Self::get_tx_errors(&deactivate_validator_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&deactivate_validator_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -126,31 +125,13 @@ pub struct DeactivateValidatorParameters {
 impl TaskParam for DeactivateValidatorParameters {
     type D = DeactivateValidatorParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
 
-        Some(Self { source })
+        Ok(Some(Self { source }))
     }
 }
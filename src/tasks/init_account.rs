@@ -1,14 +1,22 @@
 use async_trait::async_trait;
-use rand::{distributions::Alphanumeric, Rng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StateAddress, StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StateAddress, StepStorage, Storage},
+    },
+    utils::{
+        fuzz::Fuzzer,
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
+use namada_sdk::tx::{either, ProcessTxResponse};
 use namada_sdk::{args::TxBuilder, Namada};
 use namada_sdk::{args::TxInitAccount as SdkInitAccountTx, signing::default_sign};
 
@@ -46,14 +54,8 @@ impl TxInitAccount {
 }
 
 impl TxInitAccount {
-    pub fn generate_random_alias() -> String {
-        let random_suffix: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(5)
-            .map(char::from)
-            .collect();
-
-        format!("lt-acc-enst-{}", random_suffix)
+    pub fn generate_random_alias(fuzzer: &Fuzzer) -> String {
+        fuzzer.alias("alias", "lt-acc-enst")
     }
 }
 
@@ -62,6 +64,16 @@ impl Task for TxInitAccount {
     type P = TxInitAccountParameters;
     type B = SdkInitAccountTx;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxInitAccountStorageKeys::Alias, FieldType::Alias),
+            OutputField::new(TxInitAccountStorageKeys::Address, FieldType::Address),
+            OutputField::new(TxInitAccountStorageKeys::Threshold, FieldType::Number),
+            OutputField::new(TxInitAccountStorageKeys::TotalPublicKeys, FieldType::Number),
+            OutputField::new("public_key_at_index-{}", FieldType::PublicKey),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -103,38 +115,35 @@ impl Task for TxInitAccount {
             .await
             .expect("unable to sign tx");
         let tx_submission = sdk
-            .namada
             .submit(init_account_tx.clone(), &init_account_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
+        if let Some(result) = Self::tx_result(&init_account_tx, &tx_submission, &mut storage)? {
+            return Ok(result);
+        }
+
         let cmt = init_account_tx.first_commitments().unwrap().to_owned();
         let wrapper_hash = init_account_tx.wrapper_hash();
 
-        let account_address = match tx_submission {
-            Ok(process_tx_response) => {
-                match process_tx_response.is_applied_and_valid(wrapper_hash.as_ref(), &cmt) {
-                    Some(tx_result) => {
-                        if let Some(account) = tx_result.initialized_accounts.first() {
-                            account.clone()
-                        } else {
-                            let log = Self::get_tx_errors(&init_account_tx, &process_tx_response)
-                                .unwrap_or_default();
-                            return Ok(StepResult::fail(log));
-                        }
-                    }
-                    None => {
-                        let log = Self::get_tx_errors(&init_account_tx, &process_tx_response)
-                            .unwrap_or_default();
-                        return Ok(StepResult::fail(log));
-                    }
-                }
-            }
-            Err(e) => {
-                return Ok(StepResult::fail(e.to_string()));
-            }
+        let account_address = tx_submission
+            .as_ref()
+            .ok()
+            .and_then(|process_tx_response| match process_tx_response {
+                // a dry-run tx is never applied
+                ProcessTxResponse::DryRun(result) => result
+                    .get_inner_tx_result(wrapper_hash.as_ref(), either::Right(&cmt))
+                    .and_then(|tx_result| tx_result.as_ref().ok()),
+                _ => process_tx_response.is_applied_and_valid(wrapper_hash.as_ref(), &cmt),
+            })
+            .and_then(|tx_result| tx_result.initialized_accounts.first().cloned());
+        let Some(account_address) = account_address else {
+            return Ok(StepResult::fail_with_data(
+                "no account was initialized".to_string(),
+                storage,
+            ));
         };
 
         storage.add(
@@ -201,51 +210,36 @@ pub struct TxInitAccountParameters {
 impl TaskParam for TxInitAccountParameters {
     type D = TxInitAccountParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
         let alias = match dto.alias {
-            Value::Ref { .. } => unimplemented!(),
-            Value::Value { value } => value.to_string(),
-            Value::Fuzz { .. } => TxInitAccount::generate_random_alias(),
+            Value::Fuzz { .. } => Some(TxInitAccount::generate_random_alias(&state.fuzzer)),
+            value => resolver.string("alias", value)?,
+        };
+        let Some(alias) = alias else {
+            return Ok(None);
+        };
+        let Some(sources) = resolver.list("sources", dto.sources, Resolver::account)? else {
+            return Ok(None);
         };
-        let sources = dto
-            .sources
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { value, field } => {
-                    let data = state.get_step_item(&value, &field);
-                    match field.to_lowercase().as_str() {
-                        "alias" => AccountIndentifier::Alias(data),
-                        "public-key" => AccountIndentifier::PublicKey(data),
-                        "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                        _ => AccountIndentifier::Address(data),
-                    }
-                }
-                Value::Value { value } => {
-                    if value.starts_with(ADDRESS_PREFIX) {
-                        AccountIndentifier::Address(value)
-                    } else {
-                        AccountIndentifier::Alias(value)
-                    }
-                }
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect::<Vec<AccountIndentifier>>();
-
         let threshold = match dto.threshold {
-            Some(value) => match value {
-                Value::Ref { .. } => unimplemented!(),
-                Value::Value { value } => value
-                    .parse::<u64>()
-                    .expect("Should be convertiable to u64."),
-                Value::Fuzz { .. } => rand::thread_rng().gen_range(1..=sources.len()) as u64,
-            },
-            None => 1u64,
+            Some(Value::Fuzz { .. }) => Some(
+                state
+                    .fuzzer
+                    .pick("threshold", |rng| rng.gen_range(1..=sources.len()) as u64),
+            ),
+            Some(value) => resolver.number("threshold", value)?,
+            None => Some(1u64),
+        };
+        let Some(threshold) = threshold else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             alias,
             sources,
             threshold,
-        })
+        }))
     }
 }
@@ -3,7 +3,6 @@ use std::collections::BTreeMap;
 use async_trait::async_trait;
 use namada_sdk::{
     args::InitProposal,
-    error::TxSubmitError,
     governance::{
         cli::onchain::{OnChainProposal, PgfFunding, PgfFundingProposal},
         storage::{
@@ -19,11 +18,17 @@ use namada_sdk::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -68,6 +73,39 @@ impl Task for TxInitPgfFundingProposal {
     type P = TxInitPgfFundingProposalParameters;
     type B = InitProposal;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::ProposalId,
+                FieldType::Number,
+            ),
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::StartEpoch,
+                FieldType::Epoch,
+            ),
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::EndEpoch,
+                FieldType::Epoch,
+            ),
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::GraceEpoch,
+                FieldType::Epoch,
+            ),
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::ProposerAddress,
+                FieldType::Address,
+            ),
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::ContinousPgf,
+                FieldType::Json,
+            ),
+            OutputField::new(
+                TxInitPgfFundingProposalStorageKeys::RetroPgf,
+                FieldType::Json,
+            ),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -178,26 +216,14 @@ impl Task for TxInitPgfFundingProposal {
             .await
             .expect("unable to sign tx");
         let tx = sdk
-            .namada
             .submit(init_proposal_tx.clone(), &init_proposal_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&init_proposal_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&init_proposal_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&init_proposal_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         let storage_key = get_counter_key();
@@ -269,110 +295,67 @@ pub struct TxInitPgfFundingProposalParameters {
 impl TaskParam for TxInitPgfFundingProposalParameters {
     type D = TxInitPgfFundingProposalParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let continous_funding_target = dto
-            .continous_funding_target
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { value, field } => {
-                    let data = state.get_step_item(&value, &field);
-                    match field.to_lowercase().as_str() {
-                        "alias" => AccountIndentifier::Alias(data),
-                        "public-key" => AccountIndentifier::PublicKey(data),
-                        "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                        _ => AccountIndentifier::Address(data),
-                    }
-                }
-                Value::Value { value } => {
-                    if value.starts_with(ADDRESS_PREFIX) {
-                        AccountIndentifier::Address(value)
-                    } else {
-                        AccountIndentifier::Alias(value)
-                    }
-                }
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect::<Vec<AccountIndentifier>>();
-        let retro_funding_target = dto
-            .retro_funding_target
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { value, field } => {
-                    let data = state.get_step_item(&value, &field);
-                    match field.to_lowercase().as_str() {
-                        "alias" => AccountIndentifier::Alias(data),
-                        "public-key" => AccountIndentifier::PublicKey(data),
-                        "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                        _ => AccountIndentifier::Address(data),
-                    }
-                }
-                Value::Value { value } => {
-                    if value.starts_with(ADDRESS_PREFIX) {
-                        AccountIndentifier::Address(value)
-                    } else {
-                        AccountIndentifier::Alias(value)
-                    }
-                }
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect::<Vec<AccountIndentifier>>();
-        let continous_funding_amount = dto
-            .continous_funding_amount
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { .. } => unimplemented!(),
-                Value::Value { value } => value.parse::<u64>().unwrap(),
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect();
-        let retro_funding_amount = dto
-            .retro_funding_amount
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { .. } => unimplemented!(),
-                Value::Value { value } => value.parse::<u64>().unwrap(),
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect();
-        let signer = match dto.signer {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(signer) = resolver.account("signer", dto.signer)? else {
+            return Ok(None);
         };
-        let start_epoch = dto.start_epoch.map(|start_epoch| match start_epoch {
-            Value::Ref { value: _, field: _ } => {
-                unimplemented!() // can't refertence a past epoch as end epoch
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        let end_epoch = dto.end_epoch.map(|end_epoch| match end_epoch {
-            Value::Ref { value: _, field: _ } => {
-                unimplemented!() // can't refertence a past epoch as end epoch
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        let grace_epoch = dto.grace_epoch.map(|grace_epoch| match grace_epoch {
-            Value::Ref { value: _, field: _ } => unimplemented!(), // can't refertence a past epoch as grace epoch
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        Some(Self {
+        let Some(continous_funding_target) = resolver.list(
+            "continous_funding_target",
+            dto.continous_funding_target,
+            Resolver::account,
+        )?
+        else {
+            return Ok(None);
+        };
+        let Some(retro_funding_target) = resolver.list(
+            "retro_funding_target",
+            dto.retro_funding_target,
+            Resolver::account,
+        )?
+        else {
+            return Ok(None);
+        };
+        let Some(continous_funding_amount) = resolver.list(
+            "continous_funding_amount",
+            dto.continous_funding_amount,
+            Resolver::amount,
+        )?
+        else {
+            return Ok(None);
+        };
+        let Some(retro_funding_amount) = resolver.list(
+            "retro_funding_amount",
+            dto.retro_funding_amount,
+            Resolver::amount,
+        )?
+        else {
+            return Ok(None);
+        };
+        let start_epoch = match dto.start_epoch {
+            // can't reference a past epoch as start epoch
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("start_epoch")),
+            value => resolver
+                .optional("start_epoch", value, Resolver::epoch)?
+                .flatten(),
+        };
+        let end_epoch = match dto.end_epoch {
+            // can't reference a past epoch as end epoch
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("end_epoch")),
+            value => resolver
+                .optional("end_epoch", value, Resolver::epoch)?
+                .flatten(),
+        };
+        let grace_epoch = match dto.grace_epoch {
+            // can't reference a past epoch as grace epoch
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("grace_epoch")),
+            value => resolver
+                .optional("grace_epoch", value, Resolver::epoch)?
+                .flatten(),
+        };
+
+        Ok(Some(Self {
             signer,
             start_epoch,
             end_epoch,
@@ -381,6 +364,6 @@ impl TaskParam for TxInitPgfFundingProposalParameters {
             retro_funding_target,
             continous_funding_amount,
             retro_funding_amount,
-        })
+        }))
     }
 }
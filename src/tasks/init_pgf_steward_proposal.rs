@@ -3,7 +3,6 @@ use std::collections::BTreeMap;
 use async_trait::async_trait;
 use namada_sdk::{
     args::InitProposal,
-    error::TxSubmitError,
     governance::{
         cli::onchain::{OnChainProposal, PgfStewardProposal, StewardsUpdate},
         storage::keys::get_counter_key,
@@ -16,11 +15,17 @@ use namada_sdk::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -67,6 +72,39 @@ impl Task for TxInitPgfStewardProposal {
     type P = TxInitPgfStewardProposalParameters;
     type B = InitProposal;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::ProposalId,
+                FieldType::Number,
+            ),
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::StartEpoch,
+                FieldType::Epoch,
+            ),
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::EndEpoch,
+                FieldType::Epoch,
+            ),
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::GraceEpoch,
+                FieldType::Epoch,
+            ),
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::ProposerAddress,
+                FieldType::Address,
+            ),
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::StewardAdd,
+                FieldType::Address,
+            ),
+            OutputField::new(
+                TxInitPgfStewardProposalStorageKeys::StewardRemove,
+                FieldType::Json,
+            ),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -147,26 +185,14 @@ impl Task for TxInitPgfStewardProposal {
             .expect("unable to sign tx");
 
         let tx = sdk
-            .namada
             .submit(init_proposal_tx.clone(), &init_proposal_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&init_proposal_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&init_proposal_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&init_proposal_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         let storage_key = get_counter_key();
@@ -232,78 +258,45 @@ pub struct TxInitPgfStewardProposalParameters {
 impl TaskParam for TxInitPgfStewardProposalParameters {
     type D = TxInitPgfStewardProposalParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let steward_remove = dto
-            .steward_remove
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { value, field } => {
-                    let data = state.get_step_item(&value, &field);
-                    match field.to_lowercase().as_str() {
-                        "alias" => AccountIndentifier::Alias(data),
-                        "public-key" => AccountIndentifier::PublicKey(data),
-                        "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                        _ => AccountIndentifier::Address(data),
-                    }
-                }
-                Value::Value { value } => {
-                    if value.starts_with(ADDRESS_PREFIX) {
-                        AccountIndentifier::Address(value)
-                    } else {
-                        AccountIndentifier::Alias(value)
-                    }
-                }
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect::<Vec<AccountIndentifier>>();
-        let signer = match dto.signer {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(signer) = resolver.account("signer", dto.signer)? else {
+            return Ok(None);
         };
-        let start_epoch = dto.start_epoch.map(|start_epoch| match start_epoch {
-            Value::Ref { value: _, field: _ } => {
-                unimplemented!() // can't refertence a past epoch as end epoch
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        let end_epoch = dto.end_epoch.map(|end_epoch| match end_epoch {
-            Value::Ref { value: _, field: _ } => {
-                unimplemented!() // can't refertence a past epoch as end epoch
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        let grace_epoch = dto.grace_epoch.map(|grace_epoch| match grace_epoch {
-            Value::Ref { value: _, field: _ } => unimplemented!(), // can't refertence a past epoch as grace epoch
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        Some(Self {
+        let Some(steward_remove) =
+            resolver.list("steward_remove", dto.steward_remove, Resolver::account)?
+        else {
+            return Ok(None);
+        };
+        let start_epoch = match dto.start_epoch {
+            // can't reference a past epoch as start epoch
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("start_epoch")),
+            value => resolver
+                .optional("start_epoch", value, Resolver::epoch)?
+                .flatten(),
+        };
+        let end_epoch = match dto.end_epoch {
+            // can't reference a past epoch as end epoch
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("end_epoch")),
+            value => resolver
+                .optional("end_epoch", value, Resolver::epoch)?
+                .flatten(),
+        };
+        let grace_epoch = match dto.grace_epoch {
+            // can't reference a past epoch as grace epoch
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("grace_epoch")),
+            value => resolver
+                .optional("grace_epoch", value, Resolver::epoch)?
+                .flatten(),
+        };
+
+        Ok(Some(Self {
             signer,
             start_epoch,
             end_epoch,
             grace_epoch,
             steward_remove,
-        })
+        }))
     }
 }
@@ -2,25 +2,37 @@ use std::{path::PathBuf, str::FromStr};
 
 use async_trait::async_trait;
 use namada_sdk::{
+    address::Address,
     args::{self, DeviceTransport, SdkTypes, TxBuilder},
+    error::TxSubmitError,
     rpc::{self},
+    signing::{default_sign, SigningTxData},
     state::Epoch,
+    token::Denomination,
     tx::{data::GasLimit, either, ProcessTxResponse, Tx, TX_REVEAL_PK},
     Namada, DEFAULT_GAS_LIMIT,
 };
+use tendermint_rpc::error::ErrorDetail;
 use thiserror::Error;
+use tracing::{debug, error, warn};
 
 use crate::{
-    entity::address::AccountIndentifier,
+    metrics::metrics,
+    policy::FailureKind,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
+    state::{
+        schema::OutputSchema,
+        state::{StepStorage, Storage},
+    },
     utils::{
-        settings::{TxSettings, TxSettingsDto},
-        value::Value,
+        settings::{Expiration, Fee, TxSettings, TxSettingsDto},
+        value::{ParameterError, Resolved, Resolver},
     },
 };
 
+use self::tx_info::{InnerTxResult, TxStorageKeys};
+
 pub mod become_validator;
 pub mod bond;
 pub mod bond_batch;
@@ -48,22 +60,36 @@ pub mod vote;
 pub mod wallet_new_key;
 pub mod withdraw;
 pub mod tx_shielded_transfer_batch;
+pub mod tx_batch;
+pub mod tx_info;
 
 #[derive(Error, Debug)]
 pub enum TaskError {
+    /// The tx was submitted but not applied in time, with what was recorded
+    /// about it so far, e.g. its hashes for a `wait-tx` step
     #[error("error waiting for timeout")]
-    Timeout,
+    Timeout(StepStorage),
     #[error("error building tx `{0}`")]
     Build(String),
     #[error("error fetching shielded context data `{0}`")]
     ShieldedSync(String),
 }
 
+/// A tx built by `Task::build_tx`, not signed yet.
+pub struct BuiltTx {
+    pub tx: Tx,
+    pub signing_data: SigningTxData,
+    /// the args of the builder, with the settings of the step
+    pub args: args::Tx,
+}
+
 #[async_trait(?Send)]
 pub trait Task {
     type P: TaskParam;
     type B: TxBuilder<SdkTypes>;
 
+    fn outputs() -> OutputSchema;
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -72,24 +98,77 @@ pub trait Task {
         state: &Storage,
     ) -> Result<StepResult, TaskError>;
 
+    /// Builds the tx of the step without signing or submitting it, and writes
+    /// the outputs of the step to `outputs`. `None` if the step is a no-op.
+    /// Only the tasks that can be an entry of a `tx-batch` implement it.
+    async fn build_tx(
+        &self,
+        _sdk: &Sdk,
+        _parameters: Self::P,
+        _settings: TxSettings,
+        _outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
+        Err(TaskError::Build("the tx can't be batched".to_string()))
+    }
+
+    /// Builds, signs and submits the tx of a task implementing `build_tx`,
+    /// the step succeeds with the outputs of `build_tx` once the tx is applied.
+    async fn build_and_submit(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+    ) -> Result<StepResult, TaskError> {
+        let mut outputs = StepStorage::default();
+        let built = self
+            .build_tx(sdk, parameters, settings, &mut outputs)
+            .await?;
+        let Some(BuiltTx {
+            mut tx,
+            signing_data,
+            args,
+        }) = built
+        else {
+            return Ok(StepResult::no_op());
+        };
+
+        sdk.namada
+            .sign(&mut tx, &args, signing_data, default_sign, ())
+            .await
+            .expect("unable to sign tx");
+
+        let tx_response = sdk.submit(tx.clone(), &args).await;
+
+        let mut storage = StepStorage::default();
+        self.fetch_info(sdk, &mut storage).await;
+
+        if let Some(result) = Self::tx_result(&tx, &tx_response, &mut storage)? {
+            return Ok(result);
+        }
+
+        storage.storage.extend(outputs.storage);
+        Ok(StepResult::success(storage))
+    }
+
     async fn fetch_info(&self, sdk: &Sdk, step_storage: &mut StepStorage) {
         let block_height = match rpc::query_block(&sdk.namada.clone_client()).await {
-            Ok(Some(block)) => block.height.to_string(),
+            Ok(Some(block)) => block.height.0,
             Err(e) => {
-                println!("{}", e);
-                0.to_string()
+                warn!(error = %e, "Can't query the block height");
+                0
             }
-            _ => 0.to_string(),
+            _ => 0,
         };
 
         let epoch = match rpc::query_epoch(&sdk.namada.clone_client()).await {
             Ok(res) => res,
             Err(e) => {
-                println!("{}", e);
+                warn!(error = %e, "Can't query the epoch");
                 Epoch(0)
             }
         };
 
+        metrics().record_chain_info(block_height, epoch.0);
         step_storage.add("epoch".to_string(), epoch.to_string());
         step_storage.add("height".to_string(), block_height.to_string());
     }
@@ -100,38 +179,57 @@ pub trait Task {
         dto: <<Self as Task>::P as TaskParam>::D,
         settings_dto: Option<TxSettingsDto>,
         state: &Storage,
-    ) -> StepResult {
-        let parameters = if let Some(parameters) = Self::P::parameter_from_dto(dto, state) {
+        expect: bool,
+    ) -> Result<StepResult, ParameterError> {
+        let parameters = if let Some(parameters) = Self::P::parameter_from_dto(dto, state)? {
             parameters
         } else {
-            return StepResult::no_op();
+            return Ok(StepResult::no_op());
+        };
+        let Some(settings) = Self::P::settings_from_dto(settings_dto, state)? else {
+            return Ok(StepResult::no_op());
         };
-        let settings = Self::P::settings_from_dto(settings_dto, state);
 
         match self.execute(sdk, parameters, settings, state).await {
-            Ok(step_result) => step_result,
+            Ok(step_result) => Ok(step_result),
             Err(e) => {
                 match e {
+                    // the SDK refused the tx, e.g. because of its fee, which is
+                    // what a step with an `expect` may assert
+                    TaskError::Build(e) if expect => {
+                        return Ok(StepResult::fail_with_kind(
+                            FailureKind::WrapperRejection,
+                            e,
+                            StepStorage::default(),
+                        ));
+                    }
                     TaskError::Build(e) => {
-                        println!("tx build error: {e}");
+                        error!(error = %e, "Can't build tx");
                     }
                     TaskError::ShieldedSync(e) => {
-                        println!("shielded sync error: {e}");
+                        error!(error = %e, "Can't sync the shielded context");
                     }
-                    TaskError::Timeout => {
-                        println!("timeout waiting for tx to be applied");
+                    TaskError::Timeout(storage) => {
+                        warn!("Timeout waiting for tx to be applied");
+                        return Ok(StepResult::fail_with_kind(
+                            FailureKind::Timeout,
+                            "timeout waiting for tx to be applied".to_string(),
+                            storage,
+                        ));
                     }
                 }
-                StepResult::no_op()
+                Ok(StepResult::no_op())
             }
         }
     }
 
     async fn add_settings(&self, sdk: &Sdk, builder: Self::B, settings: TxSettings) -> Self::B {
-        let builder = if let Some(signers) = settings.signers {
-            if signers.is_empty() {
-                return builder;
-            }
+        let gas_token = Self::gas_token(sdk, &settings).await;
+        let signers = settings.signers.clone().unwrap_or_default();
+        // without signers the builder signs with the default keys
+        let builder = if signers.is_empty() {
+            builder
+        } else {
             let mut signing_keys = vec![];
             for signer in signers {
                 let public_key = signer.to_public_key(sdk).await;
@@ -139,19 +237,24 @@ pub trait Task {
             }
             let builder = builder.signing_keys(signing_keys.clone());
             builder.wrapper_fee_payer(signing_keys.first().unwrap().clone())
-        } else {
-            builder
         };
-        let builder = builder.tx(|x| args::Tx {
-            memo: Some(
-                "Tx sent from scenario tester"
-                    .to_string()
-                    .as_bytes()
-                    .to_vec(),
-            ),
-            ..x
+        let builder = builder.gas_limit(GasLimit::from(
+            settings.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
+        ));
+        let builder = builder.tx(|x| {
+            settings.apply(
+                args::Tx {
+                    memo: Some(
+                        "Tx sent from scenario tester"
+                            .to_string()
+                            .as_bytes()
+                            .to_vec(),
+                    ),
+                    ..x
+                },
+                gas_token,
+            )
         });
-        let builder = builder.gas_limit(GasLimit::from(settings.gas_limit.unwrap_or(300000)));
 
         if let Some(account) = settings.gas_payer {
             let public_key = account.to_public_key(sdk).await;
@@ -161,65 +264,164 @@ pub trait Task {
         }
     }
 
-    // we could do this better by returning all the errors an not just the first one we see
-    fn get_tx_errors(tx: &Tx, tx_response: &ProcessTxResponse) -> Option<String> {
-        let wrapper_hash = tx.wrapper_hash();
-        for cmt in tx.header.batch.clone() {
-            match tx_response {
-                ProcessTxResponse::Applied(result) => match &result.batch {
-                    Some(batch) => {
-                        println!("batch result: {:#?}", batch);
-                        match batch.get_inner_tx_result(wrapper_hash.as_ref(), either::Right(&cmt))
-                        {
-                            Some(Ok(res)) => {
-                                let errors = res.vps_result.errors.clone();
-                                let _status_flag = res.vps_result.status_flags;
-                                let _rejected_vps = res.vps_result.rejected_vps.clone();
-                                return Some(serde_json::to_string(&errors).unwrap());
-                            }
-                            Some(Err(e)) => return Some(e.to_string()),
-                            _ => (),
-                        }
-                    }
-                    None => (),
-                },
-                _ => (),
-            }
-        }
-        None
+    /// Records what happened to `tx` in `storage` under the keys of
+    /// `TxStorageKeys`: its hashes, the gas it used, the height it was applied
+    /// at and the result of each of its inner txs. If an inner tx wasn't
+    /// applied, returns the failed result of the step. A tx that was only
+    /// broadcast succeeds once the mempool accepted it.
+    fn tx_result(
+        tx: &Tx,
+        tx_response: &Result<ProcessTxResponse, namada_sdk::error::Error>,
+        storage: &mut StepStorage,
+    ) -> Result<Option<StepResult>, TaskError> {
+        let indexes = (0..tx.header.batch.len()).collect::<Vec<usize>>();
+        Self::batch_result(tx, tx_response, &indexes, storage)
     }
 
-    fn is_tx_rejected(
+    /// Like `tx_result`, with each inner tx recorded under the index given
+    /// for it in `indexes` instead of its position in the batch.
+    fn batch_result(
         tx: &Tx,
         tx_response: &Result<ProcessTxResponse, namada_sdk::error::Error>,
-    ) -> bool {
-        let wrapper_hash = tx.wrapper_hash();
-        for commitment in tx.header.batch.clone() {
-            let is_invalid = match tx_response {
-                Ok(tx_result) => {
-                    let r = tx_result
-                        .is_applied_and_valid(wrapper_hash.as_ref(), &commitment)
-                        .is_none();
-                    // println!("commitment: {} -> {}", commitment.data_sechash(), r);
-                    r
+        indexes: &[usize],
+        storage: &mut StepStorage,
+    ) -> Result<Option<StepResult>, TaskError> {
+        storage.add(
+            TxStorageKeys::TxHash.to_string(),
+            tx.header_hash().to_string(),
+        );
+        if let Some(wrapper_hash) = tx.wrapper_hash() {
+            storage.add(
+                TxStorageKeys::WrapperHash.to_string(),
+                wrapper_hash.to_string(),
+            );
+        }
+        storage.add(
+            TxStorageKeys::Atomic.to_string(),
+            tx.header.atomic.to_string(),
+        );
+        for (index, cmt) in indexes.iter().zip(tx.header.batch.iter()) {
+            storage.add(
+                TxStorageKeys::InnerTxHash(*index).to_string(),
+                cmt.get_hash().to_string(),
+            );
+        }
+
+        let result = match tx_response {
+            Ok(ProcessTxResponse::DryRun(result)) => {
+                storage.add(
+                    TxStorageKeys::GasUsed.to_string(),
+                    result.gas_used.to_string(),
+                );
+                result
+            }
+            // broadcast only, the tx was accepted in the mempool but may not be applied
+            Ok(ProcessTxResponse::Broadcast(response)) => {
+                storage.add(TxStorageKeys::BroadcastOnly.to_string(), "true".to_string());
+                if response.code.is_err() {
+                    return Ok(Some(StepResult::fail_with_kind(
+                        FailureKind::WrapperRejection,
+                        format!("tx rejected from the mempool: {}", response.log),
+                        storage.clone(),
+                    )));
                 }
-                Err(_) => true,
-            };
-            if is_invalid {
-                return true;
+                return Ok(None);
             }
+            Ok(ProcessTxResponse::Applied(response)) => {
+                storage.add(
+                    TxStorageKeys::InclusionHeight.to_string(),
+                    response.height.to_string(),
+                );
+                storage.add(
+                    TxStorageKeys::GasUsed.to_string(),
+                    response.gas_used.to_string(),
+                );
+                match &response.batch {
+                    Some(batch) => {
+                        debug!(?batch, "Batch result");
+                        batch
+                    }
+                    // the wrapper was rejected, e.g. by the fee checks, so no inner tx ran
+                    None => {
+                        return Ok(Some(StepResult::fail_with_kind(
+                            FailureKind::WrapperRejection,
+                            response.info.clone(),
+                            storage.clone(),
+                        )))
+                    }
+                }
+            }
+            Err(namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout)) => {
+                return Err(TaskError::Timeout(storage.clone()))
+            }
+            // the node answered but refused the tx in its mempool
+            Err(namada_sdk::error::Error::Tx(TxSubmitError::TxBroadcast(e)))
+                if matches!(e.detail(), ErrorDetail::Server(_)) =>
+            {
+                return Ok(Some(StepResult::fail_with_kind(
+                    FailureKind::WrapperRejection,
+                    e.to_string(),
+                    storage.clone(),
+                )));
+            }
+            Err(e) => {
+                return Ok(Some(StepResult::fail_with_kind(
+                    FailureKind::RpcError,
+                    e.to_string(),
+                    storage.clone(),
+                )))
+            }
+        };
+
+        let results = InnerTxResult::collect(
+            result,
+            tx.wrapper_hash().as_ref(),
+            tx.header.batch.iter().map(either::Right),
+            tx.header.atomic,
+        );
+        InnerTxResult::record(&results, indexes, storage);
+        if InnerTxResult::all_applied(&results) {
+            return Ok(None);
         }
-        false
+
+        let errors = storage
+            .get_field(&TxStorageKeys::VpErrors.to_string())
+            .unwrap_or_default();
+        Ok(Some(StepResult::fail_with_kind(
+            FailureKind::VpRejection,
+            errors,
+            storage.clone(),
+        )))
     }
 
-    async fn default_tx_arg(sdk: &Sdk) -> args::Tx {
-        let wallet = sdk.namada.wallet.read().await;
-        let nam = wallet
+    /// The gas token of `settings` and its denomination, `None` for the
+    /// native token.
+    async fn gas_token(sdk: &Sdk, settings: &TxSettings) -> Option<(Address, Denomination)> {
+        let token = settings.gas_token.as_ref()?.to_namada_address(sdk).await;
+        let denom = match rpc::query_denom(&sdk.namada.clone_client(), &token).await {
+            Some(denom) => denom,
+            None => {
+                warn!(token = %token, "Can't query the denomination of the gas token");
+                Denomination(0)
+            }
+        };
+        Some((token, denom))
+    }
+
+    /// The tx args of a batch, with the fee token, fee amount, expiration and
+    /// broadcast mode of `settings`.
+    async fn default_tx_arg(sdk: &Sdk, settings: &TxSettings) -> args::Tx {
+        let nam = sdk
+            .namada
+            .wallet
+            .read()
+            .await
             .find_address("nam")
             .expect("Native token should be present.")
             .into_owned();
+        let gas_token = Self::gas_token(sdk, settings).await;
 
-        args::Tx {
+        let tx = args::Tx {
             dry_run: false,
             dry_run_wrapper: false,
             dump_tx: false,
@@ -232,7 +434,7 @@ pub trait Task {
             fee_amount: None,
             wrapper_fee_payer: None,
             fee_token: nam,
-            gas_limit: GasLimit::from(DEFAULT_GAS_LIMIT),
+            gas_limit: GasLimit::from(settings.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT)),
             expiration: Default::default(),
             chain_id: None,
             signing_keys: vec![],
@@ -242,54 +444,98 @@ pub trait Task {
             memo: None,
             use_device: false,
             device_transport: DeviceTransport::default(),
-        }
+        };
+        settings.apply(tx, gas_token)
     }
 }
 
 pub trait TaskParam: Sized {
     type D;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self>;
-    fn settings_from_dto(dto: Option<TxSettingsDto>, _state: &Storage) -> TxSettings {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError>;
+    fn settings_from_dto(dto: Option<TxSettingsDto>, state: &Storage) -> Resolved<TxSettings> {
         let settings = if let Some(settings) = dto {
             settings
         } else {
-            return TxSettings::default();
+            return Ok(Some(TxSettings::default()));
         };
-        let broadcast_only = settings.broadcast_only.unwrap_or(false);
-        let gas_token = match settings.gas_token.clone() {
-            Some(Value::Value { value }) => Some(AccountIndentifier::Alias(value)),
-            _ => None,
+        let resolver = Resolver::new(state);
+
+        let Some(broadcast_only) =
+            resolver.optional("broadcast-only", settings.broadcast_only, Resolver::bool)?
+        else {
+            return Ok(None);
         };
-        let gas_payer = match settings.gas_payer.clone() {
-            Some(Value::Value { value }) => Some(AccountIndentifier::Alias(value)),
-            _ => None,
+        let broadcast_only = broadcast_only.unwrap_or(false);
+        let Some(gas_token) =
+            resolver.optional("gas-token", settings.gas_token, Resolver::account)?
+        else {
+            return Ok(None);
         };
-        let signers = settings.signers.clone().map(|signers| {
-            signers
-                .into_iter()
-                .filter_map(|signer| match signer {
-                    Value::Value { value } => Some(AccountIndentifier::Alias(value)),
-                    _ => None,
-                })
-                .collect::<Vec<AccountIndentifier>>()
-        });
-        let expiration = match settings.expiration.clone() {
-            Some(Value::Value { value }) => Some(value.parse::<u64>().unwrap()),
-            _ => None,
+        let fee = match (settings.gas_price, settings.fee_amount) {
+            (Some(_), Some(_)) => {
+                return Err(ParameterError::new(
+                    "fee-amount",
+                    "can't be set along with gas-price",
+                ))
+            }
+            (Some(gas_price), None) => resolver
+                .number("gas-price", gas_price)?
+                .map(|gas_price| Some(Fee::GasPrice(gas_price))),
+            (None, Some(amount)) => resolver
+                .number("fee-amount", amount)?
+                .map(|amount| Some(Fee::Amount(amount))),
+            (None, None) => Some(None),
         };
-        let gas_limit = match settings.gas_limit.clone() {
-            Some(Value::Value { value }) => Some(value.parse::<u64>().unwrap()),
-            _ => None,
+        let Some(fee) = fee else {
+            return Ok(None);
+        };
+        let Some(gas_payer) =
+            resolver.optional("gas-payer", settings.gas_payer, Resolver::account)?
+        else {
+            return Ok(None);
+        };
+        let signers = match settings.signers {
+            Some(signers) => resolver
+                .list("signers", signers, Resolver::account)?
+                .map(Some),
+            None => Some(None),
+        };
+        let Some(signers) = signers else {
+            return Ok(None);
+        };
+        let expiration = match (settings.expiration, settings.expiration_offset) {
+            (Some(_), Some(_)) => {
+                return Err(ParameterError::new(
+                    "expiration-offset",
+                    "can't be set along with expiration",
+                ))
+            }
+            (Some(timestamp), None) => resolver
+                .number("expiration", timestamp)?
+                .map(|timestamp| Some(Expiration::At(timestamp))),
+            (None, Some(offset)) => resolver
+                .number("expiration-offset", offset)?
+                .map(|offset| Some(Expiration::Offset(offset))),
+            (None, None) => Some(None),
+        };
+        let Some(expiration) = expiration else {
+            return Ok(None);
+        };
+        let Some(gas_limit) =
+            resolver.optional("gas-limit", settings.gas_limit, Resolver::number)?
+        else {
+            return Ok(None);
         };
 
-        TxSettings {
+        Ok(Some(TxSettings {
             broadcast_only,
             gas_token,
+            fee,
             gas_payer,
             signers,
             expiration,
             gas_limit,
-        }
+        }))
     }
 }
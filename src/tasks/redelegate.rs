@@ -1,18 +1,21 @@
 use async_trait::async_trait;
-use namada_sdk::{
-    args::Redelegate, error::TxSubmitError, signing::default_sign, token::Amount, Namada,
-};
-use rand::Rng;
+use namada_sdk::{args::Redelegate, signing::default_sign, token::Amount, Namada};
 use serde::{Deserialize, Serialize};
+use tracing::{error, info};
 
 use super::{Task, TaskError, TaskParam};
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
-    queries::validators::ValidatorsQueryStorageKeys,
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 pub enum TxRedelegateStorageKeys {
@@ -49,6 +52,21 @@ impl Task for TxRedelegate {
     type P = TxRedelegateParameters;
     type B = Redelegate;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(
+                TxRedelegateStorageKeys::SourceValidatorAddress,
+                FieldType::Address,
+            ),
+            OutputField::new(
+                TxRedelegateStorageKeys::DestValidatorAddress,
+                FieldType::Address,
+            ),
+            OutputField::new(TxRedelegateStorageKeys::SourceAddress, FieldType::Address),
+            OutputField::new(TxRedelegateStorageKeys::Amount, FieldType::Amount),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -117,12 +135,12 @@ impl Task for TxRedelegate {
                         ));
                     }
                     _ => {
-                        println!("no-op reason: {}", e);
+                        info!(error = %e, "Redelegation is a no-op");
                         return Ok(StepResult::no_op());
                     }
                 },
                 _ => {
-                    println!("fail response: {}", e);
+                    error!(error = %e, "Can't build redelegation tx");
                     return Ok(StepResult::fail("Failed building tx".to_string()));
                 }
             },
@@ -139,26 +157,14 @@ impl Task for TxRedelegate {
             .await
             .expect("unable to sign tx");
         let tx = sdk
-            .namada
             .submit(redelegate_tx.clone(), &redelegate_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&redelegate_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&redelegate_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&redelegate_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -201,124 +207,33 @@ pub struct TxRedelegateParameters {
 impl TaskParam for TxRedelegateParameters {
     type D = TxRedelegateParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
-        let src_validator = match dto.src_validator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(src_validator) = resolver.account("src_validator", dto.src_validator)? else {
+            return Ok(None);
         };
         let dest_validator = match dto.dest_validator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => Some(AccountIndentifier::Alias(data)),
-                    "public-key" => Some(AccountIndentifier::PublicKey(data)),
-                    "state" => Some(AccountIndentifier::StateAddress(state.get_address(&data))),
-                    _ => Some(AccountIndentifier::Address(data)),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    Some(AccountIndentifier::Address(value))
-                } else {
-                    Some(AccountIndentifier::Alias(value))
-                }
-            }
             Value::Fuzz { value } => {
-                let step_id = value.expect("Redelgate task requires fuzz for dest valdidator to define the step id to a validator query step");
-                let total_validators = state
-                    .get_step_item(
-                        &step_id,
-                        ValidatorsQueryStorageKeys::TotalValidator
-                            .to_string()
-                            .as_str(),
-                    )
-                    .parse::<u64>()
-                    .unwrap();
-
-                if total_validators < 2 {
-                    None
-                } else {
-                    loop {
-                        let validator_idx = rand::thread_rng().gen_range(0..total_validators);
-                        let validator_address = state.get_step_item(
-                            &step_id,
-                            ValidatorsQueryStorageKeys::Validator(validator_idx)
-                                .to_string()
-                                .as_str(),
-                        );
-                        let dest_validator = AccountIndentifier::Address(validator_address);
-
-                        if dest_validator != src_validator {
-                            break Some(dest_validator);
-                        }
-                    }
-                }
+                resolver.random_validator_except("dest_validator", value, &src_validator)?
             }
+            value => resolver.account("dest_validator", value)?.map(Some),
         };
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let amount = state.get_step_item(&value, &field);
-                amount.parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(dest_validator) = dest_validator else {
+            return Ok(None);
+        };
+        let Some(amount) = resolver.amount("amount", dto.amount)? else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             src_validator,
             dest_validator,
             amount,
-        })
+        }))
     }
 }
@@ -1,22 +1,25 @@
 use async_trait::async_trait;
 use namada_sdk::{
     args::TxBuilder,
-    error::TxSubmitError,
     signing::{default_sign, SigningTxData},
     token::Amount,
-    tx::{self, data::GasLimit, Tx},
-    Namada, DEFAULT_GAS_LIMIT,
+    tx::{self, Tx},
+    Namada,
 };
-use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
-    queries::validators::ValidatorsQueryStorageKeys,
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -63,6 +66,17 @@ impl Task for TxRedelegateBatch {
     type P = TxRedelegateBatchParameters;
     type B = namada_sdk::args::Redelegate;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new("source-{}-address", FieldType::Address),
+            OutputField::new("validator-{}-address", FieldType::Address),
+            OutputField::new("source-validator-{}-address", FieldType::Address),
+            OutputField::new("amount-{}", FieldType::Amount),
+            OutputField::new(TxRedelegateBatchStorageKeys::BatchSize, FieldType::Number),
+            OutputField::new(TxRedelegateBatchStorageKeys::AtomicBatch, FieldType::Bool),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -136,11 +150,8 @@ impl Task for TxRedelegateBatch {
             return Ok(StepResult::no_op());
         }
 
-        let tx_args = Self::default_tx_arg(sdk).await;
+        let tx_args = Self::default_tx_arg(sdk, &settings).await;
         let gas_payer = settings.clone().gas_payer.unwrap().to_public_key(sdk).await;
-        let tx_args = tx_args.gas_limit(GasLimit::from(
-            settings.clone().gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
-        ));
         let tx_args = tx_args.wrapper_fee_payer(gas_payer);
         let is_atomic = true;
 
@@ -155,23 +166,12 @@ impl Task for TxRedelegateBatch {
                 .expect("unable to sign tx");
         }
 
-        let tx = sdk.namada.submit(batch_tx.clone(), &tx_args).await;
+        let tx = sdk.submit(batch_tx.clone(), &tx_args).await;
 
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&batch_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&batch_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&batch_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -206,124 +206,46 @@ pub struct TxRedelegateBatchParameters {
 impl TaskParam for TxRedelegateBatchParameters {
     type D = TxRedelegateBatchParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        // entries referencing a failed step are dropped from the batch
         let batch_size = dto.sources.len();
         let batch = (0..batch_size)
-            .filter_map(|i| {
-                let source = match dto.sources[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
-                    Value::Fuzz { .. } => unimplemented!(),
+            .map(|i| {
+                let Some(source) =
+                    resolver.account(&format!("sources[{}]", i), dto.sources[i].clone())?
+                else {
+                    return Ok(None);
                 };
-                let src_validator = match dto.src_validators[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
-                    Value::Fuzz { .. } => unimplemented!(),
+                let Some(src_validator) = resolver.account(
+                    &format!("src_validators[{}]", i),
+                    dto.src_validators[i].clone(),
+                )?
+                else {
+                    return Ok(None);
                 };
+                let parameter = format!("dest_validators[{}]", i);
                 let dest_validator = match dto.dest_validators[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => Some(AccountIndentifier::Alias(data)),
-                            "public-key" => Some(AccountIndentifier::PublicKey(data)),
-                            "state" => Some(AccountIndentifier::StateAddress(state.get_address(&data))),
-                            _ => Some(AccountIndentifier::Address(data)),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            Some(AccountIndentifier::Address(value))
-                        } else {
-                            Some(AccountIndentifier::Alias(value))
-                        }
-                    }
                     Value::Fuzz { value } => {
-                        let step_id = value.expect("Redelgate task requires fuzz for dest valdidator to define the step id to a validator query step");
-                        let total_validators = state
-                            .get_step_item(
-                                &step_id,
-                                ValidatorsQueryStorageKeys::TotalValidator
-                                    .to_string()
-                                    .as_str(),
-                            )
-                            .parse::<u64>()
-                            .unwrap();
-
-                        if total_validators < 2 {
-                            None
-                        } else {
-                            loop {
-                                let validator_idx = rand::thread_rng().gen_range(0..total_validators);
-                                let validator_address = state.get_step_item(
-                                    &step_id,
-                                    ValidatorsQueryStorageKeys::Validator(validator_idx)
-                                        .to_string()
-                                        .as_str(),
-                                );
-                                let dest_validator = AccountIndentifier::Address(validator_address);
-
-                                if dest_validator != src_validator {
-                                    break Some(dest_validator);
-                                }
-                            }
-                        }
+                        resolver.random_validator_except(&parameter, value, &src_validator)?
                     }
+                    value => resolver.account(&parameter, value)?.map(Some),
                 };
-                let amount = match dto.amounts[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let amount = state.get_step_item(&value, &field);
-                        amount.parse::<u64>().unwrap()
-                    }
-                    Value::Value { value } => value.parse::<u64>().unwrap(),
-                    Value::Fuzz { .. } => unimplemented!(),
+                let Some(dest_validator) = dest_validator else {
+                    return Ok(None);
+                };
+                let Some(amount) =
+                    resolver.amount(&format!("amounts[{}]", i), dto.amounts[i].clone())?
+                else {
+                    return Ok(None);
                 };
 
-                Some((source, src_validator, dest_validator, amount))
+                Ok(Some((source, src_validator, dest_validator, amount)))
             })
+            .collect::<Result<Vec<_>, ParameterError>>()?
+            .into_iter()
+            .flatten()
             .collect::<Vec<(
                 AccountIndentifier,
                 AccountIndentifier,
@@ -331,11 +253,11 @@ impl TaskParam for TxRedelegateBatchParameters {
                 u64,
             )>>();
 
-        Some(Self {
+        Ok(Some(Self {
             sources: batch.iter().map(|t| t.0.clone()).collect(),
             src_validators: batch.iter().map(|t| t.1.clone()).collect(),
             dest_validators: batch.iter().map(|t| t.2.clone()).collect(),
             amounts: batch.iter().map(|t| t.3).collect(),
-        })
+        }))
     }
 }
@@ -3,7 +3,6 @@ use async_trait::async_trait;
 use namada_sdk::{
     address::Address,
     args::{RevealPk, TxBuilder},
-    error::TxSubmitError,
     signing::default_sign,
     Namada,
 };
@@ -11,11 +10,17 @@ use namada_sdk::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -49,6 +54,13 @@ impl Task for TxRevealPk {
     type P = RevealPkParameters;
     type B = RevealPk;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxRevealPkStorageKeys::PublicKey, FieldType::PublicKey),
+            OutputField::new(TxRevealPkStorageKeys::Address, FieldType::Address),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -84,26 +96,14 @@ impl Task for TxRevealPk {
             .expect("unable to sign tx");
 
         let tx = sdk
-            .namada
             .submit(reveal_tx.clone(), &reveal_pk_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&reveal_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&reveal_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&reveal_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         let address = Address::from(&source_public_key);
@@ -137,31 +137,13 @@ pub struct RevealPkParameters {
 impl TaskParam for RevealPkParameters {
     type D = RevealPkParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
 
-        Some(Self { source })
+        Ok(Some(Self { source }))
     }
 }
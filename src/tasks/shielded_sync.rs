@@ -10,7 +10,11 @@ use serde::{Deserialize, Serialize};
 
 use super::{Task, TaskError, TaskParam};
 use crate::utils::settings::TxSettings;
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::{schema::OutputSchema, state::Storage},
+};
 
 #[derive(Clone, Debug, Default)]
 pub struct ShieldedSync {}
@@ -24,6 +28,10 @@ impl ShieldedSync {
 #[async_trait(?Send)]
 impl Task for ShieldedSync {
     type P = ShieldedSyncParameters;
+
+    fn outputs() -> OutputSchema {
+        OutputSchema::default()
+    }
     type B = Bond; // just a placeholder
 
     async fn execute(
@@ -82,7 +90,7 @@ pub struct ShieldedSyncParameters;
 impl TaskParam for ShieldedSyncParameters {
     type D = ShieldedSyncParametersDto;
 
-    fn parameter_from_dto(_dto: Self::D, _state: &Storage) -> Option<Self> {
-        Some(ShieldedSyncParameters)
+    fn parameter_from_dto(_dto: Self::D, _state: &Storage) -> Result<Option<Self>, ParameterError> {
+        Ok(Some(ShieldedSyncParameters))
     }
 }
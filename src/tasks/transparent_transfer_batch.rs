@@ -1,20 +1,25 @@
 use async_trait::async_trait;
 use namada_sdk::{
     args::{InputAmount, TxBuilder, TxTransparentTransferData},
-    error::TxSubmitError,
     signing::{default_sign, SigningTxData},
     token::{self, DenominatedAmount},
-    tx::{self, data::GasLimit, Tx},
-    Namada, DEFAULT_GAS_LIMIT,
+    tx::{self, Tx},
+    Namada,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -34,7 +39,7 @@ impl ToString for TxTransparentTransferBatchStorageKeys {
             TxTransparentTransferBatchStorageKeys::Source(entry) => format!("source-{}", entry),
             TxTransparentTransferBatchStorageKeys::Target(entry) => format!("target-{}", entry),
             TxTransparentTransferBatchStorageKeys::Amount(entry) => format!("amount-{}", entry),
-            TxTransparentTransferBatchStorageKeys::Token(entry) => format!("amount-{}", entry),
+            TxTransparentTransferBatchStorageKeys::Token(entry) => format!("token-{}", entry),
             TxTransparentTransferBatchStorageKeys::BatchSize => "batch-size".to_string(),
             TxTransparentTransferBatchStorageKeys::AtomicBatch => "batch-atomic".to_string(),
         }
@@ -55,6 +60,23 @@ impl Task for TxTransparentTransferBatch {
     type P = TxTransparentTransferBatchParameters;
     type B = namada_sdk::args::TxTransparentTransfer;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new("source-{}", FieldType::Address),
+            OutputField::new("target-{}", FieldType::Address),
+            OutputField::new("amount-{}", FieldType::Amount),
+            OutputField::new("token-{}", FieldType::Address),
+            OutputField::new(
+                TxTransparentTransferBatchStorageKeys::BatchSize,
+                FieldType::Number,
+            ),
+            OutputField::new(
+                TxTransparentTransferBatchStorageKeys::AtomicBatch,
+                FieldType::Bool,
+            ),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -123,11 +145,8 @@ impl Task for TxTransparentTransferBatch {
             return Ok(StepResult::no_op());
         }
 
-        let tx_args = Self::default_tx_arg(sdk).await;
+        let tx_args = Self::default_tx_arg(sdk, &settings).await;
         let gas_payer = settings.clone().gas_payer.unwrap().to_public_key(sdk).await;
-        let tx_args = tx_args.gas_limit(GasLimit::from(
-            settings.clone().gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
-        ));
         let tx_args = tx_args.wrapper_fee_payer(gas_payer);
         let is_atomic = true;
 
@@ -142,23 +161,12 @@ impl Task for TxTransparentTransferBatch {
                 .expect("unable to sign tx");
         }
 
-        let tx = sdk.namada.submit(batch_tx.clone(), &tx_args).await;
+        let tx = sdk.submit(batch_tx.clone(), &tx_args).await;
 
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&batch_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&batch_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&batch_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         storage.add(
@@ -193,93 +201,42 @@ pub struct TxTransparentTransferBatchParameters {
 impl TaskParam for TxTransparentTransferBatchParameters {
     type D = TxTransparentTransferBatchParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        // entries referencing a failed step are dropped from the batch
         let batch_size = dto.sources.len();
         let batch = (0..batch_size)
-            .filter_map(|i| {
-                let source = match dto.sources[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
-                    Value::Fuzz { .. } => unimplemented!(),
+            .map(|i| {
+                let Some(source) =
+                    resolver.account(&format!("sources[{}]", i), dto.sources[i].clone())?
+                else {
+                    return Ok(None);
                 };
-                let target = match dto.targets[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
-                    Value::Fuzz { .. } => unimplemented!(),
+                let Some(target) =
+                    resolver.account(&format!("targets[{}]", i), dto.targets[i].clone())?
+                else {
+                    return Ok(None);
                 };
-                let amount = match dto.amounts[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        state.get_step_item(&value, &field).parse::<u64>().unwrap()
-                    }
-                    Value::Value { value } => value.parse::<u64>().unwrap(),
-                    Value::Fuzz { .. } => unimplemented!(),
+                let Some(token) =
+                    resolver.account(&format!("tokens[{}]", i), dto.tokens[i].clone())?
+                else {
+                    return Ok(None);
                 };
-                let token = match dto.tokens[i].clone() {
-                    Value::Ref { value, field } => {
-                        let was_step_successful = state.is_step_successful(&value);
-                        if !was_step_successful {
-                            return None;
-                        }
-                        let data = state.get_step_item(&value, &field);
-                        match field.to_lowercase().as_str() {
-                            "alias" => AccountIndentifier::Alias(data),
-                            "public-key" => AccountIndentifier::PublicKey(data),
-                            "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                            _ => AccountIndentifier::Address(data),
-                        }
-                    }
-                    Value::Value { value } => {
-                        if value.starts_with(ADDRESS_PREFIX) {
-                            AccountIndentifier::Address(value)
-                        } else {
-                            AccountIndentifier::Alias(value)
-                        }
-                    }
-                    Value::Fuzz { .. } => unimplemented!(),
+                let Some(amount) = resolver.token_amount(
+                    &format!("amounts[{}]", i),
+                    dto.amounts[i].clone(),
+                    &token,
+                )?
+                else {
+                    return Ok(None);
                 };
 
-                Some((source, target, token, amount))
+                Ok(Some((source, target, token, amount)))
             })
+            .collect::<Result<Vec<_>, ParameterError>>()?
+            .into_iter()
+            .flatten()
             .collect::<Vec<(
                 AccountIndentifier,
                 AccountIndentifier,
@@ -287,11 +244,11 @@ impl TaskParam for TxTransparentTransferBatchParameters {
                 u64,
             )>>();
 
-        Some(Self {
+        Ok(Some(Self {
             sources: batch.iter().map(|t| t.0.clone()).collect(),
             targets: batch.iter().map(|t| t.1.clone()).collect(),
             tokens: batch.iter().map(|t| t.2.clone()).collect(),
             amounts: batch.iter().map(|t| t.3).collect(),
-        })
+        }))
     }
 }
@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use namada_sdk::{
+    args::{self, TxBuilder},
+    signing::default_sign,
+    tx, Namada,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    scenario::{StepResult, StepType},
+    sdk::namada::Sdk,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
+};
+
+use super::{
+    bond::{TxBond, TxBondParameters},
+    claim_rewards::{TxClaimRewards, TxClaimRewardsteParameters},
+    tx_transparent_transfer::{TxTransparentTransfer, TxTransparentTransferParameters},
+    unbond::{TxUnbond, TxUnbondParameters},
+    update_account::{TxUpdateAccount, TxUpdateAccountParameters},
+    vote::{TxVoteProposal, TxVoteProposalParameters},
+    withdraw::{TxWithdraw, TxWithdrawParameters},
+    BuiltTx, Task, TaskError, TaskParam,
+};
+
+pub enum TxBatchStorageKeys {
+    BatchSize,
+    AtomicBatch,
+}
+
+impl ToString for TxBatchStorageKeys {
+    fn to_string(&self) -> String {
+        match self {
+            TxBatchStorageKeys::BatchSize => "batch-size".to_string(),
+            TxBatchStorageKeys::AtomicBatch => "batch-atomic".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TxBatch {}
+
+impl TxBatch {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// The outputs of the batch, with the outputs of each entry prefixed by
+    /// `tx-<index>-`.
+    pub fn batch_outputs(dto: &TxBatchParametersDto) -> OutputSchema {
+        let tx_info = OutputSchema::with_tx_info(vec![]);
+        let mut schema = Self::outputs();
+        for (index, entry) in dto.txs.iter().enumerate() {
+            let fields = entry
+                .outputs()
+                .fields
+                .into_iter()
+                .filter(|field| !tx_info.fields.contains(field))
+                .map(|field| {
+                    OutputField::new(format!("tx-{}-{}", index, field.key), field.field_type)
+                });
+            schema.fields.extend(fields);
+        }
+        schema
+    }
+}
+
+#[async_trait(?Send)]
+impl Task for TxBatch {
+    type P = TxBatchParameters;
+    type B = args::Tx;
+
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxBatchStorageKeys::BatchSize, FieldType::Number),
+            OutputField::new(TxBatchStorageKeys::AtomicBatch, FieldType::Bool),
+        ])
+    }
+
+    async fn execute(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        _state: &Storage,
+    ) -> Result<StepResult, TaskError> {
+        let mut txs = vec![];
+        let mut entries = vec![];
+
+        for (index, entry) in parameters.entries {
+            let mut outputs = StepStorage::default();
+            let Some(built) = entry.build_tx(sdk, settings.clone(), &mut outputs).await? else {
+                continue;
+            };
+            txs.push((built.tx, built.signing_data));
+            entries.push((index, outputs));
+        }
+
+        if txs.is_empty() {
+            return Ok(StepResult::no_op());
+        }
+
+        let tx_args = Self::default_tx_arg(sdk, &settings).await;
+        let tx_args = match &settings.gas_payer {
+            Some(gas_payer) => tx_args.wrapper_fee_payer(gas_payer.to_public_key(sdk).await),
+            None => tx_args,
+        };
+
+        let batch_size = txs.len();
+        let (mut batch_tx, signing_datas) =
+            tx::build_batch(txs).map_err(|e| TaskError::Build(e.to_string()))?;
+        batch_tx.header.atomic = parameters.atomic;
+
+        for signing_data in signing_datas {
+            sdk.namada
+                .sign(&mut batch_tx, &tx_args, signing_data, default_sign, ())
+                .await
+                .expect("unable to sign tx");
+        }
+
+        let tx = sdk.submit(batch_tx.clone(), &tx_args).await;
+
+        let mut storage = StepStorage::default();
+        self.fetch_info(sdk, &mut storage).await;
+
+        // the inner txs are recorded under the index of their entry in `txs`
+        let indexes = entries
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<usize>>();
+        if let Some(result) = Self::batch_result(&batch_tx, &tx, &indexes, &mut storage)? {
+            return Ok(result);
+        }
+
+        for (index, outputs) in entries {
+            for (key, value) in outputs.storage {
+                storage.add(format!("tx-{}-{}", index, key), value);
+            }
+        }
+        storage.add(
+            TxBatchStorageKeys::BatchSize.to_string(),
+            batch_size.to_string(),
+        );
+        storage.add(
+            TxBatchStorageKeys::AtomicBatch.to_string(),
+            parameters.atomic.to_string(),
+        );
+
+        Ok(StepResult::success(storage))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TxBatchParametersDto {
+    /// the steps whose txs are batched, without settings
+    pub txs: Vec<StepType>,
+    pub atomic: Option<Value>,
+}
+
+/// A tx of the batch, with its resolved parameters.
+#[derive(Clone, Debug)]
+pub enum BatchEntry {
+    TransparentTransfer(TxTransparentTransferParameters),
+    Bond(TxBondParameters),
+    Unbond(TxUnbondParameters),
+    Withdraw(TxWithdrawParameters),
+    ClaimRewards(TxClaimRewardsteParameters),
+    VoteProposal(TxVoteProposalParameters),
+    UpdateAccount(TxUpdateAccountParameters),
+}
+
+impl BatchEntry {
+    async fn build_tx(
+        self,
+        sdk: &Sdk,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
+        match self {
+            BatchEntry::TransparentTransfer(parameters) => {
+                TxTransparentTransfer::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+            BatchEntry::Bond(parameters) => {
+                TxBond::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+            BatchEntry::Unbond(parameters) => {
+                TxUnbond::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+            BatchEntry::Withdraw(parameters) => {
+                TxWithdraw::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+            BatchEntry::ClaimRewards(parameters) => {
+                TxClaimRewards::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+            BatchEntry::VoteProposal(parameters) => {
+                TxVoteProposal::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+            BatchEntry::UpdateAccount(parameters) => {
+                TxUpdateAccount::default()
+                    .build_tx(sdk, parameters, settings, outputs)
+                    .await
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TxBatchParameters {
+    /// the entries that aren't a no-op, with their index in `txs`
+    pub entries: Vec<(usize, BatchEntry)>,
+    pub atomic: bool,
+}
+
+impl TaskParam for TxBatchParameters {
+    type D = TxBatchParametersDto;
+
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let atomic = match dto.atomic {
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("atomic")),
+            value => resolver
+                .optional("atomic", value, Resolver::bool)?
+                .flatten()
+                .unwrap_or(true),
+        };
+
+        // entries referencing a failed step are dropped from the batch
+        let mut entries = vec![];
+        for (index, entry) in dto.txs.into_iter().enumerate() {
+            let parameter = format!("txs[{}]", index);
+            let entry = match entry {
+                StepType::TransparentTransfer {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(
+                    &parameter,
+                    parameters,
+                    state,
+                    BatchEntry::TransparentTransfer,
+                )?,
+                StepType::Bond {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(&parameter, parameters, state, BatchEntry::Bond)?,
+                StepType::Unbond {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(&parameter, parameters, state, BatchEntry::Unbond)?,
+                StepType::Withdraw {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(&parameter, parameters, state, BatchEntry::Withdraw)?,
+                StepType::ClaimRewards {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(&parameter, parameters, state, BatchEntry::ClaimRewards)?,
+                StepType::VoteProposal {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(&parameter, parameters, state, BatchEntry::VoteProposal)?,
+                StepType::UpdateAccount {
+                    parameters,
+                    settings: None,
+                } => entry_from_dto(&parameter, parameters, state, BatchEntry::UpdateAccount)?,
+                StepType::TransparentTransfer { .. }
+                | StepType::Bond { .. }
+                | StepType::Unbond { .. }
+                | StepType::Withdraw { .. }
+                | StepType::ClaimRewards { .. }
+                | StepType::VoteProposal { .. }
+                | StepType::UpdateAccount { .. } => {
+                    return Err(ParameterError::new(
+                        &parameter,
+                        "the settings of the batch apply to every tx",
+                    ))
+                }
+                entry => {
+                    return Err(ParameterError::new(
+                        &parameter,
+                        format!("{} can't be batched", entry),
+                    ))
+                }
+            };
+            entries.extend(entry.map(|entry| (index, entry)));
+        }
+
+        Ok(Some(Self { entries, atomic }))
+    }
+}
+
+// errors are reported as `txs[<index>].parameters.<parameter>`
+fn entry_from_dto<P: TaskParam>(
+    parameter: &str,
+    dto: P::D,
+    state: &Storage,
+    entry: impl Fn(P) -> BatchEntry,
+) -> Result<Option<BatchEntry>, ParameterError> {
+    P::parameter_from_dto(dto, state)
+        .map(|parameters| parameters.map(entry))
+        .map_err(|e| {
+            ParameterError::new(
+                &format!("{}.parameters.{}", parameter, e.parameter),
+                e.reason,
+            )
+        })
+}
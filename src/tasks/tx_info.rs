@@ -0,0 +1,277 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use namada_sdk::{
+    hash::Hash,
+    tx::{data::TxResult, either::Either, TxCommitments},
+};
+
+use crate::state::state::StepStorage;
+
+/// Keys under which a tx step records what happened to its tx, declared by
+/// `OutputSchema::with_tx_info`.
+pub enum TxStorageKeys {
+    /// hash of the header of the tx
+    TxHash,
+    WrapperHash,
+    /// whether the inner txs are rolled back once one of them isn't applied
+    Atomic,
+    /// the tx was only broadcast, see `wait-tx`
+    BroadcastOnly,
+    /// gas used by the tx, or by the simulation with `--dry-run`
+    GasUsed,
+    /// height of the block the tx was applied in
+    InclusionHeight,
+    /// errors of each inner tx that wasn't applied, as a json object keyed by
+    /// the index of the inner tx
+    VpErrors,
+    /// comma separated addresses of the VPs that rejected any of the inner txs
+    RejectedVps,
+    InnerTxHash(usize),
+    /// `applied`, `rejected`, `rolled-back` or `not-run`
+    InnerTxStatus(usize),
+    /// json list of the VP errors, or the error of the inner tx if it failed
+    InnerTxVpErrors(usize),
+    InnerTxRejectedVps(usize),
+    /// comma separated flags of the VP results, e.g. `invalid-signature`
+    InnerTxStatusFlags(usize),
+    /// json list of the events emitted by the inner tx
+    InnerTxEvents(usize),
+}
+
+impl ToString for TxStorageKeys {
+    fn to_string(&self) -> String {
+        match self {
+            TxStorageKeys::TxHash => "tx-hash".to_string(),
+            TxStorageKeys::WrapperHash => "wrapper-hash".to_string(),
+            TxStorageKeys::Atomic => "atomic".to_string(),
+            TxStorageKeys::BroadcastOnly => "broadcast-only".to_string(),
+            TxStorageKeys::GasUsed => "gas-used".to_string(),
+            TxStorageKeys::InclusionHeight => "inclusion-height".to_string(),
+            TxStorageKeys::VpErrors => "vp-errors".to_string(),
+            TxStorageKeys::RejectedVps => "rejected-vps".to_string(),
+            TxStorageKeys::InnerTxHash(index) => format!("inner-tx-{}-hash", index),
+            TxStorageKeys::InnerTxStatus(index) => format!("inner-tx-{}-status", index),
+            TxStorageKeys::InnerTxVpErrors(index) => format!("inner-tx-{}-vp-errors", index),
+            TxStorageKeys::InnerTxRejectedVps(index) => {
+                format!("inner-tx-{}-rejected-vps", index)
+            }
+            TxStorageKeys::InnerTxStatusFlags(index) => {
+                format!("inner-tx-{}-status-flags", index)
+            }
+            TxStorageKeys::InnerTxEvents(index) => format!("inner-tx-{}-events", index),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InnerTxStatus {
+    /// Applied and accepted by every VP
+    Applied,
+    /// Rejected by a VP, or failed to run
+    Rejected,
+    /// Accepted, but another inner tx of the atomic batch wasn't
+    RolledBack,
+    /// The chain has no result for it, e.g. the batch stopped before it
+    NotRun,
+}
+
+impl Display for InnerTxStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InnerTxStatus::Applied => write!(f, "applied"),
+            InnerTxStatus::Rejected => write!(f, "rejected"),
+            InnerTxStatus::RolledBack => write!(f, "rolled-back"),
+            InnerTxStatus::NotRun => write!(f, "not-run"),
+        }
+    }
+}
+
+/// What happened to an inner tx of a batch.
+#[derive(Clone, Debug)]
+pub struct InnerTxResult {
+    pub status: InnerTxStatus,
+    /// json list of the VP errors, or the error of the inner tx if it failed
+    pub errors: Option<String>,
+    pub rejected_vps: Vec<String>,
+    pub status_flags: Vec<String>,
+    /// json list of the events emitted by the inner tx
+    pub events: Option<String>,
+}
+
+impl InnerTxResult {
+    fn not_run() -> Self {
+        Self {
+            status: InnerTxStatus::NotRun,
+            errors: None,
+            rejected_vps: vec![],
+            status_flags: vec![],
+            events: None,
+        }
+    }
+
+    /// The result of each of `inner_txs`, identified by their hash or their
+    /// commitments. If the batch is atomic, the inner txs that were accepted
+    /// are rolled back once one of them isn't.
+    pub fn collect<'a>(
+        result: &TxResult<String>,
+        wrapper_hash: Option<&Hash>,
+        inner_txs: impl IntoIterator<Item = Either<&'a Hash, &'a TxCommitments>>,
+        atomic: bool,
+    ) -> Vec<Self> {
+        let mut results = inner_txs
+            .into_iter()
+            .map(|inner_tx| {
+                let inner_result = result.get_inner_tx_result(wrapper_hash, inner_tx);
+                match inner_result {
+                    Some(Ok(res)) => Self {
+                        status: if res.is_accepted() {
+                            InnerTxStatus::Applied
+                        } else {
+                            InnerTxStatus::Rejected
+                        },
+                        errors: (!res.is_accepted())
+                            .then(|| serde_json::to_string(&res.vps_result.errors).unwrap()),
+                        rejected_vps: res
+                            .vps_result
+                            .rejected_vps
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect(),
+                        status_flags: res
+                            .vps_result
+                            .status_flags
+                            .iter_names()
+                            .map(|(name, _)| name.to_lowercase().replace('_', "-"))
+                            .collect(),
+                        events: Some(serde_json::to_string(&res.events).unwrap()),
+                    },
+                    Some(Err(e)) => Self {
+                        status: InnerTxStatus::Rejected,
+                        errors: Some(e.to_string()),
+                        ..Self::not_run()
+                    },
+                    None => Self::not_run(),
+                }
+            })
+            .collect::<Vec<Self>>();
+
+        if atomic && !Self::all_applied(&results) {
+            results
+                .iter_mut()
+                .filter(|result| result.status == InnerTxStatus::Applied)
+                .for_each(|result| result.status = InnerTxStatus::RolledBack);
+        }
+        results
+    }
+
+    pub fn all_applied(results: &[Self]) -> bool {
+        results
+            .iter()
+            .all(|result| result.status == InnerTxStatus::Applied)
+    }
+
+    /// Records the result of each inner tx under the index given for it in
+    /// `indexes`, and the errors and the VPs that rejected them for the whole
+    /// tx.
+    pub fn record(results: &[Self], indexes: &[usize], storage: &mut StepStorage) {
+        let mut errors = serde_json::Map::new();
+        for (&index, result) in indexes.iter().zip(results) {
+            storage.add(
+                TxStorageKeys::InnerTxStatus(index).to_string(),
+                result.status.to_string(),
+            );
+            storage.add(
+                TxStorageKeys::InnerTxRejectedVps(index).to_string(),
+                result.rejected_vps.join(","),
+            );
+            storage.add(
+                TxStorageKeys::InnerTxStatusFlags(index).to_string(),
+                result.status_flags.join(","),
+            );
+            if let Some(events) = &result.events {
+                storage.add(
+                    TxStorageKeys::InnerTxEvents(index).to_string(),
+                    events.clone(),
+                );
+            }
+            if let Some(inner_errors) = &result.errors {
+                storage.add(
+                    TxStorageKeys::InnerTxVpErrors(index).to_string(),
+                    inner_errors.clone(),
+                );
+                // the error of a failed inner tx isn't json
+                let inner_errors = serde_json::from_str(inner_errors)
+                    .unwrap_or_else(|_| serde_json::Value::String(inner_errors.clone()));
+                errors.insert(index.to_string(), inner_errors);
+            } else if result.status == InnerTxStatus::NotRun {
+                errors.insert(
+                    index.to_string(),
+                    serde_json::Value::String("missing inner tx result".to_string()),
+                );
+            }
+        }
+
+        if !errors.is_empty() {
+            storage.add(
+                TxStorageKeys::VpErrors.to_string(),
+                serde_json::Value::Object(errors).to_string(),
+            );
+        }
+        storage.add(
+            TxStorageKeys::RejectedVps.to_string(),
+            results
+                .iter()
+                .flat_map(|result| result.rejected_vps.iter())
+                .unique()
+                .join(","),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let results = vec![
+            InnerTxResult {
+                status: InnerTxStatus::RolledBack,
+                ..InnerTxResult::not_run()
+            },
+            InnerTxResult {
+                status: InnerTxStatus::Rejected,
+                errors: Some("[[\"tnam1q\",\"invalid\"]]".to_string()),
+                rejected_vps: vec!["tnam1q".to_string()],
+                status_flags: vec!["invalid-signature".to_string()],
+                events: Some("[]".to_string()),
+            },
+            InnerTxResult {
+                status: InnerTxStatus::Rejected,
+                errors: Some("out of gas".to_string()),
+                ..InnerTxResult::not_run()
+            },
+        ];
+        let mut storage = StepStorage::default();
+        InnerTxResult::record(&results, &[0, 2, 3], &mut storage);
+
+        assert!(!InnerTxResult::all_applied(&results));
+        assert_eq!(
+            storage.get_field("inner-tx-0-status"),
+            Some("rolled-back".to_string())
+        );
+        assert_eq!(
+            storage.get_field("inner-tx-2-status-flags"),
+            Some("invalid-signature".to_string())
+        );
+        assert_eq!(
+            storage.get_field("vp-errors"),
+            Some(r#"{"2":[["tnam1q","invalid"]],"3":"out of gas"}"#.to_string())
+        );
+        assert_eq!(
+            storage.get_field("rejected-vps"),
+            Some("tnam1q".to_string())
+        );
+    }
+}
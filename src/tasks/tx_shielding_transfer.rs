@@ -1,5 +1,4 @@
 use async_trait::async_trait;
-use namada_sdk::error::TxSubmitError;
 use namada_sdk::rpc::TxResponse;
 use namada_sdk::tx::ProcessTxResponse;
 use namada_sdk::{
@@ -7,7 +6,6 @@ use namada_sdk::{
         InputAmount, TxShieldingTransfer as NamadaTxShieldingTransfer, TxShieldingTransferData,
     },
     signing::default_sign,
-    string_encoding::MASP_PAYMENT_ADDRESS_HRP,
     token::{self, DenominatedAmount},
     Namada,
 };
@@ -15,11 +13,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::utils::settings::TxSettings;
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::value::Value,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
 };
 
 use super::{Task, TaskError, TaskParam};
@@ -56,6 +57,19 @@ impl Task for TxShieldingTransfer {
     type P = TxShieldingTransferParameters;
     type B = NamadaTxShieldingTransfer;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxShieldingTransferStorageKeys::Source, FieldType::Address),
+            OutputField::new(
+                TxShieldingTransferStorageKeys::Target,
+                FieldType::PaymentAddress,
+            ),
+            OutputField::new(TxShieldingTransferStorageKeys::Amount, FieldType::Amount),
+            OutputField::new(TxShieldingTransferStorageKeys::Token, FieldType::Address),
+            OutputField::new("stx-height", FieldType::Height),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -98,26 +112,14 @@ impl Task for TxShieldingTransfer {
             .await
             .map_err(|err| TaskError::Build(err.to_string()))?;
         let tx = sdk
-            .namada
             .submit(transfer_tx.clone(), &transfer_tx_builder.tx)
             .await;
 
         let mut storage = StepStorage::default();
         self.fetch_info(sdk, &mut storage).await;
 
-        if Self::is_tx_rejected(&transfer_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&transfer_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
+        if let Some(result) = Self::tx_result(&transfer_tx, &tx, &mut storage)? {
+            return Ok(result);
         }
 
         let Ok(ProcessTxResponse::Applied(TxResponse { height, .. })) = &tx else {
@@ -165,75 +167,27 @@ pub struct TxShieldingTransferParameters {
 impl TaskParam for TxShieldingTransferParameters {
     type D = TxShieldingTransferParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
-        let target = match dto.target {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    _ => AccountIndentifier::PaymentAddress(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(MASP_PAYMENT_ADDRESS_HRP) {
-                    AccountIndentifier::PaymentAddress(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(target) = resolver.payment_address("target", dto.target)? else {
+            return Ok(None);
         };
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                state.get_step_item(&value, &field).parse::<u64>().ok()?
-            }
-            Value::Value { value } => value.parse::<u64>().ok()?,
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(token) = resolver.account("token", dto.token)? else {
+            return Ok(None);
         };
-        let token = match dto.token {
-            Value::Ref { value, field } => {
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(amount) = resolver.token_amount("amount", dto.amount, &token)? else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             target,
             amount,
             token,
-        })
+        }))
     }
 }
@@ -1,8 +1,6 @@
 use async_trait::async_trait;
 use namada_sdk::{
     args::{InputAmount, TxTransparentTransferData},
-    error::TxSubmitError,
-    signing::default_sign,
     token::{self, DenominatedAmount},
     Namada,
 };
@@ -10,14 +8,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::utils::settings::TxSettings;
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::value::Value,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::value::{ParameterError, Resolver, Value},
 };
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 
 pub enum TxTransparentTransferStorageKeys {
     Source,
@@ -51,6 +52,15 @@ impl Task for TxTransparentTransfer {
     type P = TxTransparentTransferParameters;
     type B = namada_sdk::args::TxTransparentTransfer;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxTransparentTransferStorageKeys::Source, FieldType::Address),
+            OutputField::new(TxTransparentTransferStorageKeys::Target, FieldType::Address),
+            OutputField::new(TxTransparentTransferStorageKeys::Amount, FieldType::Amount),
+            OutputField::new(TxTransparentTransferStorageKeys::Token, FieldType::Address),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -58,6 +68,16 @@ impl Task for TxTransparentTransfer {
         settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
         let target_address = parameters.target.to_namada_address(sdk).await;
         let token_address = parameters.token.to_namada_address(sdk).await;
@@ -73,64 +93,35 @@ impl Task for TxTransparentTransfer {
 
         let transfer_tx_builder = sdk.namada.new_transparent_transfer(vec![tx_transfer_data]);
 
-        let mut transfer_tx_builder = self.add_settings(sdk, transfer_tx_builder, settings).await;
+        let transfer_tx_builder = self.add_settings(sdk, transfer_tx_builder, settings).await;
 
-        let (mut transfer_tx, signing_data) = transfer_tx_builder
+        let (transfer_tx, signing_data) = transfer_tx_builder
             .build(&sdk.namada)
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut transfer_tx,
-                &transfer_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-        let tx = sdk
-            .namada
-            .submit(transfer_tx.clone(), &transfer_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&transfer_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&transfer_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(
+        outputs.add(
             TxTransparentTransferStorageKeys::Source.to_string(),
             source_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxTransparentTransferStorageKeys::Target.to_string(),
             target_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxTransparentTransferStorageKeys::Amount.to_string(),
             token_amount.raw_amount().to_string(),
         );
-        storage.add(
+        outputs.add(
             TxTransparentTransferStorageKeys::Token.to_string(),
             token_address.to_string(),
         );
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: transfer_tx,
+            signing_data,
+            args: transfer_tx_builder.tx,
+        }))
     }
 }
 
@@ -153,93 +144,27 @@ pub struct TxTransparentTransferParameters {
 impl TaskParam for TxTransparentTransferParameters {
     type D = TxTransparentTransferParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
-        let target = match dto.target {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(target) = resolver.account("target", dto.target)? else {
+            return Ok(None);
         };
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                state.get_step_item(&value, &field).parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(token) = resolver.account("token", dto.token)? else {
+            return Ok(None);
         };
-        let token = match dto.token {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(amount) = resolver.token_amount("amount", dto.amount, &token)? else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             target,
             amount,
             token,
-        })
+        }))
     }
 }
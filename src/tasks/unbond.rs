@@ -1,18 +1,22 @@
 use async_trait::async_trait;
-use namada_sdk::{
-    args::Unbond, error::TxSubmitError, signing::default_sign, token::Amount, Namada,
-};
+use namada_sdk::{args::Unbond, token::Amount, Namada};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 
 pub enum TxUnbondStorageKeys {
     SourceAddress,
@@ -44,6 +48,14 @@ impl Task for TxUnbond {
     type P = TxUnbondParameters;
     type B = Unbond;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxUnbondStorageKeys::SourceAddress, FieldType::Address),
+            OutputField::new(TxUnbondStorageKeys::ValidatorAddress, FieldType::Address),
+            OutputField::new(TxUnbondStorageKeys::Amount, FieldType::Amount),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -51,6 +63,16 @@ impl Task for TxUnbond {
         settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
         let amount = Amount::from(parameters.amount);
         let validator_address = parameters.validator.to_namada_address(sdk).await;
@@ -67,54 +89,24 @@ impl Task for TxUnbond {
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut unbond_tx,
-                &unbond_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-
-        let tx = sdk
-            .namada
-            .submit(unbond_tx.clone(), &unbond_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&unbond_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&unbond_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(
+        outputs.add(
             TxUnbondStorageKeys::ValidatorAddress.to_string(),
             validator_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxUnbondStorageKeys::SourceAddress.to_string(),
             source_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxUnbondStorageKeys::Amount.to_string(),
             amount.raw_amount().to_string(),
         );
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: unbond_tx,
+            signing_data,
+            args: unbond_tx_builder.tx,
+        }))
     }
 }
 
@@ -135,70 +127,23 @@ pub struct TxUnbondParameters {
 impl TaskParam for TxUnbondParameters {
     type D = TxUnbondParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
-        let validator = match dto.validator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(validator) = resolver.account("validator", dto.validator)? else {
+            return Ok(None);
         };
-        let amount = match dto.amount {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let amount = state.get_step_item(&value, &field);
-                amount.parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(amount) = resolver.amount("amount", dto.amount)? else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             validator,
             amount,
-        })
+        }))
     }
 }
@@ -1,22 +1,27 @@
 use std::path::PathBuf;
 
 use async_trait::async_trait;
-use rand::{distributions::Alphanumeric, Rng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
-};
-use namada_sdk::{
-    args::TxUpdateAccount as SdkUpdateAccountTx, error::TxSubmitError, signing::default_sign,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        fuzz::Fuzzer,
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
+use namada_sdk::args::TxUpdateAccount as SdkUpdateAccountTx;
 use namada_sdk::{tx::VP_USER_WASM, Namada};
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 
 pub enum TxUpdateAccountStorageKeys {
     Address,
@@ -48,14 +53,8 @@ impl TxUpdateAccount {
 }
 
 impl TxUpdateAccount {
-    pub fn generate_random_alias() -> String {
-        let random_suffix: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(5)
-            .map(char::from)
-            .collect();
-
-        format!("lt-acc-enst-{}", random_suffix)
+    pub fn generate_random_alias(fuzzer: &Fuzzer) -> String {
+        fuzzer.alias("alias", "lt-acc-enst")
     }
 }
 
@@ -64,6 +63,18 @@ impl Task for TxUpdateAccount {
     type P = TxUpdateAccountParameters;
     type B = SdkUpdateAccountTx;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxUpdateAccountStorageKeys::Address, FieldType::Address),
+            OutputField::new(TxUpdateAccountStorageKeys::Threshold, FieldType::Number),
+            OutputField::new(
+                TxUpdateAccountStorageKeys::TotalPublicKeys,
+                FieldType::Number,
+            ),
+            OutputField::new("public_key_at_index-{}", FieldType::PublicKey),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -71,6 +82,16 @@ impl Task for TxUpdateAccount {
         settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
         let threshold = parameters.threshold as u8;
 
@@ -89,64 +110,35 @@ impl Task for TxUpdateAccount {
             .add_settings(sdk, update_account_tx_builder, settings)
             .await;
 
-        let (mut update_account_tx, signing_data) = update_account_tx_builder
+        let (update_account_tx, signing_data) = update_account_tx_builder
             .build(&sdk.namada)
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut update_account_tx,
-                &update_account_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-        let tx = sdk
-            .namada
-            .submit(update_account_tx.clone(), &update_account_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&update_account_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&update_account_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(
+        outputs.add(
             TxUpdateAccountStorageKeys::Address.to_string(),
             source_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxUpdateAccountStorageKeys::Threshold.to_string(),
             parameters.threshold.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxUpdateAccountStorageKeys::TotalPublicKeys.to_string(),
             public_keys.len().to_string(),
         );
         for (key, value) in public_keys.clone().into_iter().enumerate() {
-            storage.add(
+            outputs.add(
                 TxUpdateAccountStorageKeys::PublicKeyAtIndex(key as u8).to_string(),
                 value.to_string(),
             );
         }
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: update_account_tx,
+            signing_data,
+            args: update_account_tx_builder.tx,
+        }))
     }
 }
 
@@ -177,68 +169,33 @@ pub struct TxUpdateAccountParameters {
 impl TaskParam for TxUpdateAccountParameters {
     type D = TxUpdateAccountParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
+        };
+        let Some(sources) = resolver.list("public_keys", dto.public_keys, Resolver::account)?
+        else {
+            return Ok(None);
         };
-        let sources = dto
-            .public_keys
-            .into_iter()
-            .map(|value| match value {
-                Value::Ref { value, field } => {
-                    let data = state.get_step_item(&value, &field);
-                    match field.to_lowercase().as_str() {
-                        "alias" => AccountIndentifier::Alias(data),
-                        "public-key" => AccountIndentifier::PublicKey(data),
-                        "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                        _ => AccountIndentifier::Address(data),
-                    }
-                }
-                Value::Value { value } => {
-                    if value.starts_with(ADDRESS_PREFIX) {
-                        AccountIndentifier::Address(value)
-                    } else {
-                        AccountIndentifier::Alias(value)
-                    }
-                }
-                Value::Fuzz { .. } => unimplemented!(),
-            })
-            .collect::<Vec<AccountIndentifier>>();
         let threshold = match dto.threshold {
-            Some(value) => match value {
-                Value::Ref { .. } => unimplemented!(),
-                Value::Value { value } => value
-                    .parse::<u64>()
-                    .expect("Should be convertiable to u64."),
-                Value::Fuzz { .. } => rand::thread_rng().gen_range(1..=sources.len()) as u64,
-            },
-            None => 1u64,
+            Some(Value::Fuzz { .. }) => Some(
+                state
+                    .fuzzer
+                    .pick("threshold", |rng| rng.gen_range(1..=sources.len()) as u64),
+            ),
+            Some(value) => resolver.number("threshold", value)?,
+            None => Some(1u64),
+        };
+        let Some(threshold) = threshold else {
+            return Ok(None);
         };
 
-        Some(Self {
+        Ok(Some(Self {
             source,
             sources,
             threshold,
-        })
+        }))
     }
 }
@@ -1,22 +1,25 @@
 use async_trait::async_trait;
-use namada_sdk::{
-    args::VoteProposal, error::TxSubmitError, governance::utils::ProposalStatus,
-    signing::default_sign, Namada,
-};
+use namada_sdk::{args::VoteProposal, governance::utils::ProposalStatus, Namada};
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     queries::proposals::ProposalsQueryStorageKeys,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 
 pub enum TxVoteProposalStorageKeys {
     Vote,
@@ -46,6 +49,13 @@ impl Task for TxVoteProposal {
     type P = TxVoteProposalParameters;
     type B = VoteProposal;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxVoteProposalStorageKeys::Vote, FieldType::Text),
+            OutputField::new(TxVoteProposalStorageKeys::VoterAddress, FieldType::Address),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
@@ -53,12 +63,22 @@ impl Task for TxVoteProposal {
         settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         // Params are validator: Address, source: Address, amount: u64
         let proposal_id = if let Some(id) = parameters.proposal_id {
             id
         } else {
             // no proposal id was specified or fuzzing couldn't find a suitable proposal to vote
-            return Ok(StepResult::no_op());
+            return Ok(None);
         };
         let voter_address = parameters.voter.to_namada_address(sdk).await;
         let vote = parameters.vote;
@@ -71,51 +91,22 @@ impl Task for TxVoteProposal {
             .add_settings(sdk, vote_proposal_tx_builder, settings)
             .await;
 
-        let (mut vote_proposal_tx, signing_data) = vote_proposal_tx_builder
+        let (vote_proposal_tx, signing_data) = vote_proposal_tx_builder
             .build(&sdk.namada)
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut vote_proposal_tx,
-                &vote_proposal_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-        let tx = sdk
-            .namada
-            .submit(vote_proposal_tx.clone(), &vote_proposal_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&vote_proposal_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&vote_proposal_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(TxVoteProposalStorageKeys::Vote.to_string(), vote);
-        storage.add(
+        outputs.add(TxVoteProposalStorageKeys::Vote.to_string(), vote);
+        outputs.add(
             TxVoteProposalStorageKeys::VoterAddress.to_string(),
             voter_address.to_string(),
         );
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: vote_proposal_tx,
+            signing_data,
+            args: vote_proposal_tx_builder.tx,
+        }))
     }
 }
 
@@ -136,81 +127,75 @@ pub struct TxVoteProposalParameters {
 impl TaskParam for TxVoteProposalParameters {
     type D = TxVoteProposalParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
         let proposal_id = match dto.proposal_id {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let id_string = state.get_step_item(&value, &field);
-                id_string.parse::<u64>().ok()
-            }
-            Value::Value { value } => value.parse::<u64>().ok(),
             Value::Fuzz { value } => {
-                let step_id = value.expect(
-                    "Fuzzing vote proposal require a step id pointing to a QueryProposal step.",
-                );
-                let last_proposal_id = state
-                    .get_step_item(
-                        &step_id,
-                        ProposalsQueryStorageKeys::Total.to_string().as_str(),
+                let step_id = value.ok_or_else(|| {
+                    ParameterError::new(
+                        "proposal_id",
+                        "fuzzing requires the id of a query-proposals step",
                     )
-                    .parse::<u64>()
-                    .unwrap();
+                })?;
+                let Some(last_proposal_id) = resolver.number(
+                    "proposal_id",
+                    Value::r(step_id, ProposalsQueryStorageKeys::Total.to_string()),
+                )?
+                else {
+                    return Ok(None);
+                };
                 let mut proposal_id = None;
                 for id in 0..last_proposal_id {
-                    let maybe_proposal_status = state.get_step_item(
-                        &step_id,
-                        ProposalsQueryStorageKeys::ProposalStatus(id)
-                            .to_string()
-                            .as_str(),
-                    );
+                    let maybe_proposal_status = state
+                        .get_step_item(
+                            &step_id,
+                            ProposalsQueryStorageKeys::ProposalStatus(id)
+                                .to_string()
+                                .as_str(),
+                        )
+                        .map_err(|e| ParameterError::new("proposal_id", e))?;
                     if maybe_proposal_status.eq(&ProposalStatus::OnGoing.to_string()) {
                         proposal_id = Some(id);
                     }
-                    if proposal_id.is_some() && rand::thread_rng().gen_range(0..=1) >= 1 {
+                    if proposal_id.is_some() && state.fuzzer.rng().gen_range(0..=1) >= 1 {
                         break;
                     }
                 }
-                proposal_id
-            }
-        };
-        let voter = match dto.voter {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
+                if let Some(proposal_id) = proposal_id {
+                    state.fuzzer.record("proposal_id", proposal_id);
                 }
-                let alias = state.get_step_item(&value, &field);
-                AccountIndentifier::Address(state.get_address(&alias).address)
+                proposal_id
             }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
+            value => {
+                let Some(proposal_id) = resolver.number("proposal_id", value)? else {
+                    return Ok(None);
+                };
+                Some(proposal_id)
             }
-            Value::Fuzz { .. } => unimplemented!(),
+        };
+        let Some(voter) = resolver.account("voter", dto.voter)? else {
+            return Ok(None);
         };
         let vote = match dto.vote {
-            Value::Ref { value: _, field: _ } => {
-                unimplemented!()
-            }
-            Value::Value { value } => value,
-            Value::Fuzz { .. } => match rand::thread_rng().gen_range(0..3) {
+            Value::Fuzz { .. } => state.fuzzer.pick("vote", |rng| match rng.gen_range(0..3) {
                 0 => "yay".to_string(),
                 1 => "nay".to_string(),
                 2 => "abstain".to_string(),
                 _ => "abstain".to_string(),
-            },
+            }),
+            value => {
+                let Some(vote) = resolver.string("vote", value)? else {
+                    return Ok(None);
+                };
+                vote
+            }
         };
 
-        Some(Self {
+        Ok(Some(Self {
             proposal_id,
             voter,
             vote,
-        })
+        }))
     }
 }
@@ -5,16 +5,19 @@ use namada_sdk::masp_primitives::zip32;
 use namada_sdk::PaymentAddress;
 use namada_sdk::{address::Address, key::SchemeType};
 use rand::rngs::OsRng;
-use rand::{distributions::Alphanumeric, Rng};
 use serde::{Deserialize, Serialize};
 
 use super::{Task, TaskError, TaskParam};
+use crate::utils::fuzz::Fuzzer;
 use crate::utils::settings::TxSettings;
-use crate::utils::value::Value;
+use crate::utils::value::{ParameterError, Resolver, Value};
 use crate::{
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StateAddress, StepStorage, Storage},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StateAddress, StepStorage, Storage},
+    },
 };
 use namada_sdk::key::RefTo;
 
@@ -50,20 +53,28 @@ impl WalletNewKey {
 }
 
 impl WalletNewKey {
-    pub fn generate_random_alias() -> String {
-        let random_suffix: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(5)
-            .map(char::from)
-            .collect();
-
-        format!("lt-addr-{}", random_suffix)
+    pub fn generate_random_alias(fuzzer: &Fuzzer) -> String {
+        fuzzer.alias("alias", "lt-addr")
     }
 }
 
 #[async_trait(?Send)]
 impl Task for WalletNewKey {
     type P = WalletNewKeyParameters;
+
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new(WalletNewKeyStorageKeys::Alias, FieldType::Alias),
+            OutputField::new(WalletNewKeyStorageKeys::PublicKey, FieldType::PublicKey),
+            OutputField::new(WalletNewKeyStorageKeys::Address, FieldType::Address),
+            OutputField::new(WalletNewKeyStorageKeys::PrivateKey, FieldType::PrivateKey),
+            OutputField::new(
+                WalletNewKeyStorageKeys::PaymentAddress,
+                FieldType::PaymentAddress,
+            ),
+            OutputField::new(WalletNewKeyStorageKeys::SpendingKey, FieldType::SpendingKey),
+        ])
+    }
     type B = Bond; // just a placeholder
 
     async fn execute(
@@ -161,13 +172,15 @@ pub struct WalletNewKeyParameters {
 impl TaskParam for WalletNewKeyParameters {
     type D = WalletNewKeyParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, _state: &Storage) -> Option<Self> {
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
         let alias = match dto.alias {
-            Value::Ref { .. } => unimplemented!(),
-            Value::Value { value } => value.to_string(),
-            Value::Fuzz { .. } => WalletNewKey::generate_random_alias(),
+            Value::Fuzz { .. } => Some(WalletNewKey::generate_random_alias(&state.fuzzer)),
+            value => Resolver::new(state).string("alias", value)?,
+        };
+        let Some(alias) = alias else {
+            return Ok(None);
         };
 
-        Some(WalletNewKeyParameters { alias })
+        Ok(Some(WalletNewKeyParameters { alias }))
     }
 }
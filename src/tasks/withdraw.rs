@@ -1,16 +1,22 @@
 use async_trait::async_trait;
-use namada_sdk::{args::Withdraw, error::TxSubmitError, signing::default_sign, Namada};
+use namada_sdk::{args::Withdraw, Namada};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    entity::address::AccountIndentifier,
     scenario::StepResult,
     sdk::namada::Sdk,
-    state::state::{StepStorage, Storage},
-    utils::{settings::TxSettings, value::Value},
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    utils::{
+        settings::TxSettings,
+        value::{ParameterError, Resolver, Value},
+    },
 };
 
-use super::{Task, TaskError, TaskParam};
+use super::{BuiltTx, Task, TaskError, TaskParam};
 
 pub enum TxWithdrawStorageKeys {
     SourceAddress,
@@ -40,13 +46,30 @@ impl Task for TxWithdraw {
     type P = TxWithdrawParameters;
     type B = Withdraw;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::with_tx_info(vec![
+            OutputField::new(TxWithdrawStorageKeys::SourceAddress, FieldType::Address),
+            OutputField::new(TxWithdrawStorageKeys::ValidatorAddress, FieldType::Address),
+        ])
+    }
+
     async fn execute(
         &self,
         sdk: &Sdk,
         parameters: Self::P,
-        _: TxSettings,
+        settings: TxSettings,
         _state: &Storage,
     ) -> Result<StepResult, TaskError> {
+        self.build_and_submit(sdk, parameters, settings).await
+    }
+
+    async fn build_tx(
+        &self,
+        sdk: &Sdk,
+        parameters: Self::P,
+        _settings: TxSettings,
+        outputs: &mut StepStorage,
+    ) -> Result<Option<BuiltTx>, TaskError> {
         let source_address = parameters.source.to_namada_address(sdk).await;
         let validator_address = parameters.validator.to_namada_address(sdk).await;
 
@@ -55,55 +78,25 @@ impl Task for TxWithdraw {
             .new_withdraw(validator_address.clone())
             .source(source_address.clone());
 
-        let (mut withdraw_tx, signing_data) = withdraw_tx_builder
+        let (withdraw_tx, signing_data) = withdraw_tx_builder
             .build(&sdk.namada)
             .await
             .map_err(|e| TaskError::Build(e.to_string()))?;
 
-        sdk.namada
-            .sign(
-                &mut withdraw_tx,
-                &withdraw_tx_builder.tx,
-                signing_data,
-                default_sign,
-                (),
-            )
-            .await
-            .expect("unable to sign tx");
-
-        let tx = sdk
-            .namada
-            .submit(withdraw_tx.clone(), &withdraw_tx_builder.tx)
-            .await;
-
-        let mut storage = StepStorage::default();
-        self.fetch_info(sdk, &mut storage).await;
-
-        if Self::is_tx_rejected(&withdraw_tx, &tx) {
-            match tx {
-                Ok(tx) => {
-                    let errors = Self::get_tx_errors(&withdraw_tx, &tx).unwrap_or_default();
-                    return Ok(StepResult::fail(errors));
-                }
-                Err(e) => match e {
-                    namada_sdk::error::Error::Tx(TxSubmitError::AppliedTimeout) => {
-                        return Err(TaskError::Timeout)
-                    }
-                    _ => return Ok(StepResult::fail(e.to_string())),
-                },
-            }
-        }
-
-        storage.add(
+        outputs.add(
             TxWithdrawStorageKeys::ValidatorAddress.to_string(),
             validator_address.to_string(),
         );
-        storage.add(
+        outputs.add(
             TxWithdrawStorageKeys::SourceAddress.to_string(),
             source_address.to_string(),
         );
 
-        Ok(StepResult::success(storage))
+        Ok(Some(BuiltTx {
+            tx: withdraw_tx,
+            signing_data,
+            args: withdraw_tx_builder.tx,
+        }))
     }
 }
 
@@ -122,54 +115,16 @@ pub struct TxWithdrawParameters {
 impl TaskParam for TxWithdrawParameters {
     type D = TxWithdrawParametersDto;
 
-    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Option<Self> {
-        let source = match dto.source {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+    fn parameter_from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(source) = resolver.account("source", dto.source)? else {
+            return Ok(None);
         };
-        let validator = match dto.validator {
-            Value::Ref { value, field } => {
-                let was_step_successful = state.is_step_successful(&value);
-                if !was_step_successful {
-                    return None;
-                }
-                let data = state.get_step_item(&value, &field);
-                match field.to_lowercase().as_str() {
-                    "alias" => AccountIndentifier::Alias(data),
-                    "public-key" => AccountIndentifier::PublicKey(data),
-                    "state" => AccountIndentifier::StateAddress(state.get_address(&data)),
-                    _ => AccountIndentifier::Address(data),
-                }
-            }
-            Value::Value { value } => {
-                if value.starts_with(ADDRESS_PREFIX) {
-                    AccountIndentifier::Address(value)
-                } else {
-                    AccountIndentifier::Alias(value)
-                }
-            }
-            Value::Fuzz { .. } => unimplemented!(),
+        let Some(validator) = resolver.account("validator", dto.validator)? else {
+            return Ok(None);
         };
 
-        Some(Self { source, validator })
+        Ok(Some(Self { source, validator }))
     }
 }
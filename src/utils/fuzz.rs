@@ -0,0 +1,127 @@
+use std::cell::{RefCell, RefMut};
+
+use indexmap::IndexMap;
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Source of randomness for fuzzed parameters and generated aliases.
+///
+/// The rng is reseeded from the seed of the run, the worker and the step
+/// before each run of a step, so the values a step picks don't depend on the
+/// order steps ran in and rerunning with the same `--seed` picks the same
+/// values. Every value picked is recorded for the reports.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Fuzzer {
+    seed: u64,
+    worker_id: u64,
+    // times each step started, so every iteration of a repeat block differs
+    runs: RefCell<IndexMap<u64, u64>>,
+    #[serde(skip, default = "random_rng")]
+    rng: RefCell<StdRng>,
+    #[serde(skip)]
+    values: RefCell<IndexMap<String, String>>,
+}
+
+impl Default for Fuzzer {
+    fn default() -> Self {
+        Self::new(rand::thread_rng().gen(), 0)
+    }
+}
+
+impl Fuzzer {
+    pub fn new(seed: u64, worker_id: u64) -> Self {
+        Self {
+            seed,
+            worker_id,
+            runs: Default::default(),
+            rng: RefCell::new(StdRng::seed_from_u64(mix(seed, worker_id))),
+            values: Default::default(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn worker_id(&self) -> u64 {
+        self.worker_id
+    }
+
+    /// Reseeds the rng for the next run of `step_id`.
+    pub fn start(&self, step_id: u64) {
+        let mut runs = self.runs.borrow_mut();
+        let run = runs.entry(step_id).or_default();
+        *run += 1;
+
+        let seed = mix(mix(mix(self.seed, self.worker_id), step_id), *run);
+        *self.rng.borrow_mut() = StdRng::seed_from_u64(seed);
+        self.values.borrow_mut().clear();
+    }
+
+    pub fn rng(&self) -> RefMut<'_, StdRng> {
+        self.rng.borrow_mut()
+    }
+
+    /// Records the value picked for `parameter`.
+    pub fn record(&self, parameter: &str, value: impl ToString) {
+        self.values
+            .borrow_mut()
+            .insert(parameter.to_string(), value.to_string());
+    }
+
+    /// Picks a value for `parameter` with the rng and records it.
+    pub fn pick<T: ToString>(&self, parameter: &str, pick: impl FnOnce(&mut StdRng) -> T) -> T {
+        let value = pick(&mut self.rng());
+        self.record(parameter, value.to_string());
+        value
+    }
+
+    /// Picks an alias made of `prefix` and a random suffix.
+    pub fn alias(&self, parameter: &str, prefix: &str) -> String {
+        self.pick(parameter, |rng| {
+            let random_suffix: String = rng
+                .sample_iter(&Alphanumeric)
+                .take(5)
+                .map(char::from)
+                .collect();
+
+            format!("{}-{}", prefix, random_suffix)
+        })
+    }
+
+    /// The values picked since the step started.
+    pub fn take_values(&self) -> IndexMap<String, String> {
+        self.values.take()
+    }
+}
+
+fn random_rng() -> RefCell<StdRng> {
+    RefCell::new(StdRng::seed_from_u64(rand::thread_rng().gen()))
+}
+
+/// Derives a seed from `seed` and `value`. The seed is scrambled before
+/// `value` is combined with it, so that nearby pairs like `(seed + 1, value)`
+/// and `(seed, value + 1)` give independent seeds.
+pub fn mix(seed: u64, value: u64) -> u64 {
+    splitmix(splitmix(seed) ^ value)
+}
+
+// splitmix64 finalizer
+fn splitmix(z: u64) -> u64 {
+    let mut z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mix() {
+        assert_ne!(mix(mix(7, 1), 2), mix(mix(7, 2), 1));
+        assert_ne!(mix(8, 1), mix(7, 2));
+        assert_eq!(mix(7, 2), mix(7, 2));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod fuzz;
 pub mod misc;
 pub mod settings;
 pub mod value;
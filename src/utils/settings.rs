@@ -1,3 +1,9 @@
+use namada_sdk::{
+    address::Address,
+    args::{self, TxExpiration},
+    time::DateTimeUtc,
+    token::{self, DenominatedAmount, Denomination},
+};
 use serde::{Deserialize, Serialize};
 
 use crate::entity::address::AccountIndentifier;
@@ -7,15 +13,21 @@ use super::value::Value;
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TxSettingsDto {
     #[serde(rename = "broadcast-only")]
-    pub broadcast_only: Option<bool>,
+    pub broadcast_only: Option<Value>,
     #[serde(rename = "gas-token")]
     pub gas_token: Option<Value>,
+    #[serde(rename = "gas-price")]
+    pub gas_price: Option<Value>,
+    #[serde(rename = "fee-amount")]
+    pub fee_amount: Option<Value>,
     #[serde(rename = "gas-payer")]
     pub gas_payer: Option<Value>,
     #[serde(rename = "signers")]
     pub signers: Option<Vec<Value>>,
     #[serde(rename = "expiration")]
     pub expiration: Option<Value>,
+    #[serde(rename = "expiration-offset")]
+    pub expiration_offset: Option<Value>,
     #[serde(rename = "gas-limit")]
     pub gas_limit: Option<Value>,
 }
@@ -24,48 +36,78 @@ pub struct TxSettingsDto {
 pub struct TxSettings {
     pub broadcast_only: bool,
     pub gas_token: Option<AccountIndentifier>,
+    pub fee: Option<Fee>,
     pub gas_payer: Option<AccountIndentifier>,
     pub signers: Option<Vec<AccountIndentifier>>,
-    pub expiration: Option<u64>,
+    pub expiration: Option<Expiration>,
     pub gas_limit: Option<u64>,
 }
 
-impl TxSettingsDto {
-    pub fn from_dto(&self) -> TxSettings {
-        let broadcast_only = self.broadcast_only.unwrap_or(false);
-        let gas_token = match self.gas_token.clone() {
-            Some(Value::Value { value }) => Some(AccountIndentifier::Alias(value)),
-            _ => None,
-        };
-        let gas_payer = match self.gas_payer.clone() {
-            Some(Value::Value { value }) => Some(AccountIndentifier::Alias(value)),
-            _ => None,
-        };
-        let signers = self.signers.clone().map(|signers| {
-            signers
-                .into_iter()
-                .filter_map(|signer| match signer {
-                    Value::Value { value } => Some(AccountIndentifier::Alias(value)),
-                    _ => None,
-                })
-                .collect::<Vec<AccountIndentifier>>()
+/// What the gas payer pays for the tx, in the smallest unit of the gas token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fee {
+    /// price of a unit of gas
+    GasPrice(u64),
+    /// the whole fee, the gas price is the fee divided by the gas limit
+    Amount(u64),
+}
+
+/// When the tx expires, if it isn't applied by then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expiration {
+    /// unix timestamp in seconds
+    At(u64),
+    /// seconds after the tx is built
+    Offset(u64),
+}
+
+impl Expiration {
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            Expiration::At(timestamp) => *timestamp,
+            Expiration::Offset(offset) => {
+                let now = DateTimeUtc::now().0.timestamp().max(0) as u64;
+                now.saturating_add(*offset)
+            }
+        }
+    }
+}
+
+impl TxSettings {
+    /// Sets the fee token and amount, the expiration and the broadcast mode of
+    /// `tx`, the gas token and its denomination are resolved by the caller
+    /// (the native token if `None`). Applied after the gas limit, which a fee
+    /// amount is divided by.
+    pub fn apply(&self, tx: args::Tx, gas_token: Option<(Address, Denomination)>) -> args::Tx {
+        let fee_amount = self.fee.map(|fee| {
+            let gas_price = match fee {
+                Fee::GasPrice(gas_price) => gas_price,
+                Fee::Amount(amount) => amount.div_ceil(u64::from(tx.gas_limit).max(1)),
+            };
+            let gas_price = token::Amount::from_u64(gas_price);
+            args::InputAmount::Unvalidated(match &gas_token {
+                Some((_, denom)) => DenominatedAmount::new(gas_price, *denom),
+                None => DenominatedAmount::native(gas_price),
+            })
         });
-        let expiration = match self.expiration.clone() {
-            Some(Value::Value { value }) => Some(value.parse::<u64>().unwrap()),
-            _ => None,
-        };
-        let gas_limit = match self.gas_limit.clone() {
-            Some(Value::Value { value }) => Some(value.parse::<u64>().unwrap()),
-            _ => None,
+        let expiration = match self.expiration {
+            Some(expiration) => i64::try_from(expiration.timestamp())
+                .ok()
+                .and_then(DateTimeUtc::from_unix_timestamp)
+                .map(TxExpiration::Custom)
+                // too far in the future to be represented
+                .unwrap_or(TxExpiration::NoExpiration),
+            None => tx.expiration.clone(),
         };
 
-        TxSettings {
-            broadcast_only,
-            gas_token,
-            gas_payer,
-            signers,
+        args::Tx {
+            fee_token: gas_token
+                .map(|(token, _)| token)
+                .unwrap_or_else(|| tx.fee_token.clone()),
+            fee_amount: fee_amount.or_else(|| tx.fee_amount.clone()),
             expiration,
-            gas_limit,
+            broadcast_only: self.broadcast_only,
+            ..tx
         }
     }
 }
@@ -1,4 +1,19 @@
+use std::fmt::Display;
+
+use namada_sdk::{
+    string_encoding::{MASP_EXT_SPENDING_KEY_HRP, MASP_PAYMENT_ADDRESS_HRP},
+    token::NATIVE_MAX_DECIMAL_PLACES,
+};
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use thiserror::Error;
+
+use crate::{
+    entity::address::{AccountIndentifier, ADDRESS_PREFIX},
+    queries::validators::ValidatorsQueryStorageKeys,
+    state::state::Storage,
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
@@ -9,6 +24,8 @@ pub enum Value {
     Value { value: String },
     #[serde(rename = "fuzz")]
     Fuzz { value: Option<u64> },
+    #[serde(rename = "expr")]
+    Expr { op: Op, args: Vec<Value> },
 }
 
 impl Value {
@@ -24,3 +41,586 @@ impl Value {
         Self::Fuzz { value }
     }
 }
+
+/// Operators of a `Value::Expr`, applied left to right over its arguments.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Min,
+    Max,
+}
+
+impl Op {
+    /// `None` on overflow or, for `Sub`, underflow.
+    pub fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Sub => lhs.checked_sub(rhs),
+            Op::Mul => lhs.checked_mul(rhs),
+            Op::Min => Some(lhs.min(rhs)),
+            Op::Max => Some(lhs.max(rhs)),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Op::Add => write!(f, "add"),
+            Op::Sub => write!(f, "sub"),
+            Op::Mul => write!(f, "mul"),
+            Op::Min => write!(f, "min"),
+            Op::Max => write!(f, "max"),
+        }
+    }
+}
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error("invalid parameter `{parameter}`: {reason}")]
+pub struct ParameterError {
+    pub parameter: String,
+    pub reason: String,
+}
+
+impl ParameterError {
+    pub fn new(parameter: &str, reason: impl ToString) -> Self {
+        Self {
+            parameter: parameter.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn fuzz_unsupported(parameter: &str) -> Self {
+        Self::new(parameter, "fuzzing is not supported")
+    }
+
+    pub fn ref_unsupported(parameter: &str) -> Self {
+        Self::new(parameter, "references are not supported")
+    }
+
+    pub fn expr_unsupported(parameter: &str) -> Self {
+        Self::new(parameter, "expressions are not supported")
+    }
+}
+
+pub type Resolved<T> = Result<Option<T>, ParameterError>;
+
+/// Resolves step parameters against the results of the previous steps.
+///
+/// - a `Value::Value` is parsed into the requested type
+/// - a `Value::Ref` reads `field` from the result of step `value`. If that
+///   step failed or was a no-op the parameter resolves to `None` and the step
+///   using it is recorded as a no-op. Referencing a step or field that doesn't
+///   exist is a `ParameterError`.
+/// - a `Value::Fuzz` is step specific, parameters that support it must handle
+///   it before calling the resolver, otherwise it's a `ParameterError`
+/// - a `Value::Expr` is evaluated with checked u64 arithmetic for numeric
+///   parameters (numbers, amounts, epochs and strings). If any of its
+///   arguments resolves to `None` so does the expression, an overflow or
+///   underflow is a `ParameterError`
+///
+/// Amounts are in the smallest unit of the token, but a decimal amount of the
+/// native token, as stored by the queries (e.g. `10.000000`), is accepted too
+/// and converted to that unit, also as an operand of an expression.
+///
+/// Account references follow the field name: `alias`, `public-key` and
+/// `state` (an alias of an account created by the scenario) are resolved as
+/// such, any other field is read as an address.
+#[derive(Clone, Copy)]
+pub struct Resolver<'a> {
+    state: &'a Storage,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(state: &'a Storage) -> Self {
+        Self { state }
+    }
+
+    pub fn string(&self, parameter: &str, value: Value) -> Resolved<String> {
+        match value {
+            Value::Ref { value, field } => self.reference(parameter, value, &field),
+            Value::Value { value } => Ok(Some(value)),
+            Value::Fuzz { .. } => Err(ParameterError::fuzz_unsupported(parameter)),
+            Value::Expr { op, args } => Ok(self
+                .expression(parameter, op, args, Resolver::number)?
+                .map(|result| result.to_string())),
+        }
+    }
+
+    pub fn number(&self, parameter: &str, value: Value) -> Resolved<u64> {
+        if let Value::Expr { op, args } = value {
+            return self.expression(parameter, op, args, Resolver::number);
+        }
+        self.string(parameter, value)?
+            .map(|data| {
+                data.parse::<u64>()
+                    .map_err(|e| ParameterError::new(parameter, e))
+            })
+            .transpose()
+    }
+
+    pub fn amount(&self, parameter: &str, value: Value) -> Resolved<u64> {
+        if let Value::Expr { op, args } = value {
+            return self.expression(parameter, op, args, Resolver::amount);
+        }
+        self.string(parameter, value)?
+            .map(|data| parse_amount(&data).map_err(|e| ParameterError::new(parameter, e)))
+            .transpose()
+    }
+
+    /// Resolves an amount of `token`: decimal amounts are only accepted for the
+    /// native token, the amounts of other tokens must be in their smallest unit.
+    pub fn token_amount(
+        &self,
+        parameter: &str,
+        value: Value,
+        token: &AccountIndentifier,
+    ) -> Resolved<u64> {
+        if token.is_native_token() {
+            return self.amount(parameter, value);
+        }
+        if let Value::Expr { op, args } = value {
+            return self.expression(parameter, op, args, |resolver, parameter, value| {
+                resolver.token_amount(parameter, value, token)
+            });
+        }
+        self.string(parameter, value)?
+            .map(|data| parse_raw_amount(&data).map_err(|e| ParameterError::new(parameter, e)))
+            .transpose()
+    }
+
+    pub fn epoch(&self, parameter: &str, value: Value) -> Resolved<u64> {
+        self.number(parameter, value)
+    }
+
+    pub fn bool(&self, parameter: &str, value: Value) -> Resolved<bool> {
+        self.string(parameter, value)?
+            .map(|data| {
+                data.parse::<bool>()
+                    .map_err(|e| ParameterError::new(parameter, e))
+            })
+            .transpose()
+    }
+
+    pub fn account(&self, parameter: &str, value: Value) -> Resolved<AccountIndentifier> {
+        match value {
+            Value::Ref { value, field } => {
+                let Some(data) = self.reference(parameter, value, &field)? else {
+                    return Ok(None);
+                };
+                let account = match field.to_lowercase().as_str() {
+                    "alias" => AccountIndentifier::Alias(data),
+                    "public-key" => AccountIndentifier::PublicKey(data),
+                    "state" => AccountIndentifier::StateAddress(
+                        self.state
+                            .get_address(&data)
+                            .map_err(|e| ParameterError::new(parameter, e))?,
+                    ),
+                    _ => AccountIndentifier::Address(data),
+                };
+                Ok(Some(account))
+            }
+            Value::Value { value } => {
+                if value.starts_with(ADDRESS_PREFIX) {
+                    Ok(Some(AccountIndentifier::Address(value)))
+                } else {
+                    Ok(Some(AccountIndentifier::Alias(value)))
+                }
+            }
+            Value::Fuzz { .. } => Err(ParameterError::fuzz_unsupported(parameter)),
+            Value::Expr { .. } => Err(ParameterError::expr_unsupported(parameter)),
+        }
+    }
+
+    pub fn spending_key(&self, parameter: &str, value: Value) -> Resolved<AccountIndentifier> {
+        self.shielded(
+            parameter,
+            value,
+            MASP_EXT_SPENDING_KEY_HRP,
+            AccountIndentifier::SpendingKey,
+        )
+    }
+
+    pub fn payment_address(&self, parameter: &str, value: Value) -> Resolved<AccountIndentifier> {
+        self.shielded(
+            parameter,
+            value,
+            MASP_PAYMENT_ADDRESS_HRP,
+            AccountIndentifier::PaymentAddress,
+        )
+    }
+
+    /// Picks a random validator from the result of a `query-validators` step.
+    pub fn random_validator(
+        &self,
+        parameter: &str,
+        step_id: Option<u64>,
+    ) -> Resolved<AccountIndentifier> {
+        let Some(validators) = self.validators(parameter, step_id)? else {
+            return Ok(None);
+        };
+        self.choose_validator(parameter, &validators)
+            .map(Some)
+            .ok_or_else(|| ParameterError::new(parameter, "no validator to pick from"))
+    }
+
+    /// Same as `random_validator` but never picks `other`, resolves to
+    /// `Some(None)` if there is no other validator.
+    pub fn random_validator_except(
+        &self,
+        parameter: &str,
+        step_id: Option<u64>,
+        other: &AccountIndentifier,
+    ) -> Resolved<Option<AccountIndentifier>> {
+        let Some(validators) = self.validators(parameter, step_id)? else {
+            return Ok(None);
+        };
+        let validators = validators
+            .into_iter()
+            .filter(|validator| validator != other)
+            .collect::<Vec<_>>();
+        Ok(Some(self.choose_validator(parameter, &validators)))
+    }
+
+    /// Resolves a parameter that can be omitted, `Some(None)` if it was.
+    pub fn optional<T>(
+        &self,
+        parameter: &str,
+        value: Option<Value>,
+        resolve: impl Fn(&Self, &str, Value) -> Resolved<T>,
+    ) -> Resolved<Option<T>> {
+        match value {
+            Some(value) => Ok(resolve(self, parameter, value)?.map(Some)),
+            None => Ok(Some(None)),
+        }
+    }
+
+    /// Resolves every entry of a list, errors are reported as `parameter[index]`.
+    /// The list resolves to `None` if any of its entries does.
+    pub fn list<T>(
+        &self,
+        parameter: &str,
+        values: Vec<Value>,
+        resolve: impl Fn(&Self, &str, Value) -> Resolved<T>,
+    ) -> Resolved<Vec<T>> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| resolve(self, &format!("{}[{}]", parameter, index), value))
+            .collect()
+    }
+
+    /// Replaces the values in serialized step parameters with what they
+    /// resolve to, for the reports. A reference to a failed or no-op step
+    /// resolves to `null`, fuzzed values are left as they are since they are
+    /// only generated when the step runs.
+    pub fn parameters(&self, json: Json) -> Json {
+        match json {
+            Json::Object(map) => match serde_json::from_value(Json::Object(map.clone())) {
+                Ok(Value::Value { value }) => Json::String(value),
+                Ok(value @ (Value::Ref { .. } | Value::Expr { .. })) => {
+                    match self.string("", value) {
+                        Ok(Some(value)) => Json::String(value),
+                        Ok(None) => Json::Null,
+                        Err(e) => Json::String(format!("invalid: {}", e.reason)),
+                    }
+                }
+                _ => Json::Object(
+                    map.into_iter()
+                        .map(|(key, value)| (key, self.parameters(value)))
+                        .collect(),
+                ),
+            },
+            Json::Array(values) => Json::Array(
+                values
+                    .into_iter()
+                    .map(|value| self.parameters(value))
+                    .collect(),
+            ),
+            json => json,
+        }
+    }
+
+    // picked with the fuzzer of the run so the same seed picks the same validator
+    fn choose_validator(
+        &self,
+        parameter: &str,
+        validators: &[AccountIndentifier],
+    ) -> Option<AccountIndentifier> {
+        let validator = validators.choose(&mut *self.state.fuzzer.rng()).cloned();
+        if let Some(AccountIndentifier::Address(address)) = &validator {
+            self.state.fuzzer.record(parameter, address);
+        }
+        validator
+    }
+
+    fn validators(
+        &self,
+        parameter: &str,
+        step_id: Option<u64>,
+    ) -> Resolved<Vec<AccountIndentifier>> {
+        let step_id = step_id.ok_or_else(|| {
+            ParameterError::new(
+                parameter,
+                "fuzzing requires the id of a query-validators step",
+            )
+        })?;
+        let Some(total_validators) = self.reference(
+            parameter,
+            step_id,
+            &ValidatorsQueryStorageKeys::TotalValidator.to_string(),
+        )?
+        else {
+            return Ok(None);
+        };
+        let total_validators = total_validators
+            .parse::<u64>()
+            .map_err(|e| ParameterError::new(parameter, e))?;
+
+        (0..total_validators)
+            .map(|index| {
+                self.state
+                    .get_step_item(
+                        &step_id,
+                        &ValidatorsQueryStorageKeys::Validator(index).to_string(),
+                    )
+                    .map(AccountIndentifier::Address)
+                    .map_err(|e| ParameterError::new(parameter, e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
+    fn expression(
+        &self,
+        parameter: &str,
+        op: Op,
+        args: Vec<Value>,
+        operand: impl Fn(&Self, &str, Value) -> Resolved<u64>,
+    ) -> Resolved<u64> {
+        let Some(operands) = self.list(parameter, args, operand)? else {
+            return Ok(None);
+        };
+        let mut operands = operands.into_iter();
+        let first = operands.next().ok_or_else(|| {
+            ParameterError::new(parameter, format!("{} expression has no arguments", op))
+        })?;
+        operands
+            .try_fold(first, |result, operand| op.apply(result, operand))
+            .map(Some)
+            .ok_or_else(|| {
+                ParameterError::new(parameter, format!("{} expression is out of range", op))
+            })
+    }
+
+    fn reference(&self, parameter: &str, step_id: u64, field: &str) -> Resolved<String> {
+        let was_step_successful = self
+            .state
+            .is_step_successful(&step_id)
+            .map_err(|e| ParameterError::new(parameter, e))?;
+        if !was_step_successful {
+            return Ok(None);
+        }
+        self.state
+            .get_step_item(&step_id, field)
+            .map(Some)
+            .map_err(|e| ParameterError::new(parameter, e))
+    }
+
+    fn shielded(
+        &self,
+        parameter: &str,
+        value: Value,
+        prefix: &str,
+        key: fn(String) -> AccountIndentifier,
+    ) -> Resolved<AccountIndentifier> {
+        match value {
+            Value::Ref { value, field } => {
+                let data = self.reference(parameter, value, &field)?;
+                Ok(data.map(|data| match field.to_lowercase().as_str() {
+                    "alias" => AccountIndentifier::Alias(data),
+                    _ => key(data),
+                }))
+            }
+            Value::Value { value } => {
+                if value.starts_with(prefix) {
+                    Ok(Some(key(value)))
+                } else {
+                    Ok(Some(AccountIndentifier::Alias(value)))
+                }
+            }
+            Value::Fuzz { .. } => Err(ParameterError::fuzz_unsupported(parameter)),
+            Value::Expr { .. } => Err(ParameterError::expr_unsupported(parameter)),
+        }
+    }
+}
+
+/// Parses an amount in the smallest unit of the token, or a decimal amount of
+/// the native token as written by `to_string_native`.
+pub fn parse_amount(data: &str) -> Result<u64, String> {
+    let Some((whole, fraction)) = data.split_once('.') else {
+        return data.parse::<u64>().map_err(|e| e.to_string());
+    };
+    let decimals = NATIVE_MAX_DECIMAL_PLACES as usize;
+    if fraction.len() > decimals || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!(
+            "{} isn't an amount with at most {} decimals",
+            data, decimals
+        ));
+    }
+    let whole = whole.parse::<u64>().map_err(|e| e.to_string())?;
+    let fraction = format!("{:0<width$}", fraction, width = decimals)
+        .parse::<u64>()
+        .map_err(|e| e.to_string())?;
+    whole
+        .checked_mul(10u64.pow(decimals as u32))
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(|| format!("{} is out of range", data))
+}
+
+/// Parses an amount in the smallest unit of a token whose denomination isn't
+/// known, rejecting decimal amounts instead of assuming the native one.
+pub fn parse_raw_amount(data: &str) -> Result<u64, String> {
+    if data.contains('.') {
+        return Err(format!(
+            "{} is a decimal amount, which is only supported for the native token",
+            data
+        ));
+    }
+    data.parse::<u64>().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::state::state::{StepOutcome, StepStorage};
+
+    use super::*;
+
+    fn storage() -> Storage {
+        let mut storage = Storage::default();
+        let mut data = StepStorage::default();
+        data.add("amount".to_string(), "10.000000".to_string());
+        data.add("epoch".to_string(), "7".to_string());
+        storage.save_step_outcome(0, StepOutcome::success());
+        storage.save_step_state(0, data);
+        storage.save_step_outcome(1, StepOutcome::fail("failed".to_string()));
+        storage.save_step_state(1, StepStorage::default());
+        storage
+    }
+
+    fn expr(op: Op, args: Vec<Value>) -> Value {
+        Value::Expr { op, args }
+    }
+
+    #[test]
+    fn test_expression() {
+        let state = storage();
+        let resolver = Resolver::new(&state);
+
+        let epoch = expr(
+            Op::Add,
+            vec![Value::r(0, "epoch".to_string()), Value::v("2".to_string())],
+        );
+        assert_eq!(resolver.epoch("epoch", epoch.clone()), Ok(Some(9)));
+        assert_eq!(resolver.string("epoch", epoch), Ok(Some("9".to_string())));
+
+        let amount = expr(
+            Op::Sub,
+            vec![
+                Value::r(0, "amount".to_string()),
+                Value::v("0.5".to_string()),
+                Value::v("1".to_string()),
+            ],
+        );
+        assert_eq!(resolver.amount("amount", amount), Ok(Some(9_499_999)));
+        assert_eq!(
+            resolver.amount("amount", Value::v("1.0000001".to_string())),
+            Err(ParameterError::new(
+                "amount",
+                "1.0000001 isn't an amount with at most 6 decimals"
+            ))
+        );
+
+        let token = AccountIndentifier::Alias("btc".to_string());
+        assert_eq!(
+            resolver.token_amount("amount", Value::v("5".to_string()), &token),
+            Ok(Some(5))
+        );
+        let amount = expr(
+            Op::Add,
+            vec![Value::r(0, "amount".to_string()), Value::v("1".to_string())],
+        );
+        assert_eq!(
+            resolver.token_amount("amount", amount.clone(), &token),
+            Err(ParameterError::new(
+                "amount",
+                "10.000000 is a decimal amount, which is only supported for the native token"
+            ))
+        );
+        let native = AccountIndentifier::Alias("nam".to_string());
+        assert_eq!(
+            resolver.token_amount("amount", amount, &native),
+            Ok(Some(10_000_001))
+        );
+
+        let min = expr(
+            Op::Min,
+            vec![Value::v("3".to_string()), Value::v("2".to_string())],
+        );
+        assert_eq!(resolver.number("number", min), Ok(Some(2)));
+    }
+
+    #[test]
+    fn test_expression_out_of_range() {
+        let state = storage();
+        let resolver = Resolver::new(&state);
+
+        let underflow = expr(
+            Op::Sub,
+            vec![Value::v("1".to_string()), Value::v("2".to_string())],
+        );
+        assert_eq!(
+            resolver.number("number", underflow),
+            Err(ParameterError::new(
+                "number",
+                "sub expression is out of range"
+            ))
+        );
+
+        let overflow = expr(
+            Op::Mul,
+            vec![Value::v(u64::MAX.to_string()), Value::v("2".to_string())],
+        );
+        assert_eq!(
+            resolver.amount("amount", overflow),
+            Err(ParameterError::new(
+                "amount",
+                "mul expression is out of range"
+            ))
+        );
+
+        assert_eq!(
+            resolver.number("number", expr(Op::Add, vec![])),
+            Err(ParameterError::new(
+                "number",
+                "add expression has no arguments"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_expression_propagates_none() {
+        let state = storage();
+        let resolver = Resolver::new(&state);
+
+        let amount = expr(
+            Op::Add,
+            vec![Value::v("1".to_string()), Value::r(1, "amount".to_string())],
+        );
+        assert_eq!(resolver.amount("amount", amount.clone()), Ok(None));
+        assert_eq!(resolver.string("amount", amount), Ok(None));
+    }
+}
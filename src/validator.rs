@@ -0,0 +1,634 @@
+use std::collections::HashMap;
+
+use serde_json::Value as Json;
+use thiserror::Error;
+
+use crate::{
+    scenario::{Scenario, Step, StepType},
+    utils::value::{ParameterError, Value},
+};
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum ValidationIssue {
+    #[error("step {step_id}: id is used by more than one step")]
+    DuplicateId { step_id: u64 },
+    #[error("step {step_id}: ids must be contiguous, expected {expected}")]
+    NonContiguousId { step_id: u64, expected: u64 },
+    #[error("step {step_id}: `{parameter}` references step {target} which does not exist")]
+    MissingStep {
+        step_id: u64,
+        parameter: String,
+        target: u64,
+    },
+    #[error("step {step_id}: `{parameter}` references step {target} which does not run before it")]
+    LaterStep {
+        step_id: u64,
+        parameter: String,
+        target: u64,
+    },
+    #[error(
+        "step {step_id}: `{parameter}` references step {target} which runs in parallel with it"
+    )]
+    ConcurrentStep {
+        step_id: u64,
+        parameter: String,
+        target: u64,
+    },
+    #[error("step {step_id}: {step_type} steps can't be nested in a parallel step")]
+    NestedInParallel { step_id: u64, step_type: String },
+    #[error("step {step_id}: {step_type} steps can't have a policy")]
+    PolicyOnControlFlow { step_id: u64, step_type: String },
+    #[error("step {step_id}: {step_type} steps don't send a tx and can't have an expectation")]
+    ExpectationOnNonTx { step_id: u64, step_type: String },
+    #[error("step {step_id}: {error}")]
+    InvalidExpectation { step_id: u64, error: ParameterError },
+    #[error("step {step_id}: `{parameter}` reads field `{field}` which step {target} ({step_type}) never writes")]
+    UnknownField {
+        step_id: u64,
+        parameter: String,
+        target: u64,
+        step_type: String,
+        field: String,
+    },
+    #[error("step {step_id}: `{parameter}` can't be fuzzed by {step_type}")]
+    UnsupportedFuzz {
+        step_id: u64,
+        parameter: String,
+        step_type: String,
+    },
+    #[error("step {step_id}: fuzzing `{parameter}` requires the id of a {expected} step")]
+    MissingFuzzStep {
+        step_id: u64,
+        parameter: String,
+        expected: String,
+    },
+    #[error("step {step_id}: fuzzing `{parameter}` requires step {target} to be a {expected} step, found {found}")]
+    InvalidFuzzStep {
+        step_id: u64,
+        parameter: String,
+        target: u64,
+        expected: String,
+        found: String,
+    },
+}
+
+enum FuzzSupport {
+    Random,
+    FromStep(&'static str),
+}
+
+/// Statically checks a scenario without connecting to a chain.
+pub fn validate(scenario: &Scenario) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    let mut positions = HashMap::new();
+
+    // steps nested in control flow steps follow the step containing them
+    let steps = Step::flatten(&scenario.steps);
+    for (index, step) in steps.iter().enumerate() {
+        if positions.contains_key(&step.id) {
+            issues.push(ValidationIssue::DuplicateId { step_id: step.id });
+        } else {
+            positions.insert(step.id, index);
+            if step.id != index as u64 {
+                issues.push(ValidationIssue::NonContiguousId {
+                    step_id: step.id,
+                    expected: index as u64,
+                });
+            }
+        }
+    }
+
+    let mut validator = Validator {
+        steps: steps.clone(),
+        positions,
+        enclosing: HashMap::new(),
+        parallel: HashMap::new(),
+    };
+    validator.collect_blocks(&scenario.steps, &mut vec![], None, &mut issues);
+    for (index, step) in steps.iter().enumerate() {
+        validator.validate_step(index, step, &mut issues);
+    }
+
+    issues
+}
+
+struct Validator<'a> {
+    steps: Vec<&'a Step>,
+    positions: HashMap<u64, usize>,
+    // control flow steps containing a step, which run after it
+    enclosing: HashMap<u64, Vec<u64>>,
+    // parallel step a step is directly nested in
+    parallel: HashMap<u64, u64>,
+}
+
+impl<'a> Validator<'a> {
+    fn collect_blocks(
+        &mut self,
+        steps: &[Step],
+        enclosing: &mut Vec<u64>,
+        parallel: Option<u64>,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        for step in steps {
+            self.enclosing.insert(step.id, enclosing.clone());
+            if step.policy.is_some() && step.config.is_control_flow() {
+                issues.push(ValidationIssue::PolicyOnControlFlow {
+                    step_id: step.id,
+                    step_type: step.config.to_string(),
+                });
+            }
+            if let Some(expect) = &step.expect {
+                // only the steps recording the result of a tx can check it
+                if step.config.outputs().field("rejected-vps").is_none() {
+                    issues.push(ValidationIssue::ExpectationOnNonTx {
+                        step_id: step.id,
+                        step_type: step.config.to_string(),
+                    });
+                }
+                if let Err(error) = expect.patterns() {
+                    issues.push(ValidationIssue::InvalidExpectation {
+                        step_id: step.id,
+                        error,
+                    });
+                }
+            }
+            if let Some(parallel) = parallel {
+                self.parallel.insert(step.id, parallel);
+                if step.config.is_control_flow() {
+                    issues.push(ValidationIssue::NestedInParallel {
+                        step_id: step.id,
+                        step_type: step.config.to_string(),
+                    });
+                }
+            }
+
+            let nested = step.config.nested_steps();
+            if !nested.is_empty() {
+                let parallel = match step.config {
+                    StepType::Parallel { .. } => Some(step.id),
+                    _ => None,
+                };
+                enclosing.push(step.id);
+                self.collect_blocks(nested, enclosing, parallel, issues);
+                enclosing.pop();
+            }
+        }
+    }
+
+    fn validate_step(&self, index: usize, step: &Step, issues: &mut Vec<ValidationIssue>) {
+        let mut config = serde_json::to_value(&step.config).expect("Step should be serializable.");
+        let parameters = match config.get_mut("parameters") {
+            Some(parameters) => parameters,
+            None => return,
+        };
+        // nested steps are validated on their own
+        if let Some(parameters) = parameters.as_object_mut() {
+            if step.config.is_control_flow() {
+                parameters.remove("steps");
+            }
+        }
+        let parameters = &*parameters;
+
+        let mut values = vec![];
+        collect_values(parameters, String::new(), &mut values);
+
+        for (parameter, value) in values {
+            match value {
+                Value::Ref { value, field } => {
+                    self.check_reference(index, step, &parameter, value, Some(&field), issues);
+                }
+                Value::Value { value } => {
+                    // check-step points at a step id through a plain value
+                    if let (StepType::CheckStepOutput { .. }, "id") =
+                        (&step.config, parameter.as_str())
+                    {
+                        if let Ok(target) = value.parse::<u64>() {
+                            self.check_reference(index, step, &parameter, target, None, issues);
+                        }
+                    }
+                }
+                Value::Fuzz { value } => {
+                    self.check_fuzz(index, step, &parameter, value, issues);
+                }
+                Value::Expr { args, .. } => {
+                    self.check_expression(index, step, &parameter, &args, issues);
+                }
+            }
+        }
+
+        // check-storage points at a step id and field outside of a `Value`
+        if let StepType::CheckStorage { .. } = step.config {
+            if let (Some(target), Some(field)) = (
+                parameters.get("step").and_then(Json::as_u64),
+                parameters.get("field").and_then(Json::as_str),
+            ) {
+                self.check_reference(index, step, "step", target, Some(field), issues);
+            }
+        }
+
+        // so do the conditions of control flow steps
+        for parameter in ["condition", "until"] {
+            if let Some(target) = parameters
+                .get(parameter)
+                .and_then(|condition| condition.get("step"))
+                .and_then(Json::as_u64)
+            {
+                let field = parameters[parameter].get("field").and_then(Json::as_str);
+                // `until` is checked after the block ran
+                let index = match parameter {
+                    "until" => index + Step::flatten(step.config.nested_steps()).len() + 1,
+                    _ => index,
+                };
+                self.check_reference(index, step, parameter, target, field, issues);
+            }
+        }
+    }
+
+    fn resolve_target(
+        &self,
+        index: usize,
+        step: &Step,
+        parameter: &str,
+        target: u64,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<&'a Step> {
+        match self.positions.get(&target) {
+            None => {
+                issues.push(ValidationIssue::MissingStep {
+                    step_id: step.id,
+                    parameter: parameter.to_string(),
+                    target,
+                });
+                None
+            }
+            Some(position)
+                if *position >= index
+                    || target == step.id
+                    || self.enclosing[&step.id].contains(&target) =>
+            {
+                issues.push(ValidationIssue::LaterStep {
+                    step_id: step.id,
+                    parameter: parameter.to_string(),
+                    target,
+                });
+                None
+            }
+            Some(_)
+                if self.parallel.contains_key(&step.id)
+                    && self.parallel.get(&step.id) == self.parallel.get(&target) =>
+            {
+                issues.push(ValidationIssue::ConcurrentStep {
+                    step_id: step.id,
+                    parameter: parameter.to_string(),
+                    target,
+                });
+                None
+            }
+            Some(position) => Some(self.steps[*position]),
+        }
+    }
+
+    fn check_reference(
+        &self,
+        index: usize,
+        step: &Step,
+        parameter: &str,
+        target: u64,
+        field: Option<&str>,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        let target_step = match self.resolve_target(index, step, parameter, target, issues) {
+            Some(target_step) => target_step,
+            None => return,
+        };
+        if let Some(field) = field {
+            if target_step.config.outputs().field(field).is_none() {
+                issues.push(ValidationIssue::UnknownField {
+                    step_id: step.id,
+                    parameter: parameter.to_string(),
+                    target,
+                    step_type: target_step.config.to_string(),
+                    field: field.to_string(),
+                });
+            }
+        }
+    }
+
+    fn check_expression(
+        &self,
+        index: usize,
+        step: &Step,
+        parameter: &str,
+        args: &[Value],
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        for (arg_index, arg) in args.iter().enumerate() {
+            let parameter = format!("{}[{}]", parameter, arg_index);
+            match arg {
+                Value::Ref { value, field } => {
+                    self.check_reference(index, step, &parameter, *value, Some(field), issues);
+                }
+                Value::Value { .. } => (),
+                // only literals and references can be combined
+                Value::Fuzz { .. } => issues.push(ValidationIssue::UnsupportedFuzz {
+                    step_id: step.id,
+                    parameter,
+                    step_type: step.config.to_string(),
+                }),
+                Value::Expr { args, .. } => {
+                    self.check_expression(index, step, &parameter, args, issues);
+                }
+            }
+        }
+    }
+
+    fn check_fuzz(
+        &self,
+        index: usize,
+        step: &Step,
+        parameter: &str,
+        target: Option<u64>,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        match fuzz_support(&step.config, parameter) {
+            None => issues.push(ValidationIssue::UnsupportedFuzz {
+                step_id: step.id,
+                parameter: parameter.to_string(),
+                step_type: step.config.to_string(),
+            }),
+            Some(FuzzSupport::Random) => (),
+            Some(FuzzSupport::FromStep(expected)) => {
+                let target = match target {
+                    Some(target) => target,
+                    None => {
+                        issues.push(ValidationIssue::MissingFuzzStep {
+                            step_id: step.id,
+                            parameter: parameter.to_string(),
+                            expected: expected.to_string(),
+                        });
+                        return;
+                    }
+                };
+                let target_step = match self.resolve_target(index, step, parameter, target, issues)
+                {
+                    Some(target_step) => target_step,
+                    None => return,
+                };
+                let found = target_step.config.to_string();
+                if found != expected {
+                    issues.push(ValidationIssue::InvalidFuzzStep {
+                        step_id: step.id,
+                        parameter: parameter.to_string(),
+                        target,
+                        expected: expected.to_string(),
+                        found,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Every `Value` in `json` along with the path of the parameter holding it.
+pub(crate) fn collect_values(json: &Json, path: String, values: &mut Vec<(String, Value)>) {
+    if let Ok(value) = serde_json::from_value::<Value>(json.clone()) {
+        values.push((path, value));
+        return;
+    }
+    match json {
+        Json::Object(map) => {
+            for (key, inner) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_values(inner, path, values);
+            }
+        }
+        Json::Array(items) => {
+            for (index, inner) in items.iter().enumerate() {
+                collect_values(inner, format!("{}[{}]", path, index), values);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn fuzz_support(step_type: &StepType, parameter: &str) -> Option<FuzzSupport> {
+    // an entry of a batch supports what the step it stands for does
+    if let StepType::TxBatch { parameters, .. } = step_type {
+        let (entry, parameter) = parameter
+            .strip_prefix("txs[")?
+            .split_once("].parameters.")?;
+        return fuzz_support(parameters.txs.get(entry.parse::<usize>().ok()?)?, parameter);
+    }
+
+    let parameter = parameter.split('[').next().unwrap_or(parameter);
+    match (step_type, parameter) {
+        (StepType::WalletNewKey { .. }, "alias")
+        | (StepType::InitAccount { .. }, "alias" | "threshold")
+        | (StepType::UpdateAccount { .. }, "threshold")
+        | (StepType::BecomeValidator { .. }, "commission_rate")
+        | (StepType::VoteProposal { .. }, "vote")
+        | (
+            StepType::ChangeMetadata { .. },
+            "email" | "avatar" | "description" | "discord_handle" | "website",
+        ) => Some(FuzzSupport::Random),
+        (StepType::Bond { .. }, "source" | "validator")
+        | (StepType::BondBatch { .. }, "sources" | "targets")
+        | (StepType::Redelegate { .. }, "dest_validator")
+        | (StepType::RedelegateBatch { .. }, "dest_validators") => {
+            Some(FuzzSupport::FromStep("query-validators"))
+        }
+        (StepType::VoteProposal { .. }, "proposal_id") => {
+            Some(FuzzSupport::FromStep("query-proposals"))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scenario(steps: serde_json::Value) -> Scenario {
+        serde_json::from_value(serde_json::json!({
+            "settings": { "retry_for": null },
+            "steps": steps
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_scenario() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "fuzz", "value": null } } } },
+            { "id": 1, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 0, "field": "alias" } } } }
+        ]));
+
+        assert!(validate(&scenario).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_scenario() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 1, "field": "alias" } } } },
+            { "id": 1, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "a" } } } },
+            { "id": 3, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 1, "field": "amount" } } } },
+            { "id": 3, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "fuzz", "value": null } } } }
+        ]));
+
+        let issues = validate(&scenario);
+
+        assert!(issues.contains(&ValidationIssue::LaterStep {
+            step_id: 0,
+            parameter: "source".to_string(),
+            target: 1
+        }));
+        assert!(issues.contains(&ValidationIssue::NonContiguousId {
+            step_id: 3,
+            expected: 2
+        }));
+        assert!(issues.contains(&ValidationIssue::DuplicateId { step_id: 3 }));
+        assert!(issues.contains(&ValidationIssue::UnknownField {
+            step_id: 3,
+            parameter: "source".to_string(),
+            target: 1,
+            step_type: "wallet-new-key".to_string(),
+            field: "amount".to_string()
+        }));
+        assert!(issues.contains(&ValidationIssue::UnsupportedFuzz {
+            step_id: 3,
+            parameter: "source".to_string(),
+            step_type: "tx-reveal-pk".to_string()
+        }));
+    }
+
+    #[test]
+    fn test_expression_references() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "query-balance", "parameters": { "address": { "type": "value", "value": "a" }, "token": { "type": "value", "value": "nam" } } } },
+            { "id": 1, "config": { "type": "check-balance", "parameters": {
+                "amount": { "type": "expr", "op": "sub", "args": [
+                    { "type": "ref", "value": 0, "field": "amount" },
+                    { "type": "expr", "op": "add", "args": [{ "type": "value", "value": "1" }, { "type": "ref", "value": 2, "field": "amount" }] }
+                ] },
+                "address": { "type": "value", "value": "a" },
+                "token": { "type": "value", "value": "nam" },
+                "op": { "type": "value", "value": "ge" }
+            } } }
+        ]));
+
+        assert_eq!(
+            validate(&scenario),
+            vec![ValidationIssue::MissingStep {
+                step_id: 1,
+                parameter: "amount[1][1]".to_string(),
+                target: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_control_flow() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "a" } } } },
+            { "id": 1, "config": { "type": "repeat", "parameters": {
+                "until": { "type": "succeeded", "step": 2 },
+                "steps": [
+                    { "id": 2, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 1, "field": "iterations" } } } }
+                ]
+            } } },
+            { "id": 3, "config": { "type": "parallel", "parameters": { "steps": [
+                { "id": 4, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 0, "field": "alias" } } } },
+                { "id": 5, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 4, "field": "public-key" } } } }
+            ] } } }
+        ]));
+
+        assert_eq!(
+            validate(&scenario),
+            vec![
+                ValidationIssue::LaterStep {
+                    step_id: 2,
+                    parameter: "source".to_string(),
+                    target: 1
+                },
+                ValidationIssue::ConcurrentStep {
+                    step_id: 5,
+                    parameter: "source".to_string(),
+                    target: 4
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tx_batch() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "a" } } } },
+            { "id": 1, "config": { "type": "tx-batch", "parameters": { "txs": [
+                { "type": "tx-bond", "parameters": { "source": { "type": "fuzz", "value": null }, "validator": { "type": "value", "value": "v" }, "amount": { "type": "value", "value": "1" } } },
+                { "type": "tx-transparent-transfer", "parameters": { "source": { "type": "ref", "value": 0, "field": "alias" }, "target": { "type": "value", "value": "b" }, "amount": { "type": "value", "value": "1" }, "token": { "type": "value", "value": "nam" } } }
+            ] } } },
+            { "id": 2, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 1, "field": "tx-1-source" } } } },
+            { "id": 3, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 1, "field": "tx-1-amount-0" } } } }
+        ]));
+
+        assert_eq!(
+            validate(&scenario),
+            vec![
+                ValidationIssue::MissingFuzzStep {
+                    step_id: 1,
+                    parameter: "txs[0].parameters.source".to_string(),
+                    expected: "query-validators".to_string()
+                },
+                ValidationIssue::UnknownField {
+                    step_id: 3,
+                    parameter: "source".to_string(),
+                    target: 1,
+                    step_type: "tx-batch".to_string(),
+                    field: "tx-1-amount-0".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_policy() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "a" } } }, "policy": { "timeout": 60, "retries": 2, "retry_on": ["timeout", "vp-rejection"] } },
+            { "id": 1, "config": { "type": "parallel", "parameters": { "steps": [
+                { "id": 2, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 0, "field": "alias" } } } }
+            ] } }, "policy": { "retries": 1 } }
+        ]));
+
+        assert_eq!(
+            validate(&scenario),
+            vec![ValidationIssue::PolicyOnControlFlow {
+                step_id: 1,
+                step_type: "parallel".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_expectation() {
+        let scenario = scenario(serde_json::json!([
+            { "id": 0, "config": { "type": "wallet-new-key", "parameters": { "alias": { "type": "value", "value": "a" } } }, "expect": { "result": "success" } },
+            { "id": 1, "config": { "type": "reveal-pk", "parameters": { "source": { "type": "ref", "value": 0, "field": "alias" } } }, "expect": { "result": "vp-rejection", "vp": "[" } }
+        ]));
+
+        let issues = validate(&scenario);
+
+        assert_eq!(issues.len(), 2);
+        assert!(issues.contains(&ValidationIssue::ExpectationOnNonTx {
+            step_id: 0,
+            step_type: "wallet-new-key".to_string()
+        }));
+        assert!(matches!(
+            &issues[1],
+            ValidationIssue::InvalidExpectation { step_id: 1, error } if error.parameter == "expect.vp"
+        ));
+    }
+}
@@ -5,7 +5,12 @@ use namada_sdk::rpc;
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage, utils::value::Value};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::state::Storage,
+    utils::value::{ParameterError, Resolver, Value},
+};
 
 use super::{Wait, WaitParam};
 
@@ -23,12 +28,8 @@ impl Wait for EpochWait {
     type P = EpochWaitParameters;
 
     async fn execute(&self, sdk: &Sdk, paramaters: Self::P, _state: &Storage) -> StepResult {
-        let start = paramaters.from;
-        let to = paramaters.to;
-        let r#for = paramaters.r#for;
-
-        match (start, r#for, to) {
-            (Some(start), Some(r#for), None) => {
+        match paramaters {
+            EpochWaitParameters::For { from: start, r#for } => {
                 let _epoch = rpc::query_epoch(&sdk.namada.clone_client()).await;
 
                 let to_epoch = start + r#for;
@@ -38,7 +39,7 @@ impl Wait for EpochWait {
 
                     let current_epoch = match epoch {
                         Ok(epoch) => epoch,
-                        Err(e) => return StepResult::fail(e.to_string()),
+                        Err(e) => return StepResult::fail_rpc(e.to_string()),
                     };
 
                     if current_epoch.0 >= to_epoch {
@@ -48,12 +49,12 @@ impl Wait for EpochWait {
                     }
                 }
             }
-            (None, None, Some(to)) => loop {
+            EpochWaitParameters::To(to) => loop {
                 let epoch = rpc::query_epoch(&sdk.namada.clone_client()).await;
 
                 let current_epoch = match epoch {
                     Ok(epoch) => epoch,
-                    Err(e) => return StepResult::fail(e.to_string()),
+                    Err(e) => return StepResult::fail_rpc(e.to_string()),
                 };
 
                 if current_epoch.0 >= to {
@@ -62,7 +63,6 @@ impl Wait for EpochWait {
                     sleep(Duration::from_secs(10)).await
                 }
             },
-            (_, _, _) => unimplemented!(),
         };
 
         StepResult::success_empty()
@@ -77,36 +77,39 @@ pub struct EpochWaitParametersDto {
 }
 
 #[derive(Clone, Debug)]
-pub struct EpochWaitParameters {
-    pub from: Option<u64>,
-    pub r#for: Option<u64>,
-    pub to: Option<u64>,
+pub enum EpochWaitParameters {
+    /// `for` epochs after `from`
+    For {
+        from: u64,
+        r#for: u64,
+    },
+    To(u64),
 }
 
 impl WaitParam for EpochWaitParameters {
     type D = EpochWaitParametersDto;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self {
-        let from = dto.from.map(|from| match from {
-            Value::Ref { value, field } => {
-                state.get_step_item(&value, &field).parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        let r#for = dto.r#for.map(|r#for| match r#for {
-            Value::Ref { .. } => unimplemented!(),
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
-        let to = dto.to.map(|to| match to {
-            Value::Ref { value, field } => {
-                state.get_step_item(&value, &field).parse::<u64>().unwrap()
-            }
-            Value::Value { value } => value.parse::<u64>().unwrap(),
-            Value::Fuzz { .. } => unimplemented!(),
-        });
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        let Some(from) = resolver.optional("from", dto.from, Resolver::epoch)? else {
+            return Ok(None);
+        };
+        let r#for = match dto.r#for {
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("for")),
+            value => resolver.optional("for", value, Resolver::number)?.flatten(),
+        };
+        let Some(to) = resolver.optional("to", dto.to, Resolver::epoch)? else {
+            return Ok(None);
+        };
 
-        Self { from, to, r#for }
+        match (from, r#for, to) {
+            (Some(from), Some(r#for), None) => Ok(Some(Self::For { from, r#for })),
+            (None, None, Some(to)) => Ok(Some(Self::To(to))),
+            _ => Err(ParameterError::new(
+                "to",
+                "either `from` and `for`, or only `to`, must be set",
+            )),
+        }
     }
 }
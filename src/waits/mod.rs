@@ -1,14 +1,24 @@
 use async_trait::async_trait;
 
-use crate::{scenario::StepResult, sdk::namada::Sdk, state::state::Storage};
+use crate::{
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::{schema::OutputSchema, state::Storage},
+    utils::value::ParameterError,
+};
 
 pub mod epoch;
 pub mod height;
+pub mod tx;
 
 #[async_trait(?Send)]
 pub trait Wait {
     type P: WaitParam;
 
+    fn outputs() -> OutputSchema {
+        OutputSchema::default()
+    }
+
     async fn execute(&self, sdk: &Sdk, paramaters: Self::P, state: &Storage) -> StepResult;
 
     async fn run(
@@ -16,15 +26,19 @@ pub trait Wait {
         sdk: &Sdk,
         dto: <<Self as Wait>::P as WaitParam>::D,
         state: &Storage,
-    ) -> StepResult {
-        let parameters = Self::P::from_dto(dto, state);
-
-        self.execute(sdk, parameters, state).await
+    ) -> Result<StepResult, ParameterError> {
+        let parameters = if let Some(parameters) = Self::P::from_dto(dto, state)? {
+            parameters
+        } else {
+            return Ok(StepResult::no_op());
+        };
+
+        Ok(self.execute(sdk, parameters, state).await)
     }
 }
 
-pub trait WaitParam {
+pub trait WaitParam: Sized {
     type D;
 
-    fn from_dto(dto: Self::D, state: &Storage) -> Self;
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError>;
 }
@@ -0,0 +1,221 @@
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use namada_sdk::{
+    hash::Hash,
+    rpc::{self, TxEventQuery, TxResponse},
+    tx::either,
+};
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::{
+    policy::FailureKind,
+    scenario::StepResult,
+    sdk::namada::Sdk,
+    state::{
+        schema::{FieldType, OutputField, OutputSchema},
+        state::{StepStorage, Storage},
+    },
+    tasks::tx_info::{InnerTxResult, TxStorageKeys},
+    utils::value::{ParameterError, Resolver, Value},
+};
+
+use super::{Wait, WaitParam};
+
+/// How long to wait for the tx to be applied if the step doesn't say
+const DEFAULT_TIMEOUT: u64 = 120;
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, Default)]
+pub struct TxWait {}
+
+impl TxWait {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait(?Send)]
+impl Wait for TxWait {
+    type P = TxWaitParameters;
+
+    fn outputs() -> OutputSchema {
+        OutputSchema::new(vec![
+            OutputField::new("status", FieldType::Text),
+            OutputField::new(TxStorageKeys::InclusionHeight, FieldType::Height),
+            OutputField::new(TxStorageKeys::VpErrors, FieldType::Text),
+            OutputField::new(TxStorageKeys::RejectedVps, FieldType::Text),
+            OutputField::new(TxStorageKeys::GasUsed, FieldType::Number),
+            OutputField::new("inner-tx-{}-status", FieldType::Text),
+            OutputField::new("inner-tx-{}-vp-errors", FieldType::Text),
+            OutputField::new("inner-tx-{}-rejected-vps", FieldType::Text),
+            OutputField::new("inner-tx-{}-status-flags", FieldType::Text),
+            OutputField::new("inner-tx-{}-events", FieldType::Text),
+        ])
+    }
+
+    async fn execute(&self, sdk: &Sdk, paramaters: Self::P, _state: &Storage) -> StepResult {
+        let deadline = Instant::now() + Duration::from_secs(paramaters.timeout);
+
+        let response = loop {
+            let event = rpc::query_tx_events(
+                &sdk.namada.clone_client(),
+                TxEventQuery::Applied(&paramaters.wrapper_hash),
+            )
+            .await;
+
+            match event {
+                Ok(Some(event)) => match TxResponse::try_from(event) {
+                    Ok(response) => break response,
+                    Err(e) => return StepResult::fail_rpc(e.to_string()),
+                },
+                Ok(None) if Instant::now() >= deadline => {
+                    return StepResult::fail_with_kind(
+                        FailureKind::Timeout,
+                        format!(
+                            "tx {} wasn't applied after {}s",
+                            paramaters.wrapper_hash, paramaters.timeout
+                        ),
+                        StepStorage::default(),
+                    )
+                }
+                Ok(None) => sleep(POLL_INTERVAL).await,
+                Err(e) => return StepResult::fail_rpc(e.to_string()),
+            }
+        };
+
+        let mut storage = StepStorage::default();
+        storage.add(
+            TxStorageKeys::InclusionHeight.to_string(),
+            response.height.to_string(),
+        );
+        storage.add(
+            TxStorageKeys::GasUsed.to_string(),
+            response.gas_used.to_string(),
+        );
+
+        // the wrapper itself was rejected
+        let Some(batch) = &response.batch else {
+            storage.add("status".to_string(), "rejected".to_string());
+            return StepResult::fail_with_kind(
+                FailureKind::WrapperRejection,
+                response.info.clone(),
+                storage,
+            );
+        };
+
+        let wrapper_hash = Hash::from_str(&paramaters.wrapper_hash).ok();
+        let results = InnerTxResult::collect(
+            batch,
+            wrapper_hash.as_ref(),
+            paramaters
+                .inner_hashes
+                .iter()
+                .map(|(_, hash)| either::Left(hash)),
+            paramaters.atomic,
+        );
+        let indexes = paramaters
+            .inner_hashes
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<usize>>();
+        InnerTxResult::record(&results, &indexes, &mut storage);
+
+        if InnerTxResult::all_applied(&results) {
+            storage.add("status".to_string(), "applied".to_string());
+            return StepResult::success(storage);
+        }
+        storage.add("status".to_string(), "rejected".to_string());
+        let errors = storage
+            .get_field(&TxStorageKeys::VpErrors.to_string())
+            .unwrap_or_default();
+        StepResult::fail_with_kind(FailureKind::VpRejection, errors, storage)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TxWaitParametersDto {
+    /// a reference to the `wrapper-hash` of a tx step
+    pub tx: Value,
+    pub timeout: Option<Value>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TxWaitParameters {
+    pub wrapper_hash: String,
+    /// the hashes of the inner txs, with the index the tx step recorded them under
+    pub inner_hashes: Vec<(usize, Hash)>,
+    pub atomic: bool,
+    /// seconds
+    pub timeout: u64,
+}
+
+impl WaitParam for TxWaitParameters {
+    type D = TxWaitParametersDto;
+
+    fn from_dto(dto: Self::D, state: &Storage) -> Result<Option<Self>, ParameterError> {
+        let resolver = Resolver::new(state);
+
+        // the inner tx hashes are read from the step the reference points at
+        let Value::Ref { value: step, .. } = dto.tx else {
+            return Err(ParameterError::new(
+                "tx",
+                "must be a reference to a tx step",
+            ));
+        };
+        let Some(wrapper_hash) = resolver.string("tx", dto.tx)? else {
+            return Ok(None);
+        };
+        // a batch step records its inner txs under the index of their entry
+        let inner_hashes = state
+            .step_states
+            .get(&step)
+            .map(|storage| {
+                storage
+                    .storage
+                    .iter()
+                    .filter_map(|(key, hash)| {
+                        let index = key
+                            .strip_prefix("inner-tx-")?
+                            .strip_suffix("-hash")?
+                            .parse::<usize>()
+                            .ok()?;
+                        Some((index, hash))
+                    })
+                    .map(|(index, hash)| {
+                        Hash::from_str(hash)
+                            .map(|hash| (index, hash))
+                            .map_err(|e| ParameterError::new("tx", e))
+                    })
+                    .collect::<Result<Vec<(usize, Hash)>, ParameterError>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let atomic = match state.get_step_item(&step, &TxStorageKeys::Atomic.to_string()) {
+            Ok(atomic) => atomic
+                .parse::<bool>()
+                .map_err(|e| ParameterError::new("tx", e))?,
+            Err(_) => false,
+        };
+
+        let timeout = match dto.timeout {
+            Some(Value::Ref { .. }) => return Err(ParameterError::ref_unsupported("timeout")),
+            value => resolver
+                .optional("timeout", value, Resolver::number)?
+                .flatten()
+                .unwrap_or(DEFAULT_TIMEOUT),
+        };
+
+        Ok(Some(Self {
+            wrapper_hash,
+            inner_hashes,
+            atomic,
+            timeout,
+        }))
+    }
+}